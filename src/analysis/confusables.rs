@@ -0,0 +1,207 @@
+use core::fmt;
+
+use hashbrown::HashMap;
+use locspan::Span;
+
+use crate::{object::Key, CodeMap, Object};
+
+/// Key fold under which two distinct keys are equal.
+///
+/// Reported by [`find_confusable_keys`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum KeyFold {
+	/// Keys only differ by case (e.g. `"Name"` and `"name"`).
+	Case,
+
+	/// Keys are equal after Unicode compatibility normalization (NFKC), and
+	/// possibly case folding (e.g. `"ｎａｍｅ"` and `"name"`).
+	///
+	/// Only reported when the `unicode-normalization` feature is enabled.
+	Normalization,
+}
+
+impl fmt::Display for KeyFold {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Case => write!(f, "keys only differ by case"),
+			Self::Normalization => write!(f, "keys are equal after Unicode normalization"),
+		}
+	}
+}
+
+/// Pair of distinct object keys that are equal under some [`KeyFold`].
+///
+/// Reported by [`find_confusable_keys`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfusableKeys<'a> {
+	/// Position of the first entry in the object.
+	pub first: usize,
+
+	/// Position of the second entry in the object.
+	pub second: usize,
+
+	/// Keys of the first and second entries.
+	pub keys: [&'a Key; 2],
+
+	/// Fold under which both keys are equal.
+	pub fold: KeyFold,
+
+	/// Spans of the first and second keys, if a code map was provided.
+	pub spans: Option<[Span; 2]>,
+}
+
+/// Folds the given key.
+fn fold(key: &str) -> (String, Option<String>) {
+	let case = key.to_lowercase();
+
+	#[cfg(feature = "unicode-normalization")]
+	{
+		use unicode_normalization::UnicodeNormalization;
+		let normalized: String = key.nfkc().collect::<String>().to_lowercase();
+		let normalized = normalized.nfkc().collect();
+		(case, Some(normalized))
+	}
+
+	#[cfg(not(feature = "unicode-normalization"))]
+	(case, None)
+}
+
+/// Finds pairs of distinct keys of `object` that are equal after case
+/// folding or Unicode normalization.
+///
+/// Such keys are likely to be a configuration mistake, or a spoofing attempt.
+/// Identical keys are not reported. Pairs are returned in entry order.
+///
+/// Unicode normalization is only performed when the `unicode-normalization`
+/// feature is enabled. Use [`find_confusable_keys_mapped`] to also get the
+/// key spans.
+///
+/// # Example
+///
+/// ```
+/// use json_syntax::{json, analysis::{find_confusable_keys, KeyFold}};
+///
+/// let value = json!({ "name": 0, "id": 1, "Name": 2 });
+/// let pairs = find_confusable_keys(value.as_object().unwrap());
+///
+/// assert_eq!(pairs.len(), 1);
+/// assert_eq!((pairs[0].first, pairs[0].second), (0, 2));
+/// assert_eq!(pairs[0].fold, KeyFold::Case);
+/// ```
+pub fn find_confusable_keys(object: &Object) -> Vec<ConfusableKeys<'_>> {
+	let mut groups: HashMap<String, Vec<usize>> = HashMap::new();
+	let mut result = Vec::new();
+
+	let folds: Vec<_> = object.iter().map(|e| fold(&e.key)).collect();
+	for (j, (case, normalized)) in folds.iter().enumerate() {
+		let group = groups
+			.entry(normalized.as_ref().unwrap_or(case).clone())
+			.or_default();
+
+		for &i in group.iter() {
+			let (a, b) = (&object.entries()[i].key, &object.entries()[j].key);
+			if a != b {
+				let fold = if folds[i].0 == *case {
+					KeyFold::Case
+				} else {
+					KeyFold::Normalization
+				};
+
+				result.push(ConfusableKeys {
+					first: i,
+					second: j,
+					keys: [a, b],
+					fold,
+					spans: None,
+				})
+			}
+		}
+
+		group.push(j)
+	}
+
+	result.sort_by_key(|p| (p.first, p.second));
+	result
+}
+
+/// Finds confusable keys of `object` like [`find_confusable_keys`], also
+/// providing the key spans.
+///
+/// The object value is expected to be at fragment index `offset` in the
+/// given code map (`0` if it is the root value).
+///
+/// # Example
+///
+/// ```
+/// use json_syntax::{Parse, Value, analysis::find_confusable_keys_mapped};
+///
+/// let (value, code_map) = Value::parse_str(r#"{ "id": 0, "ID": 1 }"#).unwrap();
+/// let pairs = find_confusable_keys_mapped(value.as_object().unwrap(), &code_map, 0);
+///
+/// let [a, b] = pairs[0].spans.unwrap();
+/// assert_eq!((a.range(), b.range()), (2..6, 11..15));
+/// ```
+pub fn find_confusable_keys_mapped<'a>(
+	object: &'a Object,
+	code_map: &CodeMap,
+	offset: usize,
+) -> Vec<ConfusableKeys<'a>> {
+	let mut key_spans = Vec::with_capacity(object.len());
+	let mut entry_offset = offset + 1;
+	for _ in object {
+		let entry = code_map.get(entry_offset);
+		key_spans.push(code_map.get(entry_offset + 1).map(|key| key.span));
+		entry_offset += entry.map(|e| e.volume).unwrap_or(1).max(1)
+	}
+
+	let mut result = find_confusable_keys(object);
+	for pair in &mut result {
+		pair.spans = key_spans[pair.first]
+			.zip(key_spans[pair.second])
+			.map(|(a, b)| [a, b])
+	}
+
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::json;
+
+	#[test]
+	fn confusable_keys() {
+		let value = json!({ "a": 0, "A": 1, "b": 2, "a": 3, "B": 4 });
+		let pairs: Vec<_> = find_confusable_keys(value.as_object().unwrap())
+			.into_iter()
+			.map(|p| (p.first, p.second, p.fold))
+			.collect();
+
+		assert_eq!(
+			pairs,
+			[
+				(0, 1, KeyFold::Case),
+				(1, 3, KeyFold::Case),
+				(2, 4, KeyFold::Case)
+			]
+		)
+	}
+
+	#[cfg(feature = "unicode-normalization")]
+	#[test]
+	fn confusable_keys_normalization() {
+		let value = json!({ "ﬁle": 0, "File": 1, "e\u{301}": 2, "é": 3 });
+		let pairs: Vec<_> = find_confusable_keys(value.as_object().unwrap())
+			.into_iter()
+			.map(|p| (p.first, p.second, p.fold))
+			.collect();
+
+		assert_eq!(
+			pairs,
+			[
+				(0, 1, KeyFold::Normalization),
+				(2, 3, KeyFold::Normalization)
+			]
+		)
+	}
+}
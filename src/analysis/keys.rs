@@ -0,0 +1,133 @@
+use core::fmt;
+
+use super::visit;
+use crate::{code_map::Mapped, FragmentRef, Value};
+
+/// Object key policy.
+///
+/// Rules object keys must follow, used by [`Value::validate_keys`].
+/// By default, no rule is enforced.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct KeyPolicy {
+	/// Maximum key length, in characters.
+	pub max_len: Option<usize>,
+
+	/// Allowed key characters.
+	pub charset: Option<fn(char) -> bool>,
+
+	/// Whether or not to forbid control characters (`U+0000` to `U+001F`,
+	/// and `U+007F` to `U+009F`).
+	pub no_control_chars: bool,
+
+	/// Whether or not keys must be unique in each object.
+	pub unique: bool,
+}
+
+impl KeyPolicy {
+	/// Checks the given key against this policy.
+	///
+	/// Uniqueness is not checked, since it depends on the object containing
+	/// the key.
+	pub fn check(&self, key: &str, issues: &mut Vec<KeyIssue>) {
+		if let Some(max) = self.max_len {
+			let len = key.chars().count();
+			if len > max {
+				issues.push(KeyIssue::TooLong { len, max })
+			}
+		}
+
+		for c in key.chars() {
+			if self.no_control_chars && c.is_control() {
+				issues.push(KeyIssue::ControlChar(c))
+			} else if let Some(charset) = self.charset {
+				if !charset(c) {
+					issues.push(KeyIssue::InvalidChar(c))
+				}
+			}
+		}
+	}
+}
+
+/// Object key issue.
+///
+/// Reported by [`Value::validate_keys`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyIssue {
+	/// Key is longer than the maximum length.
+	TooLong { len: usize, max: usize },
+
+	/// Key contains a character outside of the allowed charset.
+	InvalidChar(char),
+
+	/// Key contains a control character.
+	ControlChar(char),
+
+	/// Key is already used by a previous entry of the same object.
+	Duplicate,
+}
+
+impl fmt::Display for KeyIssue {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::TooLong { len, max } => {
+				write!(f, "key is too long ({len} characters, maximum is {max})")
+			}
+			Self::InvalidChar(c) => write!(f, "invalid key character `{}`", c.escape_debug()),
+			Self::ControlChar(c) => write!(f, "control character `{}` in key", c.escape_debug()),
+			Self::Duplicate => write!(f, "duplicate key"),
+		}
+	}
+}
+
+impl std::error::Error for KeyIssue {}
+
+impl Value {
+	/// Checks every object key of this value, recursively, against the given
+	/// policy.
+	///
+	/// Each issue is mapped to the fragment index of the offending key, that
+	/// can be used to find its span in the code map (see
+	/// [`analysis::span_of`](crate::analysis::span_of)).
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{Parse, Value, analysis::{self, KeyIssue, KeyPolicy}};
+	///
+	/// let (value, code_map) = Value::parse_str(r#"{ "a": 0, "a": { "b c": 1 } }"#).unwrap();
+	///
+	/// let policy = KeyPolicy {
+	///   charset: Some(|c| c.is_ascii_alphanumeric()),
+	///   unique: true,
+	///   ..Default::default()
+	/// };
+	///
+	/// let issues = value.validate_keys(&policy);
+	/// assert_eq!(issues.len(), 2);
+	/// assert_eq!(issues[0].value, KeyIssue::Duplicate);
+	/// assert_eq!(analysis::span_of(&code_map, &issues[0]).unwrap().range(), 10..13);
+	/// assert_eq!(issues[1].value, KeyIssue::InvalidChar(' '));
+	/// ```
+	pub fn validate_keys(&self, policy: &KeyPolicy) -> Vec<Mapped<KeyIssue>> {
+		let mut result = Vec::new();
+		let mut issues = Vec::new();
+
+		visit(self, |i, fragment, parent| {
+			if let FragmentRef::Entry(entry) = fragment {
+				policy.check(&entry.key, &mut issues);
+
+				if policy.unique {
+					if let Some((object, j)) = parent {
+						if object.index_of(&entry.key) != Some(j) {
+							issues.push(KeyIssue::Duplicate)
+						}
+					}
+				}
+
+				result.extend(issues.drain(..).map(|issue| Mapped::new(i + 1, issue)))
+			}
+		});
+
+		result
+	}
+}
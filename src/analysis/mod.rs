@@ -0,0 +1,53 @@
+//! Analysis passes over JSON values.
+use crate::{code_map::Mapped, object::Entry, CodeMap, FragmentRef, Object, Value};
+use locspan::Span;
+use smallvec::SmallVec;
+
+mod confusables;
+mod keys;
+mod numbers;
+
+pub use confusables::*;
+pub use keys::*;
+pub use numbers::*;
+
+/// Fragment of a value, with the object containing it (and its position in
+/// the object) if it is an entry.
+type ParentedFragment<'a> = (FragmentRef<'a>, Option<(&'a Object, usize)>);
+
+/// Visits every fragment of `value` in [`CodeMap`] order, along with its
+/// fragment index and, for entries, the object containing it and the entry
+/// position in the object.
+pub(crate) fn visit<'a>(
+	value: &'a Value,
+	mut f: impl FnMut(usize, FragmentRef<'a>, Option<(&'a Object, usize)>),
+) {
+	let mut stack: SmallVec<[ParentedFragment<'a>; 8]> = SmallVec::new();
+	stack.push((FragmentRef::Value(value), None));
+
+	let mut index = 0;
+	while let Some((fragment, parent)) = stack.pop() {
+		match fragment {
+			FragmentRef::Value(Value::Object(o)) => stack.extend(
+				o.entries()
+					.iter()
+					.enumerate()
+					.rev()
+					.map(|(i, e)| (FragmentRef::Entry(e), Some((o, i)))),
+			),
+			FragmentRef::Entry(Entry { key, value }) => {
+				stack.push((FragmentRef::Value(value), None));
+				stack.push((FragmentRef::Key(key), None))
+			}
+			fragment => stack.extend(fragment.sub_fragments().rev().map(|f| (f, None))),
+		}
+
+		f(index, fragment, parent);
+		index += 1
+	}
+}
+
+/// Returns the span of the given mapped item, if it is in the code map.
+pub fn span_of<T>(code_map: &CodeMap, item: &Mapped<T>) -> Option<Span> {
+	code_map.get(item.offset).map(|entry| entry.span)
+}
@@ -0,0 +1,187 @@
+use core::fmt;
+use locspan::Span;
+
+use crate::{CodeMap, FragmentRef, Number, Value};
+
+/// Largest integer that can be exactly represented by a `f64` (and a
+/// JavaScript number), along with all smaller integers.
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+/// Precision-risky number issue.
+///
+/// See [`find_unsafe_numbers`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NumberIssue {
+	/// Integer that fits neither in a `i64` nor in a `u64`.
+	IntegerOutOfRange,
+
+	/// Integer outside of the `[-(2^53 - 1), 2^53 - 1]` range, that may not be
+	/// exactly represented as a `f64`.
+	UnsafeInteger,
+
+	/// Number too large to be represented as a (finite) `f64`.
+	Overflow,
+
+	/// Number that does not round-trip through `f64`: the shortest decimal
+	/// representation of the nearest `f64` has a different value.
+	PrecisionLoss,
+}
+
+impl fmt::Display for NumberIssue {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::IntegerOutOfRange => write!(f, "integer out of 64-bit range"),
+			Self::UnsafeInteger => write!(f, "integer out of the `f64` safe range"),
+			Self::Overflow => write!(f, "number out of `f64` range"),
+			Self::PrecisionLoss => write!(f, "number does not round-trip through `f64`"),
+		}
+	}
+}
+
+/// Number that may be corrupted by consumers using `f64` or 64-bit integers.
+#[derive(Debug, Clone, Copy)]
+pub struct UnsafeNumber<'a> {
+	/// Fragment index of the number.
+	pub offset: usize,
+
+	/// Span of the number in the source, if the code map has an entry for
+	/// it.
+	pub span: Option<Span>,
+
+	/// Number.
+	pub number: &'a Number,
+
+	/// Issue.
+	pub issue: NumberIssue,
+}
+
+/// Checks if the given number can be safely used by consumers using `f64`
+/// or 64-bit integers.
+pub fn check_number(n: &Number) -> Option<NumberIssue> {
+	let s = n.as_str();
+	if s.bytes().all(|b| b == b'-' || b.is_ascii_digit()) {
+		match s.parse::<i64>() {
+			Ok(i) => (i.unsigned_abs() > MAX_SAFE_INTEGER).then_some(NumberIssue::UnsafeInteger),
+			Err(_) => match s.parse::<u64>() {
+				Ok(_) => Some(NumberIssue::UnsafeInteger),
+				Err(_) => Some(NumberIssue::IntegerOutOfRange),
+			},
+		}
+	} else {
+		let f = n.as_f64_lossy();
+		if !f.is_finite() {
+			Some(NumberIssue::Overflow)
+		} else {
+			match (decimal(s), decimal(&format!("{f:e}"))) {
+				(Some(a), Some(b)) if a == b => None,
+				_ => Some(NumberIssue::PrecisionLoss),
+			}
+		}
+	}
+}
+
+/// Normalized decimal number: sign, significant digits and exponent.
+type Decimal = (bool, std::string::String, i64);
+
+/// Normalizes the given number lexical representation.
+fn decimal(s: &str) -> Option<Decimal> {
+	let (negative, s) = match s.strip_prefix('-') {
+		Some(s) => (true, s),
+		None => (false, s),
+	};
+
+	let (mantissa, mut exponent) = match s.find(['e', 'E']) {
+		Some(i) => (
+			&s[..i],
+			s[(i + 1)..].trim_start_matches('+').parse::<i64>().ok()?,
+		),
+		None => (s, 0),
+	};
+
+	let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+	let digits = format!("{integer}{fraction}");
+	exponent -= fraction.len() as i64;
+
+	let digits = digits.trim_start_matches('0');
+	let trimmed = digits.trim_end_matches('0');
+	exponent += (digits.len() - trimmed.len()) as i64;
+
+	if trimmed.is_empty() {
+		Some((false, std::string::String::new(), 0))
+	} else {
+		Some((negative, trimmed.to_owned(), exponent))
+	}
+}
+
+/// Finds all the numbers of `value` that do not round-trip through `f64`,
+/// or that exceed the 64-bit integer ranges.
+///
+/// Such numbers may be silently corrupted by consumers (e.g. JavaScript
+/// clients parsing large identifiers).
+///
+/// It is assumed that the offset of `value` in the code map is `0`, for
+/// instance if it is the output of a [`Parse`](crate::Parse) trait function.
+///
+/// # Example
+///
+/// ```
+/// use json_syntax::{Parse, Value, analysis::{find_unsafe_numbers, NumberIssue}};
+///
+/// let (value, code_map) = Value::parse_str(r#"{ "id": 9007199254740993, "x": 0.5 }"#).unwrap();
+/// let numbers = find_unsafe_numbers(&value, &code_map);
+///
+/// assert_eq!(numbers.len(), 1);
+/// assert_eq!(numbers[0].issue, NumberIssue::UnsafeInteger);
+/// assert_eq!(numbers[0].span.unwrap().range(), 8..24);
+/// ```
+pub fn find_unsafe_numbers<'a>(value: &'a Value, code_map: &CodeMap) -> Vec<UnsafeNumber<'a>> {
+	value
+		.traverse()
+		.filter_map(|(offset, fragment)| match fragment {
+			FragmentRef::Value(Value::Number(n)) => check_number(n).map(|issue| UnsafeNumber {
+				offset,
+				span: code_map.get(offset).map(|entry| entry.span),
+				number: n,
+				issue,
+			}),
+			_ => None,
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::NumberBuf;
+
+	fn check(s: &str) -> Option<NumberIssue> {
+		check_number(&NumberBuf::new(s.as_bytes().into()).unwrap())
+	}
+
+	#[test]
+	fn unsafe_numbers() {
+		assert_eq!(check("0"), None);
+		assert_eq!(check("-12"), None);
+		assert_eq!(check("9007199254740991"), None);
+		assert_eq!(check("-9007199254740992"), Some(NumberIssue::UnsafeInteger));
+		assert_eq!(
+			check("18446744073709551615"),
+			Some(NumberIssue::UnsafeInteger)
+		);
+		assert_eq!(
+			check("18446744073709551616"),
+			Some(NumberIssue::IntegerOutOfRange)
+		);
+		assert_eq!(check("0.5"), None);
+		assert_eq!(check("4.50"), None);
+		assert_eq!(check("-0.0e10"), None);
+		assert_eq!(check("1E30"), None);
+		assert_eq!(check("0.1"), None);
+		assert_eq!(check("1e400"), Some(NumberIssue::Overflow));
+		assert_eq!(
+			check("0.12345678901234567890123"),
+			Some(NumberIssue::PrecisionLoss)
+		);
+		assert_eq!(check("1e-400"), Some(NumberIssue::PrecisionLoss));
+	}
+}
@@ -0,0 +1,237 @@
+//! [`Arbitrary`] implementation for property testing and fuzzing.
+//!
+//! The generated values can be tuned using [`Options`] and
+//! [`Value::arbitrary_with`].
+//!
+//! # Example
+//!
+//! ```
+//! use arbitrary::{Arbitrary, Unstructured};
+//! use json_syntax::{arbitrary::Options, Parse, Value};
+//!
+//! let bytes: Vec<u8> = (0..1024u32).map(|i| (i * 37 % 251) as u8).collect();
+//! let mut u = Unstructured::new(&bytes);
+//!
+//! let options = Options {
+//!   max_depth: 2,
+//!   ..Default::default()
+//! };
+//!
+//! let value = Value::arbitrary_with(&mut u, &options).unwrap();
+//! assert!(value.depth() <= 2);
+//!
+//! // Generated values always print as valid JSON.
+//! let printed = value.to_string();
+//! assert_eq!(Value::parse_str(&printed).unwrap().0, value);
+//! ```
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{Kind, NumberBuf, Object, Value};
+
+/// Generation options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Options {
+	/// Maximum nesting depth of the generated values.
+	///
+	/// Arrays and objects are not generated at this depth.
+	pub max_depth: usize,
+
+	/// Maximum number of items per array and entries per object.
+	pub max_len: usize,
+
+	/// Maximum number of characters per string and object key.
+	pub max_string_len: usize,
+
+	/// Relative probability of each kind of value.
+	pub weights: KindWeights,
+}
+
+impl Default for Options {
+	fn default() -> Self {
+		Self {
+			max_depth: 4,
+			max_len: 8,
+			max_string_len: 32,
+			weights: KindWeights::default(),
+		}
+	}
+}
+
+/// Relative probability of each kind of value.
+///
+/// A kind with weight `0` is never generated. By default every kind has
+/// weight `1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KindWeights {
+	pub null: u32,
+	pub boolean: u32,
+	pub number: u32,
+	pub string: u32,
+	pub array: u32,
+	pub object: u32,
+}
+
+impl KindWeights {
+	/// Returns the weight of the given kind.
+	pub fn get(&self, kind: Kind) -> u32 {
+		match kind {
+			Kind::Null => self.null,
+			Kind::Boolean => self.boolean,
+			Kind::Number => self.number,
+			Kind::String => self.string,
+			Kind::Array => self.array,
+			Kind::Object => self.object,
+		}
+	}
+
+	/// Selects a kind, excluding arrays and objects if `leaf` is `true`.
+	///
+	/// Returns `Kind::Null` if all the candidate weights are `0`.
+	fn select(&self, u: &mut Unstructured, leaf: bool) -> Result<Kind> {
+		let kinds = [
+			Kind::Null,
+			Kind::Boolean,
+			Kind::Number,
+			Kind::String,
+			Kind::Array,
+			Kind::Object,
+		];
+		let candidates = if leaf { &kinds[..4] } else { &kinds[..] };
+
+		let total: u64 = candidates.iter().map(|k| self.get(*k) as u64).sum();
+		if total == 0 {
+			return Ok(Kind::Null);
+		}
+
+		let mut n = u.int_in_range(0..=total - 1)?;
+		for kind in candidates {
+			let weight = self.get(*kind) as u64;
+			if n < weight {
+				return Ok(*kind);
+			}
+
+			n -= weight
+		}
+
+		unreachable!()
+	}
+}
+
+impl Default for KindWeights {
+	fn default() -> Self {
+		Self {
+			null: 1,
+			boolean: 1,
+			number: 1,
+			string: 1,
+			array: 1,
+			object: 1,
+		}
+	}
+}
+
+impl Value {
+	/// Generates an arbitrary value with the given options.
+	pub fn arbitrary_with(u: &mut Unstructured, options: &Options) -> Result<Self> {
+		arbitrary_value(u, options, 0)
+	}
+}
+
+impl<'a> Arbitrary<'a> for Value {
+	/// Generates an arbitrary value with the default [`Options`].
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		Self::arbitrary_with(u, &Options::default())
+	}
+}
+
+fn arbitrary_value(u: &mut Unstructured, options: &Options, depth: usize) -> Result<Value> {
+	let kind = options.weights.select(u, depth >= options.max_depth)?;
+	Ok(match kind {
+		Kind::Null => Value::Null,
+		Kind::Boolean => Value::Boolean(u.arbitrary()?),
+		Kind::Number => Value::Number(arbitrary_number(u)?),
+		Kind::String => Value::String(arbitrary_string(u, options)?.into()),
+		Kind::Array => {
+			let len = u.int_in_range(0..=options.max_len)?;
+			let mut items = Vec::with_capacity(len);
+			for _ in 0..len {
+				items.push(arbitrary_value(u, options, depth + 1)?)
+			}
+
+			Value::Array(items)
+		}
+		Kind::Object => {
+			let len = u.int_in_range(0..=options.max_len)?;
+			let mut object = Object::with_capacity(len);
+			for _ in 0..len {
+				let key = arbitrary_string(u, options)?;
+				object.push(key.as_str().into(), arbitrary_value(u, options, depth + 1)?);
+			}
+
+			Value::Object(object)
+		}
+	})
+}
+
+fn arbitrary_number(u: &mut Unstructured) -> Result<NumberBuf> {
+	Ok(match u.int_in_range(0u8..=2)? {
+		0 => i64::arbitrary(u)?.into(),
+		1 => u64::arbitrary(u)?.into(),
+		_ => {
+			let f = f64::arbitrary(u)?;
+			NumberBuf::try_from(f).unwrap_or_else(|_| 0u8.into())
+		}
+	})
+}
+
+fn arbitrary_string(u: &mut Unstructured, options: &Options) -> Result<String> {
+	let s = <&str>::arbitrary(u)?;
+	Ok(s.chars().take(options.max_string_len).collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Parse;
+
+	#[test]
+	fn round_trip() {
+		let options = Options {
+			max_depth: 3,
+			max_len: 4,
+			..Default::default()
+		};
+
+		for seed in 0..256u32 {
+			let bytes: Vec<u8> = (0..4096u32)
+				.map(|i| (i.wrapping_mul(seed * 2 + 1) >> 3) as u8)
+				.collect();
+			let mut u = Unstructured::new(&bytes);
+			let value = Value::arbitrary_with(&mut u, &options).unwrap();
+
+			assert!(value.depth() <= options.max_depth);
+			assert_eq!(Value::parse_str(&value.to_string()).unwrap().0, value)
+		}
+	}
+
+	#[test]
+	fn weights() {
+		let options = Options {
+			weights: KindWeights {
+				null: 0,
+				boolean: 0,
+				number: 0,
+				string: 0,
+				array: 0,
+				object: 1,
+			},
+			..Default::default()
+		};
+
+		let mut u = Unstructured::new(&[0xff; 64]);
+		let value = Value::arbitrary_with(&mut u, &options).unwrap();
+		assert!(value
+			.traverse()
+			.all(|(_, f)| !f.is_value() || f.is_object() || f.is_null()))
+	}
+}
@@ -1,4 +1,12 @@
-use crate::{code_map::Mapped, CodeMap, Value};
+use core::{cmp::Ordering, fmt};
+use std::collections::{BTreeMap, HashSet};
+
+use locspan::Span;
+
+use crate::{
+	code_map::Mapped, object::Key, CodeMap, Kind, KindSet, Number, Object, UnorderedPartialEq,
+	Value,
+};
 
 /// Array.
 pub type Array = Vec<Value>;
@@ -45,3 +53,458 @@ impl<'a, 'm> Iterator for IterMapped<'a, 'm> {
 		})
 	}
 }
+
+/// Array item kind mismatch error.
+///
+/// Returned by the checked bulk accessors of [`Value`], such as
+/// [`Value::as_slice_of_str`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ItemKindMismatch {
+	/// Index of the first offending item, or `None` if the value itself is
+	/// not an array.
+	pub index: Option<usize>,
+
+	/// Expected kind.
+	pub expected: KindSet,
+
+	/// Found kind.
+	pub found: Kind,
+}
+
+impl ItemKindMismatch {
+	/// Returns the span of the offending item (or array), given the code
+	/// map and the fragment index of the array.
+	pub fn span(&self, code_map: &CodeMap, offset: usize) -> Option<Span> {
+		let mut item_offset = offset;
+
+		if let Some(index) = self.index {
+			item_offset += 1;
+			for _ in 0..index {
+				item_offset += code_map.get(item_offset)?.volume
+			}
+		}
+
+		code_map.get(item_offset).map(|e| e.span)
+	}
+}
+
+impl fmt::Display for ItemKindMismatch {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.index {
+			Some(i) => write!(
+				f,
+				"expected {} at index {i}, found {}",
+				self.expected.as_disjunction(),
+				self.found
+			),
+			None => write!(f, "expected array, found {}", self.found),
+		}
+	}
+}
+
+impl std::error::Error for ItemKindMismatch {}
+
+macro_rules! slice_accessors {
+	($($(#[$meta:meta])* $name:ident: $kind:ident => $ty:ty = $accessor:ident),*) => {
+		impl Value {
+			$(
+				$(#[$meta])*
+				pub fn $name(&self) -> Result<Vec<$ty>, ItemKindMismatch> {
+					let items = self.as_array().ok_or(ItemKindMismatch {
+						index: None,
+						expected: KindSet::ARRAY,
+						found: self.kind(),
+					})?;
+
+					items
+						.iter()
+						.enumerate()
+						.map(|(i, item)| {
+							item.$accessor().ok_or(ItemKindMismatch {
+								index: Some(i),
+								expected: KindSet::$kind,
+								found: item.kind(),
+							})
+						})
+						.collect()
+				}
+			)*
+		}
+	};
+}
+
+slice_accessors! {
+	/// Returns the items of this array if they are all booleans.
+	as_slice_of_booleans: BOOLEAN => bool = as_boolean,
+
+	/// Returns the items of this array if they are all numbers.
+	as_slice_of_numbers: NUMBER => &Number = as_number,
+
+	/// Returns the items of this array if they are all strings.
+	///
+	/// Returns an error naming the first item that is not a string, whose
+	/// span can be retrieved with [`ItemKindMismatch::span`].
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, Kind};
+	///
+	/// let value = json!(["a", "b"]);
+	/// assert_eq!(value.as_slice_of_str().unwrap(), ["a", "b"]);
+	///
+	/// let value = json!(["a", 1, null]);
+	/// let e = value.as_slice_of_str().unwrap_err();
+	/// assert_eq!(e.index, Some(1));
+	/// assert_eq!(e.found, Kind::Number);
+	/// ```
+	as_slice_of_str: STRING => &str = as_str,
+
+	/// Returns the items of this array if they are all arrays.
+	as_slice_of_arrays: ARRAY => &[Value] = as_array,
+
+	/// Returns the items of this array if they are all objects.
+	as_slice_of_objects: OBJECT => &Object = as_object
+}
+
+/// Position of the items missing the sort key, used by
+/// [`sort_objects_by_key`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Missing {
+	/// Items missing the key are placed before the other items.
+	First,
+
+	/// Items missing the key are placed after the other items.
+	#[default]
+	Last,
+}
+
+/// Sorts an array of objects by the value of the given key, using the
+/// `compare` function to compare values.
+///
+/// Items that are not objects, or objects without the given key, are placed
+/// first or last according to `missing`. If the key appears more than once
+/// in an object, its first value is used. The sort is stable.
+///
+/// # Example
+///
+/// ```
+/// use json_syntax::{json, array::{sort_objects_by_key, Missing}};
+///
+/// let mut value = json!([
+///   { "name": "b" },
+///   { "id": 0 },
+///   { "name": "a" },
+///   null
+/// ]);
+///
+/// sort_objects_by_key(value.as_array_mut().unwrap(), "name", Missing::First, |a, b| {
+///   a.as_str().cmp(&b.as_str())
+/// });
+///
+/// assert_eq!(value, json!([
+///   { "id": 0 },
+///   null,
+///   { "name": "a" },
+///   { "name": "b" }
+/// ]));
+/// ```
+pub fn sort_objects_by_key<F>(array: &mut [Value], key: &str, missing: Missing, mut compare: F)
+where
+	F: FnMut(&Value, &Value) -> Ordering,
+{
+	fn field<'a>(item: &'a Value, key: &str) -> Option<&'a Value> {
+		item.as_object().and_then(|o| o.get(key).next())
+	}
+
+	array.sort_by(|a, b| match (field(a, key), field(b, key)) {
+		(Some(a), Some(b)) => compare(a, b),
+		(Some(_), None) => match missing {
+			Missing::First => Ordering::Greater,
+			Missing::Last => Ordering::Less,
+		},
+		(None, Some(_)) => match missing {
+			Missing::First => Ordering::Less,
+			Missing::Last => Ordering::Greater,
+		},
+		(None, None) => Ordering::Equal,
+	})
+}
+
+/// Grouping error, returned by [`group_by_key`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GroupError {
+	/// The item at the given index is not an object.
+	NotAnObject(usize, Kind),
+
+	/// The item at the given index does not have the group key.
+	MissingKey(usize),
+
+	/// The group key value of the item at the given index is not a string.
+	NotAString(usize, Kind),
+}
+
+impl GroupError {
+	/// Returns the index of the item causing the error.
+	pub fn index(&self) -> usize {
+		match self {
+			Self::NotAnObject(i, _) | Self::MissingKey(i) | Self::NotAString(i, _) => *i,
+		}
+	}
+}
+
+impl fmt::Display for GroupError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::NotAnObject(i, kind) => write!(f, "item {i} is not an object, but {kind}"),
+			Self::MissingKey(i) => write!(f, "item {i} does not have the group key"),
+			Self::NotAString(i, kind) => {
+				write!(f, "group key of item {i} is not a string, but {kind}")
+			}
+		}
+	}
+}
+
+impl std::error::Error for GroupError {}
+
+/// Returns the group key of the given array item.
+fn group_key<'a>(i: usize, item: &'a Value, key: &str) -> Result<&'a str, GroupError> {
+	let object = item
+		.as_object()
+		.ok_or_else(|| GroupError::NotAnObject(i, item.kind()))?;
+	let value = object.get(key).next().ok_or(GroupError::MissingKey(i))?;
+	value
+		.as_str()
+		.ok_or_else(|| GroupError::NotAString(i, value.kind()))
+}
+
+/// Groups the items of an array of objects by the (string) value of the
+/// given key.
+///
+/// Items keep their relative order in each group. If the key appears more
+/// than once in an object, its first value is used. See
+/// [`group_by_key_owned`] for the owned variant.
+///
+/// # Example
+///
+/// ```
+/// use json_syntax::{json, array::group_by_key};
+///
+/// let value = json!([
+///   { "team": "a", "name": "x" },
+///   { "team": "b", "name": "y" },
+///   { "team": "a", "name": "z" }
+/// ]);
+///
+/// let groups = group_by_key(value.as_array().unwrap(), "team").unwrap();
+/// assert_eq!(groups.len(), 2);
+/// assert_eq!(groups["a"].len(), 2);
+/// assert_eq!(groups["b"][0], &json!({ "team": "b", "name": "y" }));
+/// ```
+pub fn group_by_key<'a>(
+	array: &'a [Value],
+	key: &str,
+) -> Result<BTreeMap<Key, Vec<&'a Value>>, GroupError> {
+	let mut groups: BTreeMap<Key, Vec<&'a Value>> = BTreeMap::new();
+
+	for (i, item) in array.iter().enumerate() {
+		let group = group_key(i, item, key)?;
+		match groups.get_mut(group) {
+			Some(items) => items.push(item),
+			None => {
+				groups.insert(group.into(), vec![item]);
+			}
+		}
+	}
+
+	Ok(groups)
+}
+
+/// Groups the items of an array of objects by the (string) value of the
+/// given key, taking ownership of the items.
+///
+/// See [`group_by_key`].
+pub fn group_by_key_owned(
+	array: Array,
+	key: &str,
+) -> Result<BTreeMap<Key, Vec<Value>>, GroupError> {
+	let keys = array
+		.iter()
+		.enumerate()
+		.map(|(i, item)| group_key(i, item, key).map(Key::from))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let mut groups: BTreeMap<Key, Vec<Value>> = BTreeMap::new();
+	for (group, item) in keys.into_iter().zip(array) {
+		groups.entry(group).or_default().push(item)
+	}
+
+	Ok(groups)
+}
+
+/// Projects an array of objects on the given keys.
+///
+/// Returns an array containing, for each item, an object with only the
+/// entries whose key is in `keys`. Entries keep their original order, and
+/// duplicate entries are preserved. Items that are not objects are
+/// projected to empty objects.
+///
+/// # Example
+///
+/// ```
+/// use json_syntax::{json, array::project};
+///
+/// let value = json!([
+///   { "id": 0, "name": "a", "age": 25 },
+///   { "name": "b", "id": 1 },
+///   { "age": 30 }
+/// ]);
+///
+/// assert_eq!(
+///   project(value.as_array().unwrap(), &["id", "name"]),
+///   vec![
+///     json!({ "id": 0, "name": "a" }),
+///     json!({ "name": "b", "id": 1 }),
+///     json!({})
+///   ]
+/// )
+/// ```
+pub fn project(array: &[Value], keys: &[&str]) -> Array {
+	array
+		.iter()
+		.map(|item| {
+			let entries = item.as_object().into_iter().flat_map(|o| o.iter());
+			Value::Object(
+				entries
+					.filter(|e| keys.contains(&e.key.as_str()))
+					.cloned()
+					.collect::<Object>(),
+			)
+		})
+		.collect()
+}
+
+/// Removes duplicate items from an array, keeping the first occurrence of
+/// each item.
+///
+/// Contrarily to [`Vec::dedup`], duplicates do not need to be consecutive.
+/// Items are compared with [`PartialEq`], so objects with the same entries in
+/// a different order are considered distinct. See [`dedup_unordered`] to
+/// ignore entries order.
+///
+/// # Example
+///
+/// ```
+/// use json_syntax::{json, array::dedup};
+///
+/// let mut value = json!([1, "a", 1, { "b": 2, "c": 3 }, "a", { "c": 3, "b": 2 }]);
+/// dedup(value.as_array_mut().unwrap());
+/// assert_eq!(value, json!([1, "a", { "b": 2, "c": 3 }, { "c": 3, "b": 2 }]));
+/// ```
+pub fn dedup(array: &mut Array) {
+	let mut seen = HashSet::with_capacity(array.len());
+	let keep: Vec<bool> = array.iter().map(|item| seen.insert(item)).collect();
+	let mut keep = keep.into_iter();
+	array.retain(|_| keep.next().unwrap())
+}
+
+/// Removes duplicate items from an array, keeping the first occurrence of
+/// each item, ignoring the order of object entries.
+///
+/// Items are compared with [`UnorderedPartialEq`]. This takes quadratic time
+/// in the length of the array.
+///
+/// # Example
+///
+/// ```
+/// use json_syntax::{json, array::dedup_unordered};
+///
+/// let mut value = json!([{ "b": 2, "c": 3 }, 1, { "c": 3, "b": 2 }]);
+/// dedup_unordered(value.as_array_mut().unwrap());
+/// assert_eq!(value, json!([{ "b": 2, "c": 3 }, 1]));
+/// ```
+pub fn dedup_unordered(array: &mut Array) {
+	let mut i = 0;
+	while i < array.len() {
+		let (kept, rest) = array.split_at(i);
+		if kept.iter().any(|item| item.unordered_eq(&rest[0])) {
+			array.remove(i);
+		} else {
+			i += 1
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::json;
+
+	#[test]
+	fn sort_objects_by_key_missing_last() {
+		let mut array = vec![
+			json!({ "k": 2, "i": 0 }),
+			json!({ "i": 1 }),
+			json!({ "k": 1, "i": 2 }),
+			json!("x"),
+			json!({ "k": 2, "i": 4 }),
+		];
+
+		sort_objects_by_key(&mut array, "k", Missing::Last, |a, b| {
+			a.as_number()
+				.and_then(|n| n.as_u64())
+				.cmp(&b.as_number().and_then(|n| n.as_u64()))
+		});
+
+		assert_eq!(
+			array,
+			[
+				json!({ "k": 1, "i": 2 }),
+				json!({ "k": 2, "i": 0 }),
+				json!({ "k": 2, "i": 4 }),
+				json!({ "i": 1 }),
+				json!("x"),
+			]
+		)
+	}
+
+	#[test]
+	fn slice_accessors() {
+		use crate::Parse;
+		let (value, code_map) = Value::parse_str("[1, [2, 3], true]").unwrap();
+
+		let e = value.as_slice_of_numbers().unwrap_err();
+		assert_eq!(e.index, Some(1));
+		assert_eq!(e.span(&code_map, 0).unwrap().range(), 4..10);
+
+		let e = value.as_slice_of_booleans().unwrap_err();
+		assert_eq!(e.span(&code_map, 0).unwrap().range(), 1..2);
+
+		let e = Value::Null.as_slice_of_objects().unwrap_err();
+		assert_eq!((e.index, e.found), (None, Kind::Null));
+		assert_eq!(
+			value.as_array().unwrap()[1].as_slice_of_numbers().unwrap(),
+			[
+				crate::Number::new("2").unwrap(),
+				crate::Number::new("3").unwrap()
+			]
+		)
+	}
+
+	#[test]
+	fn group_by_key_errors() {
+		let array = vec![json!({ "g": "a" }), json!({ "h": "b" }), json!(null)];
+		assert_eq!(
+			group_by_key(&array, "g").unwrap_err(),
+			GroupError::MissingKey(1)
+		);
+		assert_eq!(
+			group_by_key_owned(vec![json!({ "g": "a" }), json!({ "g": 1 })], "g").unwrap_err(),
+			GroupError::NotAString(1, Kind::Number)
+		);
+		assert_eq!(
+			group_by_key(&array[2..], "g").unwrap_err(),
+			GroupError::NotAnObject(0, Kind::Null)
+		);
+	}
+}
@@ -0,0 +1,308 @@
+use core::fmt;
+
+use crate::{object::Key, print::printed_string_size, Array, CodeMap, Object, Value};
+
+/// Value builder error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuildError {
+	/// A value was given inside an object, without key.
+	ExpectedKey,
+
+	/// A key was given outside of an object, or right after another key.
+	UnexpectedKey,
+
+	/// [`ValueBuilder::end`] was called without any open array or object,
+	/// or right after a key.
+	UnexpectedEnd,
+
+	/// A value was given after the root value was completed.
+	Complete,
+
+	/// The builder was finished before the root value was completed.
+	Incomplete,
+}
+
+impl fmt::Display for BuildError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::ExpectedKey => write!(f, "expected object key"),
+			Self::UnexpectedKey => write!(f, "unexpected object key"),
+			Self::UnexpectedEnd => write!(f, "unexpected end of array or object"),
+			Self::Complete => write!(f, "value already complete"),
+			Self::Incomplete => write!(f, "incomplete value"),
+		}
+	}
+}
+
+impl std::error::Error for BuildError {}
+
+/// Array or object under construction.
+#[derive(Debug, Clone)]
+enum Frame {
+	Array(Array),
+	Object(Object, Option<Key>),
+}
+
+/// Stack-based value builder.
+///
+/// Builds a value from a stream of events, without requiring the producer
+/// to hold on to intermediate arrays and objects. This is useful to build
+/// values from streaming producers such as database cursors.
+///
+/// # Example
+///
+/// ```
+/// use json_syntax::{json, Print, ValueBuilder};
+///
+/// let mut builder = ValueBuilder::new();
+/// builder.begin_object()?;
+/// builder.key("rows")?.begin_array()?;
+/// for row in [1, 2, 3] {
+///   builder.value(row)?;
+/// }
+/// builder.end()?;
+/// builder.key("done")?.value(true)?;
+/// builder.end()?;
+///
+/// let (value, code_map) = builder.finish_with_code_map()?;
+/// assert_eq!(value, json!({ "rows": [1, 2, 3], "done": true }));
+///
+/// // The code map matches the compact printed form of the value.
+/// let printed = value.compact_print().to_string();
+/// assert_eq!(&printed[code_map[3].span.range()], "[1,2,3]");
+/// # Ok::<(), json_syntax::BuildError>(())
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ValueBuilder {
+	stack: Vec<Frame>,
+	result: Option<Value>,
+}
+
+impl ValueBuilder {
+	/// Creates a new builder.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the current nesting depth (number of open arrays and
+	/// objects).
+	pub fn depth(&self) -> usize {
+		self.stack.len()
+	}
+
+	/// Checks if the root value is complete.
+	pub fn is_complete(&self) -> bool {
+		self.result.is_some()
+	}
+
+	/// Opens an array.
+	///
+	/// Following values are added to this array until [`Self::end`] is
+	/// called.
+	pub fn begin_array(&mut self) -> Result<&mut Self, BuildError> {
+		self.check_value()?;
+		self.stack.push(Frame::Array(Array::new()));
+		Ok(self)
+	}
+
+	/// Opens an object.
+	///
+	/// Following entries are added to this object until [`Self::end`] is
+	/// called. Each entry value must be preceded by a call to [`Self::key`].
+	pub fn begin_object(&mut self) -> Result<&mut Self, BuildError> {
+		self.check_value()?;
+		self.stack.push(Frame::Object(Object::new(), None));
+		Ok(self)
+	}
+
+	/// Sets the key of the next object entry.
+	pub fn key(&mut self, key: impl Into<Key>) -> Result<&mut Self, BuildError> {
+		match self.stack.last_mut() {
+			Some(Frame::Object(_, pending @ None)) => {
+				*pending = Some(key.into());
+				Ok(self)
+			}
+			_ => Err(BuildError::UnexpectedKey),
+		}
+	}
+
+	/// Adds a value.
+	///
+	/// The value is pushed to the current array, inserted in the current
+	/// object (after a key), or becomes the root value.
+	pub fn value(&mut self, value: impl Into<Value>) -> Result<&mut Self, BuildError> {
+		self.check_value()?;
+		self.push(value.into());
+		Ok(self)
+	}
+
+	/// Closes the current array or object.
+	pub fn end(&mut self) -> Result<&mut Self, BuildError> {
+		let value = match self.stack.pop() {
+			Some(Frame::Array(items)) => Value::Array(items),
+			Some(Frame::Object(object, None)) => Value::Object(object),
+			Some(frame) => {
+				self.stack.push(frame);
+				return Err(BuildError::UnexpectedEnd);
+			}
+			None => return Err(BuildError::UnexpectedEnd),
+		};
+
+		self.push(value);
+		Ok(self)
+	}
+
+	/// Returns the built value.
+	pub fn finish(self) -> Result<Value, BuildError> {
+		self.result.ok_or(BuildError::Incomplete)
+	}
+
+	/// Returns the built value with a synthetic code map.
+	///
+	/// The code map spans refer to the
+	/// [compact printed form](crate::Print::compact_print) of the value.
+	pub fn finish_with_code_map(self) -> Result<(Value, CodeMap), BuildError> {
+		let value = self.finish()?;
+		let mut code_map = CodeMap::default();
+		synthetic_code_map(&mut code_map, 0, &value);
+		Ok((value, code_map))
+	}
+
+	/// Checks that a value can be added.
+	fn check_value(&self) -> Result<(), BuildError> {
+		match self.stack.last() {
+			Some(Frame::Object(_, None)) => Err(BuildError::ExpectedKey),
+			Some(_) => Ok(()),
+			None if self.result.is_some() => Err(BuildError::Complete),
+			None => Ok(()),
+		}
+	}
+
+	fn push(&mut self, value: Value) {
+		match self.stack.last_mut() {
+			Some(Frame::Array(items)) => items.push(value),
+			Some(Frame::Object(object, key)) => {
+				object.push(key.take().unwrap(), value);
+			}
+			None => self.result = Some(value),
+		}
+	}
+}
+
+/// Adds the code map entries of the given value, compact printed at
+/// `offset`, returning the end offset.
+fn synthetic_code_map(code_map: &mut CodeMap, offset: usize, value: &Value) -> usize {
+	let i = code_map.reserve(offset);
+	let end = match value {
+		Value::Null | Value::Boolean(true) => offset + 4,
+		Value::Boolean(false) => offset + 5,
+		Value::Number(n) => offset + n.as_str().len(),
+		Value::String(s) => offset + printed_string_size(s),
+		Value::Array(items) => {
+			let mut end = offset + 1;
+			for (j, item) in items.iter().enumerate() {
+				if j > 0 {
+					end += 1
+				}
+
+				end = synthetic_code_map(code_map, end, item)
+			}
+
+			end + 1
+		}
+		Value::Object(object) => {
+			let mut end = offset + 1;
+			for (j, entry) in object.iter().enumerate() {
+				if j > 0 {
+					end += 1
+				}
+
+				let entry_i = code_map.reserve(end);
+				let key_i = code_map.reserve(end);
+				end += printed_string_size(&entry.key);
+				close(code_map, key_i, end);
+				end = synthetic_code_map(code_map, end + 1, &entry.value);
+				close(code_map, entry_i, end)
+			}
+
+			end + 1
+		}
+	};
+
+	close(code_map, i, end);
+	end
+}
+
+fn close(code_map: &mut CodeMap, i: usize, end: usize) {
+	let volume = code_map.len() - i;
+	let entry = code_map.get_mut(i).unwrap();
+	entry.span.set_end(end);
+	entry.volume = volume
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{json, FragmentRef, Print};
+
+	#[test]
+	fn errors() {
+		let mut builder = ValueBuilder::new();
+		assert_eq!(builder.key("a").err(), Some(BuildError::UnexpectedKey));
+		assert_eq!(builder.end().err(), Some(BuildError::UnexpectedEnd));
+
+		builder.begin_object().unwrap();
+		assert_eq!(builder.value(1).err(), Some(BuildError::ExpectedKey));
+		builder.key("a").unwrap();
+		assert_eq!(builder.key("b").err(), Some(BuildError::UnexpectedKey));
+		assert_eq!(builder.end().err(), Some(BuildError::UnexpectedEnd));
+		builder.value(1).unwrap().end().unwrap();
+
+		assert_eq!(builder.value(2).err(), Some(BuildError::Complete));
+		assert_eq!(builder.finish(), Ok(json!({ "a": 1 })));
+		assert_eq!(
+			ValueBuilder::new().begin_array().unwrap().clone().finish(),
+			Err(BuildError::Incomplete)
+		)
+	}
+
+	#[test]
+	fn code_map() {
+		let mut builder = ValueBuilder::new();
+		builder
+			.begin_array()
+			.unwrap()
+			.value(json!({ "a\n": [null, false], "b": {} }))
+			.unwrap()
+			.begin_object()
+			.unwrap()
+			.end()
+			.unwrap()
+			.value("x")
+			.unwrap()
+			.end()
+			.unwrap();
+
+		let (value, code_map) = builder.finish_with_code_map().unwrap();
+		let printed = value.compact_print().to_string();
+
+		assert_eq!(code_map.len(), value.traverse().count());
+		for ((_, entry), (_, fragment)) in code_map.iter().zip(value.traverse()) {
+			let expected = match fragment {
+				FragmentRef::Value(value) => value.compact_print().to_string(),
+				FragmentRef::Entry(entry) => {
+					format!(
+						"{}:{}",
+						Value::from(&*entry.key).compact_print(),
+						entry.value.compact_print()
+					)
+				}
+				FragmentRef::Key(key) => Value::from(&**key).compact_print().to_string(),
+			};
+
+			assert_eq!(&printed[entry.span.range()], expected)
+		}
+
+		assert_eq!(&printed[code_map[9].span.range()], "{}");
+	}
+}
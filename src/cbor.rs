@@ -0,0 +1,790 @@
+//! CBOR ([RFC 8949](https://www.rfc-editor.org/rfc/rfc8949)) conversion.
+//!
+//! Numbers are encoded as integers when they fit in 64 bits, and as double
+//! precision floats when this does not change their lexical representation.
+//! Other numbers are encoded as bignums (tags 2 and 3) or decimal fractions
+//! (tag 4), preserving their precision. Object entries are encoded in order,
+//! including duplicate entries.
+//!
+//! Converting bignums from and to decimal takes quadratic time, so bignums
+//! are limited to [`MAX_BIGNUM_LEN`] bytes: larger bignums are rejected when
+//! decoding, and larger numbers are encoded as (lossy) floats.
+use core::fmt;
+
+use crate::{object::Key, Number, NumberBuf, Object, Value};
+
+const TAG_POSITIVE_BIGNUM: u64 = 2;
+const TAG_NEGATIVE_BIGNUM: u64 = 3;
+const TAG_DECIMAL_FRACTION: u64 = 4;
+
+const BREAK: u8 = 0xff;
+
+/// Maximum length, in bytes, of encoded and decoded bignums.
+pub const MAX_BIGNUM_LEN: usize = 1024;
+
+/// Maximum number of decimal digits of an integer encoded as a bignum.
+///
+/// Any integer with at most this many digits fits in [`MAX_BIGNUM_LEN`]
+/// bytes (`256^1024 > 10^2466`).
+const MAX_BIGNUM_DIGITS: usize = 2466;
+
+/// CBOR decoding error.
+///
+/// Each variant (but `UnexpectedEnd`) holds the offset of the offending
+/// data item in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Error {
+	/// Unexpected end of input.
+	UnexpectedEnd,
+
+	/// Input bytes remain after the decoded data item.
+	TrailingBytes(usize),
+
+	/// Malformed data item.
+	Malformed(usize),
+
+	/// Data item with no JSON equivalent, such as a byte string or
+	/// `undefined`.
+	Unsupported(usize),
+
+	/// Map key that is not a text string.
+	InvalidKey(usize),
+
+	/// Text string that is not valid UTF-8.
+	InvalidUtf8(usize),
+
+	/// Infinite or NaN float.
+	NonFiniteFloat(usize),
+
+	/// Bignum longer than [`MAX_BIGNUM_LEN`] bytes.
+	BignumTooLarge(usize),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::UnexpectedEnd => write!(f, "unexpected end of CBOR input"),
+			Self::TrailingBytes(i) => write!(f, "trailing bytes at offset {i}"),
+			Self::Malformed(i) => write!(f, "malformed CBOR data item at offset {i}"),
+			Self::Unsupported(i) => write!(f, "unsupported CBOR data item at offset {i}"),
+			Self::InvalidKey(i) => write!(f, "non-text map key at offset {i}"),
+			Self::InvalidUtf8(i) => write!(f, "invalid UTF-8 text string at offset {i}"),
+			Self::NonFiniteFloat(i) => write!(f, "non-finite float at offset {i}"),
+			Self::BignumTooLarge(i) => write!(f, "bignum too large at offset {i}"),
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+impl Value {
+	/// Encodes this value as CBOR.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, Value};
+	///
+	/// let value = json!({ "a": [1, -2, 0.5, "b", null, true] });
+	/// let bytes = value.to_cbor_bytes();
+	/// assert_eq!(Value::from_cbor_slice(&bytes).unwrap(), value);
+	/// ```
+	pub fn to_cbor_bytes(&self) -> Vec<u8> {
+		let mut bytes = Vec::new();
+		self.write_cbor(&mut bytes);
+		bytes
+	}
+
+	/// Encodes this value as CBOR, appending the bytes to `bytes`.
+	pub fn write_cbor(&self, bytes: &mut Vec<u8>) {
+		enum Item<'a> {
+			Key(&'a Key),
+			Value(&'a Value),
+		}
+
+		let mut stack = vec![Item::Value(self)];
+		while let Some(item) = stack.pop() {
+			match item {
+				Item::Key(key) => write_text(bytes, key),
+				Item::Value(Self::Null) => bytes.push(0xf6),
+				Item::Value(Self::Boolean(false)) => bytes.push(0xf4),
+				Item::Value(Self::Boolean(true)) => bytes.push(0xf5),
+				Item::Value(Self::Number(n)) => write_number(bytes, n),
+				Item::Value(Self::String(s)) => write_text(bytes, s),
+				Item::Value(Self::Array(items)) => {
+					write_head(bytes, 4, items.len() as u64);
+					stack.extend(items.iter().rev().map(Item::Value))
+				}
+				Item::Value(Self::Object(object)) => {
+					write_head(bytes, 5, object.len() as u64);
+					for entry in object.iter().rev() {
+						stack.push(Item::Value(&entry.value));
+						stack.push(Item::Key(&entry.key))
+					}
+				}
+			}
+		}
+	}
+
+	/// Decodes a value from CBOR bytes.
+	///
+	/// The input must contain exactly one data item. Byte strings and
+	/// `undefined` are not supported, and map keys must be text strings.
+	/// Tags other than bignums and decimal fractions are ignored.
+	pub fn from_cbor_slice(bytes: &[u8]) -> Result<Self, Error> {
+		Decoder { bytes, pos: 0 }.decode()
+	}
+}
+
+fn write_head(bytes: &mut Vec<u8>, major: u8, n: u64) {
+	let major = major << 5;
+	if n < 24 {
+		bytes.push(major | n as u8)
+	} else if n <= u8::MAX as u64 {
+		bytes.push(major | 24);
+		bytes.push(n as u8)
+	} else if n <= u16::MAX as u64 {
+		bytes.push(major | 25);
+		bytes.extend((n as u16).to_be_bytes())
+	} else if n <= u32::MAX as u64 {
+		bytes.push(major | 26);
+		bytes.extend((n as u32).to_be_bytes())
+	} else {
+		bytes.push(major | 27);
+		bytes.extend(n.to_be_bytes())
+	}
+}
+
+fn write_text(bytes: &mut Vec<u8>, s: &str) {
+	write_head(bytes, 3, s.len() as u64);
+	bytes.extend(s.as_bytes())
+}
+
+fn write_number(bytes: &mut Vec<u8>, n: &Number) {
+	if let Some(i) = n.as_u64() {
+		write_head(bytes, 0, i)
+	} else if let Some(i) = n.as_i64().filter(|i| *i < 0) {
+		write_head(bytes, 1, !(i as u64))
+	} else if let Some(f) = n
+		.as_f64_lossy()
+		.is_finite()
+		.then(|| n.as_f64_lossless())
+		.flatten()
+	{
+		write_f64(bytes, f)
+	} else {
+		match decompose(n.as_str()) {
+			Some((true, digits, _)) if digits == b"0" => {
+				// Negative zero, only representable as a float.
+				write_f64(bytes, -0.0)
+			}
+			Some((_, digits, _)) if digits.len() > MAX_BIGNUM_DIGITS => {
+				write_f64(bytes, n.as_f64_lossy().clamp(f64::MIN, f64::MAX))
+			}
+			Some((negative, digits, 0)) => write_integer(bytes, negative, &digits),
+			Some((negative, digits, exponent)) => {
+				write_head(bytes, 6, TAG_DECIMAL_FRACTION);
+				write_head(bytes, 4, 2);
+				if exponent < 0 {
+					write_head(bytes, 1, !(exponent as u64))
+				} else {
+					write_head(bytes, 0, exponent as u64)
+				}
+				write_integer(bytes, negative, &digits)
+			}
+			None => {
+				// Exponent overflow, fall back to a lossy float, clamped so
+				// that it can be decoded back.
+				write_f64(bytes, n.as_f64_lossy().clamp(f64::MIN, f64::MAX))
+			}
+		}
+	}
+}
+
+fn write_f64(bytes: &mut Vec<u8>, f: f64) {
+	bytes.push(0xfb);
+	bytes.extend(f.to_bits().to_be_bytes())
+}
+
+/// Decomposes a lexical number into its sign, significant decimal digits
+/// and exponent.
+///
+/// Returns `None` if the exponent overflows.
+fn decompose(n: &str) -> Option<(bool, Vec<u8>, i64)> {
+	let (negative, n) = match n.strip_prefix('-') {
+		Some(n) => (true, n),
+		None => (false, n),
+	};
+
+	let (mantissa, exponent) = match n.split_once(['e', 'E']) {
+		Some((mantissa, exponent)) => (mantissa, exponent.parse::<i64>().ok()?),
+		None => (n, 0),
+	};
+
+	let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+	let exponent = exponent.checked_sub(fraction.len().try_into().ok()?)?;
+
+	let mut digits: Vec<u8> = integer
+		.bytes()
+		.chain(fraction.bytes())
+		.skip_while(|b| *b == b'0')
+		.collect();
+	if digits.is_empty() {
+		digits.push(b'0')
+	}
+
+	Some((negative, digits, exponent))
+}
+
+/// Writes an integer given by its sign and decimal digits, as a bignum if
+/// it does not fit in 64 bits.
+fn write_integer(bytes: &mut Vec<u8>, negative: bool, digits: &[u8]) {
+	let magnitude = decimal_to_bytes(digits);
+	if magnitude.iter().all(|b| *b == 0) {
+		write_head(bytes, 0, 0)
+	} else if negative {
+		// Negative integers are encoded as `-1 - n`.
+		let n = decrement(magnitude);
+		write_unsigned(bytes, 1, TAG_NEGATIVE_BIGNUM, n)
+	} else {
+		write_unsigned(bytes, 0, TAG_POSITIVE_BIGNUM, magnitude)
+	}
+}
+
+fn write_unsigned(bytes: &mut Vec<u8>, major: u8, tag: u64, n: Vec<u8>) {
+	let n: Vec<u8> = n.into_iter().skip_while(|b| *b == 0).collect();
+	if n.len() <= 8 {
+		let mut buffer = [0; 8];
+		buffer[8 - n.len()..].copy_from_slice(&n);
+		write_head(bytes, major, u64::from_be_bytes(buffer))
+	} else {
+		write_head(bytes, 6, tag);
+		write_head(bytes, 2, n.len() as u64);
+		bytes.extend(n)
+	}
+}
+
+/// Converts ASCII decimal digits into a big-endian unsigned integer.
+fn decimal_to_bytes(digits: &[u8]) -> Vec<u8> {
+	let mut result: Vec<u8> = Vec::new(); // little-endian bytes.
+
+	for d in digits {
+		let mut carry = (d - b'0') as u32;
+		for b in result.iter_mut() {
+			let v = *b as u32 * 10 + carry;
+			*b = v as u8;
+			carry = v >> 8;
+		}
+
+		while carry > 0 {
+			result.push(carry as u8);
+			carry >>= 8
+		}
+	}
+
+	result.reverse();
+	result
+}
+
+/// Converts a big-endian unsigned integer into decimal digits.
+fn bytes_to_decimal(bytes: &[u8]) -> String {
+	let mut digits: Vec<u8> = Vec::new(); // little-endian decimal digits.
+
+	for b in bytes {
+		let mut carry = *b as u32;
+		for d in digits.iter_mut() {
+			let v = *d as u32 * 256 + carry;
+			*d = (v % 10) as u8;
+			carry = v / 10;
+		}
+
+		while carry > 0 {
+			digits.push((carry % 10) as u8);
+			carry /= 10
+		}
+	}
+
+	if digits.is_empty() {
+		digits.push(0)
+	}
+
+	digits.iter().rev().map(|d| (b'0' + d) as char).collect()
+}
+
+/// Subtracts one to a non-zero big-endian unsigned integer.
+fn decrement(mut bytes: Vec<u8>) -> Vec<u8> {
+	for b in bytes.iter_mut().rev() {
+		let (v, borrow) = b.overflowing_sub(1);
+		*b = v;
+		if !borrow {
+			break;
+		}
+	}
+
+	bytes
+}
+
+/// Adds one to a big-endian unsigned integer.
+fn increment(mut bytes: Vec<u8>) -> Vec<u8> {
+	for b in bytes.iter_mut().rev() {
+		let (v, carry) = b.overflowing_add(1);
+		*b = v;
+		if !carry {
+			return bytes;
+		}
+	}
+
+	bytes.insert(0, 1);
+	bytes
+}
+
+/// Decoded integer, as a sign and decimal digits.
+struct Integer {
+	negative: bool,
+	digits: String,
+}
+
+impl Integer {
+	fn from_head(major: u8, n: u64) -> Self {
+		if major == 0 {
+			Self {
+				negative: false,
+				digits: n.to_string(),
+			}
+		} else {
+			Self {
+				negative: true,
+				digits: (n as u128 + 1).to_string(),
+			}
+		}
+	}
+
+	fn into_number(self) -> NumberBuf {
+		let s = if self.negative {
+			format!("-{}", self.digits)
+		} else {
+			self.digits
+		};
+
+		NumberBuf::new(s.as_bytes().into()).unwrap()
+	}
+
+	/// Builds the number `self * 10^exponent`, preferring a decimal notation
+	/// without exponent when it is reasonably short.
+	fn into_decimal(self, exponent: i64) -> NumberBuf {
+		let sign = if self.negative { "-" } else { "" };
+		let digits = self.digits;
+
+		let s = if exponent == 0 {
+			format!("{sign}{digits}")
+		} else if exponent > 0 {
+			format!("{sign}{digits}e{exponent}")
+		} else {
+			let k = exponent.unsigned_abs();
+			let len = digits.len() as u64;
+			if k < len {
+				let (integer, fraction) = digits.split_at((len - k) as usize);
+				format!("{sign}{integer}.{fraction}")
+			} else if k - len <= 6 {
+				let zeros = "0".repeat((k - len) as usize);
+				format!("{sign}0.{zeros}{digits}")
+			} else {
+				format!("{sign}{digits}e{exponent}")
+			}
+		};
+
+		NumberBuf::new(s.as_bytes().into()).unwrap()
+	}
+}
+
+enum Frame {
+	Array {
+		remaining: Option<u64>,
+		items: Vec<Value>,
+	},
+	Object {
+		remaining: Option<u64>,
+		object: Object,
+		key: Option<Key>,
+	},
+}
+
+/// Decoded data item.
+enum Item {
+	Value(Value),
+	Array(Option<u64>),
+	Object(Option<u64>),
+}
+
+struct Decoder<'a> {
+	bytes: &'a [u8],
+	pos: usize,
+}
+
+impl<'a> Decoder<'a> {
+	fn decode(mut self) -> Result<Value, Error> {
+		let mut stack: Vec<Frame> = Vec::new();
+
+		loop {
+			let completed = match stack.last_mut() {
+				Some(Frame::Array { remaining, .. })
+				| Some(Frame::Object {
+					remaining,
+					key: None,
+					..
+				}) => self.is_complete(remaining)?,
+				_ => false,
+			};
+
+			let value = if completed {
+				match stack.pop().unwrap() {
+					Frame::Array { items, .. } => Value::Array(items),
+					Frame::Object { object, .. } => Value::Object(object),
+				}
+			} else {
+				if let Some(Frame::Object {
+					key: key @ None, ..
+				}) = stack.last_mut()
+				{
+					*key = Some(self.read_key()?);
+				}
+
+				match self.read_item()? {
+					Item::Value(value) => value,
+					Item::Array(remaining) => {
+						stack.push(Frame::Array {
+							remaining,
+							items: Vec::with_capacity(self.capacity(remaining)),
+						});
+						continue;
+					}
+					Item::Object(remaining) => {
+						stack.push(Frame::Object {
+							remaining,
+							object: Object::with_capacity(self.capacity(remaining)),
+							key: None,
+						});
+						continue;
+					}
+				}
+			};
+
+			match stack.last_mut() {
+				Some(Frame::Array { remaining, items }) => {
+					items.push(value);
+					if let Some(r) = remaining {
+						*r -= 1
+					}
+				}
+				Some(Frame::Object {
+					remaining,
+					object,
+					key,
+				}) => {
+					object.push(key.take().unwrap(), value);
+					if let Some(r) = remaining {
+						*r -= 1
+					}
+				}
+				None => {
+					return if self.pos < self.bytes.len() {
+						Err(Error::TrailingBytes(self.pos))
+					} else {
+						Ok(value)
+					};
+				}
+			}
+		}
+	}
+
+	/// Checks if an array or map with the given remaining number of items is
+	/// complete, consuming the break code of indefinite length items.
+	fn is_complete(&mut self, remaining: &Option<u64>) -> Result<bool, Error> {
+		match remaining {
+			Some(n) => Ok(*n == 0),
+			None => {
+				if self.peek()? == BREAK {
+					self.pos += 1;
+					Ok(true)
+				} else {
+					Ok(false)
+				}
+			}
+		}
+	}
+
+	/// Initial capacity for an array or map with the given length.
+	///
+	/// Bounded by the remaining input length to avoid large allocations on
+	/// malformed inputs.
+	fn capacity(&self, len: Option<u64>) -> usize {
+		let available = self.bytes.len() - self.pos;
+		len.map(|n| (n as usize).min(available)).unwrap_or(0)
+	}
+
+	fn peek(&self) -> Result<u8, Error> {
+		self.bytes
+			.get(self.pos)
+			.copied()
+			.ok_or(Error::UnexpectedEnd)
+	}
+
+	fn read_bytes(&mut self, len: u64) -> Result<&'a [u8], Error> {
+		let end = usize::try_from(len)
+			.ok()
+			.and_then(|len| self.pos.checked_add(len))
+			.filter(|end| *end <= self.bytes.len())
+			.ok_or(Error::UnexpectedEnd)?;
+		let bytes = &self.bytes[self.pos..end];
+		self.pos = end;
+		Ok(bytes)
+	}
+
+	/// Reads the head of a data item, returning its major type, additional
+	/// information and argument (`None` for indefinite lengths).
+	fn read_head(&mut self) -> Result<(u8, u8, Option<u64>), Error> {
+		let offset = self.pos;
+		let initial = self.peek()?;
+		self.pos += 1;
+
+		let major = initial >> 5;
+		let info = initial & 0x1f;
+		let arg = match info {
+			0..=23 => Some(info as u64),
+			24 => Some(self.read_bytes(1)?[0] as u64),
+			25 => Some(u16::from_be_bytes(self.read_bytes(2)?.try_into().unwrap()) as u64),
+			26 => Some(u32::from_be_bytes(self.read_bytes(4)?.try_into().unwrap()) as u64),
+			27 => Some(u64::from_be_bytes(self.read_bytes(8)?.try_into().unwrap())),
+			31 if matches!(major, 2..=5 | 7) => None,
+			_ => return Err(Error::Malformed(offset)),
+		};
+
+		Ok((major, info, arg))
+	}
+
+	fn read_key(&mut self) -> Result<Key, Error> {
+		let offset = self.pos;
+		match self.read_head()? {
+			(3, _, len) => Ok(self.read_text(offset, len)?.as_str().into()),
+			_ => Err(Error::InvalidKey(offset)),
+		}
+	}
+
+	fn read_text(&mut self, offset: usize, len: Option<u64>) -> Result<String, Error> {
+		match len {
+			Some(len) => {
+				let bytes = self.read_bytes(len)?;
+				std::str::from_utf8(bytes)
+					.map(ToOwned::to_owned)
+					.map_err(|_| Error::InvalidUtf8(offset))
+			}
+			None => {
+				let mut result = String::new();
+				while self.peek()? != BREAK {
+					let chunk_offset = self.pos;
+					match self.read_head()? {
+						(3, _, Some(len)) => {
+							result.push_str(&self.read_text(chunk_offset, Some(len))?)
+						}
+						_ => return Err(Error::Malformed(chunk_offset)),
+					}
+				}
+
+				self.pos += 1;
+				Ok(result)
+			}
+		}
+	}
+
+	/// Reads an integer, either as major type 0 or 1, or as a bignum.
+	fn read_integer(&mut self) -> Result<Integer, Error> {
+		let offset = self.pos;
+		match self.read_head()? {
+			(major @ (0 | 1), _, Some(n)) => Ok(Integer::from_head(major, n)),
+			(6, _, Some(tag @ (TAG_POSITIVE_BIGNUM | TAG_NEGATIVE_BIGNUM))) => {
+				self.read_bignum(tag)
+			}
+			_ => Err(Error::Malformed(offset)),
+		}
+	}
+
+	fn read_bignum(&mut self, tag: u64) -> Result<Integer, Error> {
+		let offset = self.pos;
+		let bytes = match self.read_head()? {
+			(2, _, Some(len)) => self.read_bytes(len)?,
+			_ => return Err(Error::Malformed(offset)),
+		};
+
+		// Leading zeros do not count towards the limit.
+		let bytes = &bytes[bytes.iter().take_while(|b| **b == 0).count()..];
+		if bytes.len() > MAX_BIGNUM_LEN {
+			return Err(Error::BignumTooLarge(offset));
+		}
+
+		if tag == TAG_POSITIVE_BIGNUM {
+			Ok(Integer {
+				negative: false,
+				digits: bytes_to_decimal(bytes),
+			})
+		} else {
+			Ok(Integer {
+				negative: true,
+				digits: bytes_to_decimal(&increment(bytes.to_vec())),
+			})
+		}
+	}
+
+	fn read_item(&mut self) -> Result<Item, Error> {
+		loop {
+			let offset = self.pos;
+			let (major, info, arg) = self.read_head()?;
+			let value = match (major, arg) {
+				(0 | 1, Some(n)) => Value::Number(Integer::from_head(major, n).into_number()),
+				(2, _) => return Err(Error::Unsupported(offset)),
+				(3, len) => Value::String(self.read_text(offset, len)?.into()),
+				(4, len) => return Ok(Item::Array(len)),
+				(5, len) => return Ok(Item::Object(len)),
+				(6, Some(tag @ (TAG_POSITIVE_BIGNUM | TAG_NEGATIVE_BIGNUM))) => {
+					Value::Number(self.read_bignum(tag)?.into_number())
+				}
+				(6, Some(TAG_DECIMAL_FRACTION)) => {
+					match self.read_head()? {
+						(4, _, Some(2)) => (),
+						_ => return Err(Error::Malformed(offset)),
+					}
+
+					let exponent = match self.read_head()? {
+						(0, _, Some(n)) => i64::try_from(n).ok(),
+						(1, _, Some(n)) => i64::try_from(n).ok().map(|n| -1 - n),
+						_ => None,
+					}
+					.ok_or(Error::Unsupported(offset))?;
+
+					Value::Number(self.read_integer()?.into_decimal(exponent))
+				}
+				// Other tags are ignored.
+				(6, Some(_)) => continue,
+				(7, arg) => match info {
+					20 => Value::Boolean(false),
+					21 => Value::Boolean(true),
+					22 => Value::Null,
+					25 => float(offset, f16_to_f64(arg.unwrap() as u16))?,
+					26 => float(offset, f32::from_bits(arg.unwrap() as u32) as f64)?,
+					27 => float(offset, f64::from_bits(arg.unwrap()))?,
+					31 => return Err(Error::Malformed(offset)),
+					_ => return Err(Error::Unsupported(offset)),
+				},
+				_ => return Err(Error::Malformed(offset)),
+			};
+
+			return Ok(Item::Value(value));
+		}
+	}
+}
+
+fn float(offset: usize, f: f64) -> Result<Value, Error> {
+	NumberBuf::try_from(f)
+		.map(Value::Number)
+		.map_err(|_| Error::NonFiniteFloat(offset))
+}
+
+/// Converts a half-precision float into a double-precision float.
+fn f16_to_f64(bits: u16) -> f64 {
+	let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+	let exponent = ((bits >> 10) & 0x1f) as i32;
+	let fraction = (bits & 0x3ff) as f64;
+
+	sign * match exponent {
+		0 => fraction * 2f64.powi(-24),
+		31 if fraction == 0.0 => f64::INFINITY,
+		31 => f64::NAN,
+		e => (1.0 + fraction / 1024.0) * 2f64.powi(e - 15),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{json, Parse, Print};
+
+	fn round_trip(input: &str) -> String {
+		let (value, _) = Value::parse_str(input).unwrap();
+		Value::from_cbor_slice(&value.to_cbor_bytes())
+			.unwrap()
+			.compact_print()
+			.to_string()
+	}
+
+	#[test]
+	fn numbers() {
+		for input in [
+			"0",
+			"-1",
+			"18446744073709551615",
+			"-18446744073709551616",
+			"123456789012345678901234567890",
+			"-123456789012345678901234567890",
+			"1.0",
+			"-0.001",
+			"3.141592653589793238462643383279",
+			"1e300",
+			"12e-30",
+			"1e400",
+			"-1e-400",
+		] {
+			assert_eq!(round_trip(input), input)
+		}
+
+		assert_eq!(round_trip("-0.0"), "-0");
+		assert_eq!(round_trip(&"9".repeat(3000)), "1.7976931348623157e308")
+	}
+
+	#[test]
+	fn bignum_limit() {
+		let mut bytes = vec![0xc2, 0x5a];
+		bytes.extend(((MAX_BIGNUM_LEN + 1) as u32).to_be_bytes());
+		bytes.push(1);
+		bytes.resize(bytes.len() + MAX_BIGNUM_LEN, 0);
+		assert_eq!(
+			Value::from_cbor_slice(&bytes),
+			Err(Error::BignumTooLarge(1))
+		);
+
+		// Leading zeros are ignored.
+		bytes[6] = 0;
+		assert!(Value::from_cbor_slice(&bytes).is_ok())
+	}
+
+	#[test]
+	fn duplicate_keys() {
+		let input = r#"{"a":1,"a":[{"b":null}]}"#;
+		assert_eq!(round_trip(input), input)
+	}
+
+	#[test]
+	fn decode() {
+		// Indefinite length map and text, half float, tag 0 date string.
+		let bytes = [
+			0xbf, 0x61, 0x61, 0x7f, 0x61, 0x62, 0x61, 0x63, 0xff, 0x61, 0x64, 0xf9, 0x3e, 0x00,
+			0x61, 0x65, 0xc0, 0x61, 0x66, 0xff,
+		];
+		assert_eq!(
+			Value::from_cbor_slice(&bytes).unwrap(),
+			json!({ "a": "bc", "d": 1.5, "e": "f" })
+		);
+
+		assert_eq!(Value::from_cbor_slice(&[0x40]), Err(Error::Unsupported(0)));
+		assert_eq!(
+			Value::from_cbor_slice(&[0xa1, 0x01, 0x02]),
+			Err(Error::InvalidKey(1))
+		);
+		assert_eq!(
+			Value::from_cbor_slice(&[0xf6, 0xf6]),
+			Err(Error::TrailingBytes(1))
+		);
+		assert_eq!(
+			Value::from_cbor_slice(&[0x82, 0x01]),
+			Err(Error::UnexpectedEnd)
+		);
+	}
+}
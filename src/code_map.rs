@@ -1,7 +1,7 @@
 use core::fmt;
 use std::{borrow::Borrow, ops::Deref};
 
-use locspan::Span;
+pub use locspan::Span;
 
 /// Code-map.
 #[derive(Debug, Default, Clone)]
@@ -21,13 +21,252 @@ impl CodeMap {
 		i
 	}
 
+	/// Removes all the entries, keeping the allocated memory.
+	pub(crate) fn clear(&mut self) {
+		self.0.clear()
+	}
+
 	pub(crate) fn get_mut(&mut self, i: usize) -> Option<&mut Entry> {
 		self.0.get_mut(i)
 	}
 
-	pub fn iter(&self) -> Iter {
+	pub fn iter(&self) -> Iter<'_> {
 		self.0.iter().enumerate()
 	}
+
+	/// Returns the line and column numbers (starting at 1) of the beginning
+	/// of the given fragment, or `None` if there is no such fragment.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{code_map::LineIndex, Parse, Value};
+	///
+	/// let source = "{\n  \"a\": [\n    true\n  ]\n}";
+	/// let (_, code_map) = Value::parse_str(source).unwrap();
+	/// let lines = LineIndex::new(source);
+	///
+	/// assert_eq!(code_map.position_of(0, &lines), Some((1, 1)));
+	/// assert_eq!(code_map.position_of(4, &lines), Some((3, 5))); // true
+	/// assert_eq!(code_map.position_of(5, &lines), None);
+	/// ```
+	pub fn position_of(&self, fragment: usize, lines: &LineIndex) -> Option<(usize, usize)> {
+		self.0
+			.get(fragment)
+			.map(|entry| lines.position(entry.span.start()))
+	}
+
+	/// Returns the index of the innermost fragment whose span contains the
+	/// given byte offset, or `None` if no fragment contains it.
+	///
+	/// Fragment spans start in pre-order, so the last fragment starting at or
+	/// before `offset` is found by binary search. The result is this
+	/// fragment or one of its ancestors. In the latter case, the tree is
+	/// descended from the root towards this fragment, using fragment volumes
+	/// to skip over the subtrees of preceding siblings.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{Parse, Value};
+	///
+	/// let source = r#"{ "a": [true, null] }"#;
+	/// let (_, code_map) = Value::parse_str(source).unwrap();
+	///
+	/// assert_eq!(code_map.fragment_containing(0), Some(0)); // the object
+	/// assert_eq!(code_map.fragment_containing(3), Some(2)); // "a"
+	/// assert_eq!(code_map.fragment_containing(5), Some(1)); // "a": [true, null]
+	/// assert_eq!(code_map.fragment_containing(16), Some(5)); // null
+	/// assert_eq!(code_map.fragment_containing(21), None);
+	/// ```
+	pub fn fragment_containing(&self, offset: usize) -> Option<usize> {
+		let last = self
+			.0
+			.partition_point(|entry| entry.span.start() <= offset)
+			.checked_sub(1)?;
+
+		if self.0[last].span.range().contains(&offset) {
+			return Some(last);
+		}
+
+		// Root fragment of the tree containing `last`.
+		let mut i = 0;
+		while i + self.0[i].volume.max(1) <= last {
+			i += self.0[i].volume.max(1)
+		}
+
+		if !self.0[i].span.range().contains(&offset) {
+			return None;
+		}
+
+		// Descend towards `last` while the fragments contain `offset`.
+		loop {
+			let mut child = i + 1;
+			while child + self.0[child].volume.max(1) <= last {
+				child += self.0[child].volume.max(1)
+			}
+
+			if child > last || !self.0[child].span.range().contains(&offset) {
+				break Some(i);
+			}
+
+			i = child
+		}
+	}
+
+	/// Shifts by `delta` bytes every span boundary located at or after
+	/// `offset`.
+	///
+	/// This is used to keep the code map consistent with its source text
+	/// after `delta` bytes were inserted (or `-delta` bytes removed) at
+	/// `offset`. With an `offset` of `0`, this rebases the whole code map,
+	/// for instance to map a value parsed separately into a larger document.
+	pub fn shift(&mut self, offset: usize, delta: isize) {
+		let shift = |i: usize| {
+			if i >= offset {
+				i.saturating_add_signed(delta)
+			} else {
+				i
+			}
+		};
+
+		for entry in &mut self.0 {
+			entry.span = Span::new(shift(entry.span.start()), shift(entry.span.end()))
+		}
+	}
+
+	/// Returns the code map of the given fragment and its sub-fragments, or
+	/// `None` if there is no such fragment.
+	///
+	/// The given fragment is the first fragment of the returned code map,
+	/// which can hence be used with the corresponding sub-value. Spans are
+	/// unchanged and still refer to the original source text. They can be
+	/// made relative to the sub-value text using [`Self::shift`].
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{Parse, Value};
+	///
+	/// let source = r#"{ "a": [true, null] }"#;
+	/// let (value, code_map) = Value::parse_str(source).unwrap();
+	///
+	/// let items = code_map.slice(3).unwrap();
+	/// assert_eq!(items.len(), 3);
+	/// assert_eq!(&source[items[2].span.range()], "null");
+	///
+	/// let (sub_value, sub_code_map) = Value::parse_str("[true, null]").unwrap();
+	/// let mut items = items;
+	/// items.shift(0, -7);
+	/// assert_eq!(sub_value, value["a"]);
+	/// assert_eq!(items.as_slice(), sub_code_map.as_slice());
+	/// ```
+	pub fn slice(&self, fragment: usize) -> Option<CodeMap> {
+		let volume = self.0.get(fragment)?.volume;
+		let end = (fragment + volume).min(self.0.len());
+		Some(CodeMap(self.0[fragment..end].to_vec()))
+	}
+
+	/// Replaces the given fragment and its sub-fragments by the fragments of
+	/// `replacement`, returning the replaced fragments.
+	///
+	/// The `replacement` code map must describe a single value (or entry, if
+	/// `fragment` is an object entry), or be empty to remove `fragment`.
+	/// The volume of every ancestor of `fragment` is updated accordingly.
+	/// Spans are copied as is: the spans of `replacement` must already be
+	/// expressed in the edited source text, and the rest of the code map can
+	/// be updated using [`Self::shift`].
+	///
+	/// Returns `None`, leaving the code map untouched, if there is no such
+	/// fragment.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{Parse, Value};
+	///
+	/// let (mut value, mut code_map) = Value::parse_str("[1, [2, 3], 4]").unwrap();
+	///
+	/// // Replace `[2, 3]` (fragment 2, bytes 4..10) with `true`.
+	/// let (new_item, mut new_code_map) = Value::parse_str("true").unwrap();
+	/// value.as_array_mut().unwrap()[1] = new_item;
+	///
+	/// code_map.shift(10, 4 - 6);
+	/// new_code_map.shift(0, 4);
+	/// code_map.splice(2, new_code_map).unwrap();
+	///
+	/// assert_eq!(code_map.as_slice(), Value::parse_str("[1, true, 4]").unwrap().1.as_slice());
+	/// ```
+	pub fn splice(&mut self, fragment: usize, replacement: CodeMap) -> Option<CodeMap> {
+		let volume = self.0.get(fragment)?.volume;
+		let new_volume = replacement.len();
+
+		let mut i = 0;
+		while i < fragment {
+			let entry = &mut self.0[i];
+			if fragment < i + entry.volume {
+				entry.volume = entry.volume - volume + new_volume;
+				i += 1
+			} else {
+				i += entry.volume.max(1)
+			}
+		}
+
+		let end = (fragment + volume).min(self.0.len());
+		Some(CodeMap(
+			self.0.splice(fragment..end, replacement.0).collect(),
+		))
+	}
+}
+
+/// Line index of a source text.
+///
+/// Maps byte offsets in the source text to line and column numbers.
+#[derive(Debug, Clone)]
+pub struct LineIndex<'a> {
+	source: &'a str,
+
+	/// Byte offset of the beginning of each line.
+	starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+	/// Indexes the lines of the given source text.
+	///
+	/// Lines are terminated by `\n` (optionally preceded by `\r`).
+	pub fn new(source: &'a str) -> Self {
+		let starts = std::iter::once(0)
+			.chain(source.match_indices('\n').map(|(i, _)| i + 1))
+			.collect();
+
+		Self { source, starts }
+	}
+
+	/// Returns the indexed source text.
+	pub fn source(&self) -> &'a str {
+		self.source
+	}
+
+	/// Returns the number of lines.
+	pub fn line_count(&self) -> usize {
+		self.starts.len()
+	}
+
+	/// Returns the line and column numbers (starting at 1) of the given
+	/// byte offset.
+	///
+	/// Columns are counted in characters. Offsets past the end of the source
+	/// text are clamped to its length.
+	pub fn position(&self, offset: usize) -> (usize, usize) {
+		let offset = offset.min(self.source.len());
+		let line = self.starts.partition_point(|start| *start <= offset) - 1;
+		let column = self.source[self.starts[line]..]
+			.char_indices()
+			.take_while(|(i, _)| self.starts[line] + i < offset)
+			.count();
+
+		(line + 1, column + 1)
+	}
 }
 
 impl Deref for CodeMap {
@@ -114,9 +353,100 @@ impl<T: 'static + std::error::Error> std::error::Error for Mapped<T> {
 	}
 }
 
+/// Serializes entries as a sequence of entries.
+#[cfg(feature = "serde")]
+impl serde::Serialize for CodeMap {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		self.0.serialize(serializer)
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CodeMap {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		Vec::deserialize(deserializer).map(Self)
+	}
+}
+
+/// Serializes the entry as a `(start, end, volume)` tuple.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Entry {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		(self.span.start(), self.span.end(), self.volume).serialize(serializer)
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Entry {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		let (start, end, volume) = serde::Deserialize::deserialize(deserializer)?;
+		Ok(Self::new(span_serde::new_span(start, end)?, volume))
+	}
+}
+
+/// Serialization of [`Span`]s as `(start, end)` tuples, to be used with
+/// `#[serde(with = "json_syntax::code_map::span_serde")]`.
+///
+/// # Example
+///
+/// ```
+/// use json_syntax::code_map::Span;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Diagnostic {
+///   message: String,
+///
+///   #[serde(with = "json_syntax::code_map::span_serde")]
+///   span: Span,
+/// }
+/// ```
+#[cfg(feature = "serde")]
+pub mod span_serde {
+	use super::Span;
+	use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+	pub fn serialize<S>(span: &Span, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		(span.start(), span.end()).serialize(serializer)
+	}
+
+	pub fn deserialize<'de, D>(deserializer: D) -> Result<Span, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let (start, end) = Deserialize::deserialize(deserializer)?;
+		new_span(start, end)
+	}
+
+	pub(super) fn new_span<E: Error>(start: usize, end: usize) -> Result<Span, E> {
+		if start <= end {
+			Ok(Span::new(start, end))
+		} else {
+			Err(E::custom(format_args!(
+				"invalid span: start ({start}) is greater than end ({end})"
+			)))
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
-	use super::Entry;
+	use super::{CodeMap, Entry, LineIndex};
 	use crate::{Parse, Value};
 	use locspan::Span;
 
@@ -178,4 +508,91 @@ mod tests {
 			assert_eq!(entry, expected[i])
 		}
 	}
+
+	#[test]
+	fn line_index() {
+		let source = "[\r\n  \"é\", 1,\n\n2]";
+		let lines = LineIndex::new(source);
+		assert_eq!(lines.line_count(), 4);
+		assert_eq!(lines.position(0), (1, 1));
+		assert_eq!(lines.position(1), (1, 2));
+		assert_eq!(lines.position(3), (2, 1));
+		assert_eq!(lines.position(source.find('1').unwrap()), (2, 8));
+		assert_eq!(lines.position(source.len()), (4, 3));
+
+		let (_, code_map) = Value::parse_str(source).unwrap();
+		assert_eq!(code_map.position_of(3, &lines), Some((4, 1)));
+	}
+
+	#[test]
+	fn fragment_containing() {
+		for source in [
+			r#"[{}, "a", { "b": [1, {}] }]"#,
+			"{ \"a\" : [ [ [ 1 ] , 2 ] ,\n 3 ] , \"b\" : { \"c\" : null }\n}",
+			"[ [ [ [ [ ] ] ] , { \"a\" : [ 1 , 2 , 3 ] } ]   ,   4 ]",
+		] {
+			let (value, code_map) = Value::parse_str(source).unwrap();
+			assert_eq!(code_map.len(), value.traverse().count());
+
+			for offset in 0..=source.len() {
+				let expected = code_map
+					.iter()
+					.filter(|(_, entry)| entry.span.range().contains(&offset))
+					.map(|(i, _)| i)
+					.next_back();
+				assert_eq!(code_map.fragment_containing(offset), expected)
+			}
+		}
+
+		let source = r#"[{}, "a", { "b": [1, {}] }]"#;
+		let (_, code_map) = Value::parse_str(source).unwrap();
+		assert_eq!(code_map.fragment_containing(1), Some(1));
+		assert_eq!(code_map.fragment_containing(source.len()), None);
+		assert_eq!(CodeMap::default().fragment_containing(0), None);
+	}
+
+	#[test]
+	fn splice() {
+		let (mut value, mut code_map) =
+			Value::parse_str(r#"{ "a": 1, "b": [2], "c": 3 }"#).unwrap();
+
+		// Remove `"b": [2], `.
+		value.as_object_mut().unwrap().remove_at(1);
+		code_map.shift(20, -10);
+		let removed = code_map.splice(4, CodeMap::default()).unwrap();
+		assert_eq!(removed.len(), 4);
+
+		let (expected_value, expected) = Value::parse_str(r#"{ "a": 1, "c": 3 }"#).unwrap();
+		assert_eq!(value, expected_value);
+		assert_eq!(code_map.as_slice(), expected.as_slice());
+
+		// Replace `1` with `[{}]`.
+		let (_, mut replacement) = Value::parse_str("[{}]").unwrap();
+		replacement.shift(0, 7);
+		code_map.shift(8, 3);
+		code_map.splice(3, replacement).unwrap();
+		assert_eq!(
+			code_map.as_slice(),
+			Value::parse_str(r#"{ "a": [{}], "c": 3 }"#)
+				.unwrap()
+				.1
+				.as_slice()
+		);
+
+		assert!(code_map
+			.splice(code_map.len(), CodeMap::default())
+			.is_none());
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn serde() {
+		let (_, code_map) = Value::parse_str(r#"{ "a": [1, {}] }"#).unwrap();
+		let value = crate::to_value(&code_map).unwrap();
+		assert_eq!(value.as_array().unwrap()[1], crate::json!([2, 14, 5]));
+		let deserialized: CodeMap = crate::from_value(value).unwrap();
+		assert_eq!(deserialized.as_slice(), code_map.as_slice());
+
+		assert!(crate::from_value::<Entry>(crate::json!([3, 2, 1])).is_err());
+	}
 }
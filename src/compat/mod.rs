@@ -0,0 +1,3 @@
+//! Compatibility layers with other JSON libraries.
+#[cfg(feature = "serde")]
+pub mod serde_json;
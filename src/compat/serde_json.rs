@@ -0,0 +1,182 @@
+//! Drop-in replacement for the top-level API of the
+//! [`serde_json`](https://crates.io/crates/serde_json) crate.
+//!
+//! The functions of this module have the same names and signatures as their
+//! `serde_json` counterparts, but are implemented using this crate. Migrating
+//! can be done by replacing `use serde_json` with
+//! `use json_syntax::compat::serde_json`.
+//!
+//! # Example
+//!
+//! ```
+//! use json_syntax::compat::serde_json;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! struct Point {
+//!     x: i32,
+//!     y: i32,
+//! }
+//!
+//! let point: Point = serde_json::from_str(r#"{ "x": 1, "y": 2 }"#).unwrap();
+//! assert_eq!(point, Point { x: 1, y: 2 });
+//! assert_eq!(serde_json::to_string(&point).unwrap(), r#"{"x":1,"y":2}"#);
+//!
+//! let value: serde_json::Value = serde_json::json!({ "x": 1 });
+//! assert_eq!(serde_json::to_string_pretty(&value).unwrap(), "{\n  \"x\": 1\n}");
+//! ```
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	parse,
+	print::{Limit, Options},
+	DeserializeError, Parse, Print, SerializeError,
+};
+
+pub use crate::json;
+
+/// JSON value.
+pub type Value = crate::Value;
+
+/// JSON object.
+pub type Map = crate::Object;
+
+/// JSON number.
+pub type Number = crate::NumberBuf;
+
+/// Error that can occur when parsing, serializing or deserializing.
+#[derive(Debug)]
+pub enum Error {
+	/// Parse error.
+	Parse(parse::Error),
+
+	/// Serialization error.
+	Serialize(SerializeError),
+
+	/// Deserialization error.
+	Deserialize(DeserializeError),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Parse(e) => e.fmt(f),
+			Self::Serialize(e) => e.fmt(f),
+			Self::Deserialize(e) => e.fmt(f),
+		}
+	}
+}
+
+impl std::error::Error for Error {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::Parse(e) => Some(e),
+			Self::Serialize(e) => Some(e),
+			Self::Deserialize(e) => Some(e),
+		}
+	}
+}
+
+impl From<parse::Error> for Error {
+	fn from(value: parse::Error) -> Self {
+		Self::Parse(value)
+	}
+}
+
+impl From<SerializeError> for Error {
+	fn from(value: SerializeError) -> Self {
+		Self::Serialize(value)
+	}
+}
+
+impl From<DeserializeError> for Error {
+	fn from(value: DeserializeError) -> Self {
+		Self::Deserialize(value)
+	}
+}
+
+/// Alias for a `Result` with the error type [`Error`].
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Pretty print options matching the `serde_json` pretty printer: every
+/// non-empty array and object is expanded.
+fn pretty_options() -> Options {
+	let mut options = Options::pretty();
+	options.array_limit = Some(Limit::Item(0));
+	options.object_limit = Some(Limit::Item(0));
+	options
+}
+
+/// Deserializes an instance of type `T` from a string of JSON text.
+///
+/// The input is first parsed into a [`Value`], so strings are never
+/// borrowed from `s`: deserializing a type with borrowed fields (such as
+/// `&'a str`) fails, like `serde_json` does for strings containing escape
+/// sequences. Use `Cow<'a, str>` or owned fields instead.
+pub fn from_str<'a, T>(s: &'a str) -> Result<T>
+where
+	T: Deserialize<'a>,
+{
+	let (value, _) = Value::parse_str(s)?;
+	Ok(T::deserialize(value)?)
+}
+
+/// Deserializes an instance of type `T` from bytes of JSON text.
+///
+/// Strings are never borrowed from `v`, see [`from_str`].
+pub fn from_slice<'a, T>(v: &'a [u8]) -> Result<T>
+where
+	T: Deserialize<'a>,
+{
+	let (value, _) = Value::parse_slice(v)?;
+	Ok(T::deserialize(value)?)
+}
+
+/// Interprets a [`Value`] as an instance of type `T`.
+pub fn from_value<T>(value: Value) -> Result<T>
+where
+	T: serde::de::DeserializeOwned,
+{
+	Ok(crate::from_value(value)?)
+}
+
+/// Converts a `T` into a [`Value`].
+pub fn to_value<T>(value: T) -> Result<Value>
+where
+	T: Serialize,
+{
+	Ok(crate::to_value(value)?)
+}
+
+/// Serializes the given data structure as a compact JSON string.
+pub fn to_string<T>(value: &T) -> Result<std::string::String>
+where
+	T: ?Sized + Serialize,
+{
+	Ok(to_value(value)?.compact_print().to_string())
+}
+
+/// Serializes the given data structure as a pretty-printed JSON string.
+pub fn to_string_pretty<T>(value: &T) -> Result<std::string::String>
+where
+	T: ?Sized + Serialize,
+{
+	Ok(to_value(value)?.print_with(pretty_options()).to_string())
+}
+
+/// Serializes the given data structure as a compact JSON byte vector.
+pub fn to_vec<T>(value: &T) -> Result<Vec<u8>>
+where
+	T: ?Sized + Serialize,
+{
+	Ok(to_value(value)?.to_vec_with(&Options::compact()))
+}
+
+/// Serializes the given data structure as a pretty-printed JSON byte vector.
+pub fn to_vec_pretty<T>(value: &T) -> Result<Vec<u8>>
+where
+	T: ?Sized + Serialize,
+{
+	Ok(to_value(value)?.to_vec_with(&pretty_options()))
+}
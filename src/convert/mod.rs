@@ -1,2 +1,4 @@
 #[cfg(feature = "serde_json")]
 mod serde_json;
+#[cfg(feature = "simd-json")]
+mod simd_json;
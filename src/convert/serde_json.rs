@@ -1,4 +1,4 @@
-use crate::{object::Entry, Value};
+use crate::{object::Entry, Object, Value};
 
 impl Value {
 	/// Converts a [`serde_json::Value`] into a `Value`.
@@ -27,11 +27,7 @@ impl Value {
 			serde_json::Value::Array(a) => {
 				Self::Array(a.into_iter().map(Self::from_serde_json).collect())
 			}
-			serde_json::Value::Object(o) => Self::Object(
-				o.into_iter()
-					.map(|(k, v)| Entry::new(k.into(), Self::from_serde_json(v)))
-					.collect(),
-			),
+			serde_json::Value::Object(o) => Self::Object(Object::from_serde_json_map(o)),
 		}
 	}
 
@@ -61,11 +57,7 @@ impl Value {
 			Self::Array(a) => {
 				serde_json::Value::Array(a.into_iter().map(Value::into_serde_json).collect())
 			}
-			Self::Object(o) => serde_json::Value::Object(
-				o.into_iter()
-					.map(|Entry { key, value }| (key.into_string(), Value::into_serde_json(value)))
-					.collect(),
-			),
+			Self::Object(o) => serde_json::Value::Object(o.into_serde_json_map()),
 		}
 	}
 }
@@ -82,3 +74,47 @@ impl From<Value> for serde_json::Value {
 		value.into_serde_json()
 	}
 }
+
+impl Object {
+	/// Converts a [`serde_json::Map`] into an `Object`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// let mut a = serde_json::Map::new();
+	/// a.insert("foo".to_string(), 1.into());
+	///
+	/// let b = json_syntax::Object::from_serde_json_map(a);
+	/// assert_eq!(b.get_i64("foo"), Ok(Some(1)));
+	///
+	/// let _ = b.into_serde_json_map();
+	/// ```
+	pub fn from_serde_json_map(map: serde_json::Map<String, serde_json::Value>) -> Self {
+		map.into_iter()
+			.map(|(k, v)| Entry::new(k.into(), Value::from_serde_json(v)))
+			.collect()
+	}
+
+	/// Converts an `Object` into a [`serde_json::Map`].
+	///
+	/// If the object contains duplicate keys, only the last entry of each key
+	/// is kept.
+	pub fn into_serde_json_map(self) -> serde_json::Map<String, serde_json::Value> {
+		self.into_iter()
+			.map(|Entry { key, value }| (key.into_string(), Value::into_serde_json(value)))
+			.collect()
+	}
+}
+
+impl From<serde_json::Map<String, serde_json::Value>> for Object {
+	#[inline(always)]
+	fn from(map: serde_json::Map<String, serde_json::Value>) -> Self {
+		Self::from_serde_json_map(map)
+	}
+}
+
+impl From<Object> for serde_json::Map<String, serde_json::Value> {
+	fn from(object: Object) -> Self {
+		object.into_serde_json_map()
+	}
+}
@@ -0,0 +1,199 @@
+use std::borrow::Cow;
+
+use simd_json::{BorrowedValue, OwnedValue, StaticNode};
+
+use crate::{object::Entry, NumberBuf, Value};
+
+/// Converts a `simd_json` static node into a `Value`.
+///
+/// Non-finite floating point numbers are converted into `null`.
+fn from_static_node(node: StaticNode) -> Value {
+	match node {
+		StaticNode::Null => Value::Null,
+		StaticNode::Bool(b) => Value::Boolean(b),
+		StaticNode::I64(n) => Value::Number(n.into()),
+		StaticNode::U64(n) => Value::Number(n.into()),
+		StaticNode::F64(n) => NumberBuf::try_from(n)
+			.map(Value::Number)
+			.unwrap_or(Value::Null),
+		#[allow(unreachable_patterns)]
+		node => Value::Number(node.to_string().parse().unwrap()),
+	}
+}
+
+/// Converts a number into a `simd_json` static node.
+///
+/// Numbers that are not 64-bit integers are converted into the closest
+/// floating point number.
+fn into_static_node(n: &crate::Number) -> StaticNode {
+	if let Some(n) = n.as_u64() {
+		StaticNode::U64(n)
+	} else if let Some(n) = n.as_i64() {
+		StaticNode::I64(n)
+	} else {
+		StaticNode::F64(n.as_f64_lossy())
+	}
+}
+
+impl Value {
+	/// Converts a [`simd_json::OwnedValue`] into a `Value`.
+	///
+	/// Non-finite floating point numbers are converted into `null`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// let mut input = br#"{ "foo": 1, "bar": [2.5, "baz"] }"#.to_vec();
+	///
+	/// // We parse the input with `simd_json`.
+	/// let a = simd_json::to_owned_value(&mut input).unwrap();
+	///
+	/// // We convert the `simd_json` value into a `json_syntax` value.
+	/// let b = json_syntax::Value::from_simd_json(a);
+	/// assert_eq!(b, json_syntax::json!({ "foo": 1, "bar": [2.5, "baz"] }));
+	///
+	/// // We convert it back into a `simd_json` value.
+	/// let _ = b.into_simd_json();
+	/// ```
+	pub fn from_simd_json(value: OwnedValue) -> Self {
+		match value {
+			OwnedValue::Static(node) => from_static_node(node),
+			OwnedValue::String(s) => Self::String(s.into()),
+			OwnedValue::Array(a) => Self::Array(a.into_iter().map(Self::from_simd_json).collect()),
+			OwnedValue::Object(o) => Self::Object(
+				o.into_iter()
+					.map(|(k, v)| Entry::new(k.into(), Self::from_simd_json(v)))
+					.collect(),
+			),
+		}
+	}
+
+	/// Converts a [`simd_json::BorrowedValue`] into a `Value`.
+	///
+	/// Non-finite floating point numbers are converted into `null`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// let mut input = br#"{ "foo": 1, "bar": [2.5, "baz"] }"#.to_vec();
+	/// let a = simd_json::to_borrowed_value(&mut input).unwrap();
+	///
+	/// let b = json_syntax::Value::from_simd_json_borrowed(a);
+	/// assert_eq!(b, json_syntax::json!({ "foo": 1, "bar": [2.5, "baz"] }));
+	///
+	/// // Strings of the converted value are borrowed from `b`.
+	/// let _ = b.to_simd_json_borrowed();
+	/// ```
+	pub fn from_simd_json_borrowed(value: BorrowedValue) -> Self {
+		match value {
+			BorrowedValue::Static(node) => from_static_node(node),
+			BorrowedValue::String(Cow::Borrowed(s)) => Self::String(s.into()),
+			BorrowedValue::String(Cow::Owned(s)) => Self::String(s.into()),
+			BorrowedValue::Array(a) => {
+				Self::Array(a.into_iter().map(Self::from_simd_json_borrowed).collect())
+			}
+			BorrowedValue::Object(o) => Self::Object(
+				o.into_iter()
+					.map(|(k, v)| Entry::new(k.as_ref().into(), Self::from_simd_json_borrowed(v)))
+					.collect(),
+			),
+		}
+	}
+
+	/// Converts a `Value` into a [`simd_json::OwnedValue`].
+	///
+	/// Numbers that are not 64-bit integers are converted into the closest
+	/// floating point number. If an object contains duplicate keys, only the
+	/// last entry of each key is kept.
+	pub fn into_simd_json(self) -> OwnedValue {
+		match self {
+			Self::Null => OwnedValue::Static(StaticNode::Null),
+			Self::Boolean(b) => OwnedValue::Static(StaticNode::Bool(b)),
+			Self::Number(n) => OwnedValue::Static(into_static_node(&n)),
+			Self::String(s) => OwnedValue::String(s.into_string()),
+			Self::Array(a) => OwnedValue::Array(a.into_iter().map(Self::into_simd_json).collect()),
+			Self::Object(o) => OwnedValue::Object(Box::new(
+				o.into_iter()
+					.map(|Entry { key, value }| (key.into_string(), value.into_simd_json()))
+					.collect(),
+			)),
+		}
+	}
+
+	/// Converts a reference to a `Value` into a [`simd_json::BorrowedValue`]
+	/// borrowing its strings and keys.
+	///
+	/// Numbers that are not 64-bit integers are converted into the closest
+	/// floating point number. If an object contains duplicate keys, only the
+	/// last entry of each key is kept.
+	pub fn to_simd_json_borrowed(&self) -> BorrowedValue<'_> {
+		match self {
+			Self::Null => BorrowedValue::Static(StaticNode::Null),
+			Self::Boolean(b) => BorrowedValue::Static(StaticNode::Bool(*b)),
+			Self::Number(n) => BorrowedValue::Static(into_static_node(n)),
+			Self::String(s) => BorrowedValue::String(Cow::Borrowed(s)),
+			Self::Array(a) => {
+				BorrowedValue::Array(a.iter().map(Self::to_simd_json_borrowed).collect())
+			}
+			Self::Object(o) => BorrowedValue::Object(Box::new(
+				o.iter()
+					.map(|entry| {
+						(
+							Cow::Borrowed(&*entry.key),
+							entry.value.to_simd_json_borrowed(),
+						)
+					})
+					.collect(),
+			)),
+		}
+	}
+}
+
+impl From<OwnedValue> for Value {
+	#[inline(always)]
+	fn from(value: OwnedValue) -> Self {
+		Self::from_simd_json(value)
+	}
+}
+
+impl<'a> From<BorrowedValue<'a>> for Value {
+	#[inline(always)]
+	fn from(value: BorrowedValue<'a>) -> Self {
+		Self::from_simd_json_borrowed(value)
+	}
+}
+
+impl From<Value> for OwnedValue {
+	fn from(value: Value) -> Self {
+		value.into_simd_json()
+	}
+}
+
+impl<'a> From<&'a Value> for BorrowedValue<'a> {
+	fn from(value: &'a Value) -> Self {
+		value.to_simd_json_borrowed()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{json, Parse};
+
+	#[test]
+	fn round_trip() {
+		let value =
+			json!({ "a": [null, true, -1, 18446744073709551615u64, 0.5], "b": { "c": "d" } });
+		assert_eq!(Value::from_simd_json(value.clone().into_simd_json()), value);
+		assert_eq!(
+			Value::from_simd_json_borrowed(value.to_simd_json_borrowed()),
+			value
+		);
+
+		let (value, _) = Value::parse_str("[1e400, 0.1000000000000000000001]").unwrap();
+		assert_eq!(
+			Value::from_simd_json(value.into_simd_json()),
+			json!([null, 0.1])
+		);
+	}
+}
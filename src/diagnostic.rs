@@ -0,0 +1,187 @@
+//! Diagnostics rendering.
+//!
+//! Errors located in a source text (parse errors, or [`Mapped`] errors
+//! returned by [`TryFromJson`](crate::TryFromJson) using a code map) are
+//! converted into [`Diagnostic`]s, which can be rendered with a span
+//! underlined in the original source text using the
+//! [`ariadne`](https://crates.io/crates/ariadne) crate (by enabling the
+//! `ariadne` feature) or the
+//! [`codespan-reporting`](https://crates.io/crates/codespan-reporting) crate
+//! (by enabling the `codespan-reporting` feature).
+use core::fmt;
+use std::ops::Range;
+
+use locspan::Span;
+
+use crate::{analysis::span_of, code_map::Mapped, parse, CodeMap};
+
+/// Error message located in a source text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+	/// Error message.
+	pub message: String,
+
+	/// Byte span of the error in the source text.
+	pub span: Span,
+}
+
+impl Diagnostic {
+	pub fn new(message: impl fmt::Display, span: Span) -> Self {
+		Self {
+			message: message.to_string(),
+			span,
+		}
+	}
+
+	/// Creates a diagnostic from an error mapped to a fragment of the given
+	/// code map.
+	///
+	/// Returns `None` if the fragment is not in the code map.
+	pub fn from_mapped<E: fmt::Display>(error: &Mapped<E>, code_map: &CodeMap) -> Option<Self> {
+		span_of(code_map, error).map(|span| Self::new(&error.value, span))
+	}
+
+	/// Returns the byte range of the error in the source text.
+	pub fn range(&self) -> Range<usize> {
+		self.span.range()
+	}
+
+	/// Converts this diagnostic into an `ariadne` report, for the source
+	/// text with the given identifier.
+	#[cfg(feature = "ariadne")]
+	pub fn to_ariadne<Id>(&self, id: Id) -> ariadne::Report<'static, (Id, Range<usize>)>
+	where
+		Id: Clone + fmt::Debug + std::hash::Hash + Eq,
+	{
+		ariadne::Report::build(ariadne::ReportKind::Error, id.clone(), self.span.start())
+			.with_config(ariadne::Config::default().with_index_type(ariadne::IndexType::Byte))
+			.with_message(&self.message)
+			.with_label(ariadne::Label::new((id, self.range())))
+			.finish()
+	}
+
+	/// Renders this diagnostic using `ariadne`, without colors.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{diagnostic::Diagnostic, Parse, Value};
+	///
+	/// let source = "[1, 2,, 3]";
+	/// let error = Value::parse_str(source).unwrap_err();
+	///
+	/// let report = Diagnostic::from(error).render_ariadne("input.json", source);
+	/// assert!(report.contains("unexpected character `,`"));
+	/// assert!(report.contains("input.json:1:7"));
+	/// ```
+	#[cfg(feature = "ariadne")]
+	pub fn render_ariadne(&self, name: &str, source: &str) -> String {
+		let config = ariadne::Config::default()
+			.with_index_type(ariadne::IndexType::Byte)
+			.with_color(false);
+
+		let mut output = Vec::new();
+		ariadne::Report::build(ariadne::ReportKind::Error, name, self.span.start())
+			.with_config(config)
+			.with_message(&self.message)
+			.with_label(ariadne::Label::new((name, self.range())))
+			.finish()
+			.write((name, ariadne::Source::from(source)), &mut output)
+			.unwrap();
+
+		String::from_utf8(output).unwrap()
+	}
+
+	/// Converts this diagnostic into a `codespan-reporting` diagnostic, for
+	/// the file with the given identifier.
+	#[cfg(feature = "codespan-reporting")]
+	pub fn to_codespan<FileId>(
+		&self,
+		file_id: FileId,
+	) -> codespan_reporting::diagnostic::Diagnostic<FileId> {
+		use codespan_reporting::diagnostic;
+		diagnostic::Diagnostic::error()
+			.with_message(&self.message)
+			.with_labels(vec![diagnostic::Label::primary(file_id, self.range())])
+	}
+
+	/// Renders this diagnostic using `codespan-reporting`, without colors.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{diagnostic::Diagnostic, Parse, Value};
+	///
+	/// let source = "[1, 2,, 3]";
+	/// let error = Value::parse_str(source).unwrap_err();
+	///
+	/// let report = Diagnostic::from(error).render_codespan("input.json", source);
+	/// assert!(report.contains("unexpected character `,`"));
+	/// assert!(report.contains("input.json:1:7"));
+	/// ```
+	#[cfg(feature = "codespan-reporting")]
+	pub fn render_codespan(&self, name: &str, source: &str) -> String {
+		use codespan_reporting::{files::SimpleFile, term};
+		let file = SimpleFile::new(name, source);
+		let mut output = term::termcolor::NoColor::new(Vec::new());
+		term::emit(
+			&mut output,
+			&term::Config::default(),
+			&file,
+			&self.to_codespan(()),
+		)
+		.unwrap();
+
+		String::from_utf8(output.into_inner()).unwrap()
+	}
+}
+
+impl fmt::Display for Diagnostic {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.message.fmt(f)
+	}
+}
+
+impl<'a, E: fmt::Display> From<&'a parse::Error<E>> for Diagnostic {
+	fn from(error: &'a parse::Error<E>) -> Self {
+		Self::new(error, error.span())
+	}
+}
+
+impl<E: fmt::Display> From<parse::Error<E>> for Diagnostic {
+	fn from(error: parse::Error<E>) -> Self {
+		Self::from(&error)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Parse, TryFromJson, Value};
+
+	#[test]
+	fn mapped() {
+		let source = r#"{ "a": [true, "two"] }"#;
+		let (value, code_map) = Value::parse_str(source).unwrap();
+		let items = value
+			.as_object()
+			.unwrap()
+			.get_unique_mapped(&code_map, 0, "a");
+		let items = items.unwrap().unwrap();
+		let error =
+			Vec::<bool>::try_from_json_at(items.value, &code_map, items.offset).unwrap_err();
+
+		let diagnostic = Diagnostic::from_mapped(&error, &code_map).unwrap();
+		assert_eq!(&source[diagnostic.range()], r#""two""#);
+
+		#[cfg(feature = "ariadne")]
+		assert!(diagnostic
+			.render_ariadne("a.json", source)
+			.contains("expected boolean, found string"));
+
+		#[cfg(feature = "codespan-reporting")]
+		assert!(diagnostic
+			.render_codespan("a.json", source)
+			.contains("expected boolean, found string"));
+	}
+}
@@ -0,0 +1,200 @@
+use core::ops::{Index, IndexMut};
+
+use crate::{Object, Value};
+
+static NULL: Value = Value::Null;
+
+/// Returns the (first) value associated to the given key.
+///
+/// Returns [`Value::Null`] if the value is not an object, or if it has no
+/// entry with the given key.
+///
+/// # Example
+///
+/// ```
+/// use json_syntax::{json, Value};
+///
+/// let value = json!({ "users": [ { "name": "Alice" } ] });
+/// assert_eq!(value["users"][0]["name"], json!("Alice"));
+/// assert_eq!(value["groups"], Value::Null);
+/// ```
+impl<'a> Index<&'a str> for Value {
+	type Output = Value;
+
+	fn index(&self, key: &'a str) -> &Value {
+		match self {
+			Self::Object(object) => object.get(key).next().unwrap_or(&NULL),
+			_ => &NULL,
+		}
+	}
+}
+
+/// Returns the array item at the given index.
+///
+/// Returns [`Value::Null`] if the value is not an array, or if the index is
+/// out of bounds.
+impl Index<usize> for Value {
+	type Output = Value;
+
+	fn index(&self, i: usize) -> &Value {
+		match self {
+			Self::Array(array) => array.get(i).unwrap_or(&NULL),
+			_ => &NULL,
+		}
+	}
+}
+
+/// Returns a mutable reference to the (first) value associated to the given
+/// key.
+///
+/// If the value is [`Value::Null`], it is first replaced by an empty object.
+/// If the object has no entry with the given key, a `null` entry is inserted.
+///
+/// # Panics
+///
+/// Panics if the value is neither an object nor `null`.
+///
+/// # Example
+///
+/// ```
+/// use json_syntax::{json, Value};
+///
+/// let mut value = Value::Null;
+/// value["user"]["name"] = json!("Alice");
+/// assert_eq!(value, json!({ "user": { "name": "Alice" } }));
+/// ```
+impl<'a> IndexMut<&'a str> for Value {
+	fn index_mut(&mut self, key: &'a str) -> &mut Value {
+		if self.is_null() {
+			*self = Self::Object(Object::new())
+		}
+
+		match self {
+			Self::Object(object) => object.get_mut_or_insert_with(key, || Value::Null),
+			other => panic!("cannot index {} value with key `{key}`", other.kind()),
+		}
+	}
+}
+
+/// Returns a mutable reference to the array item at the given index.
+///
+/// # Panics
+///
+/// Panics if the value is not an array, or if the index is out of bounds.
+impl IndexMut<usize> for Value {
+	fn index_mut(&mut self, i: usize) -> &mut Value {
+		match self {
+			Self::Array(array) => {
+				let len = array.len();
+				array
+					.get_mut(i)
+					.unwrap_or_else(|| panic!("index {i} out of bounds for array of length {len}"))
+			}
+			other => panic!("cannot index {} value with index {i}", other.kind()),
+		}
+	}
+}
+
+/// Object key or array index.
+///
+/// Argument of [`Value::get`] and [`Value::get_mut`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Accessor<'a> {
+	/// Object key.
+	Key(&'a str),
+
+	/// Array index.
+	Index(usize),
+}
+
+impl<'a> From<&'a str> for Accessor<'a> {
+	fn from(key: &'a str) -> Self {
+		Self::Key(key)
+	}
+}
+
+impl<'a> From<&'a String> for Accessor<'a> {
+	fn from(key: &'a String) -> Self {
+		Self::Key(key)
+	}
+}
+
+impl<'a> From<usize> for Accessor<'a> {
+	fn from(i: usize) -> Self {
+		Self::Index(i)
+	}
+}
+
+impl Value {
+	/// Returns the (first) value associated to the given key if this is an
+	/// object, or the item at the given index if this is an array.
+	///
+	/// Returns `None` if the value is not of the expected kind, or if no
+	/// such entry or item exists. Unlike indexing, this never falls back to
+	/// `null` and composes with the `?` operator.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, Value};
+	///
+	/// let value = json!({ "a": [{ "b": true }] });
+	///
+	/// fn read(value: &Value) -> Option<&Value> {
+	///   value.get("a")?.get(0)?.get("b")
+	/// }
+	///
+	/// assert_eq!(read(&value), Some(&json!(true)));
+	/// assert_eq!(value.get(0), None);
+	/// assert_eq!(value.get("a").and_then(|a| a.get(1)), None);
+	/// ```
+	pub fn get<'a>(&self, accessor: impl Into<Accessor<'a>>) -> Option<&Value> {
+		match (self, accessor.into()) {
+			(Self::Object(object), Accessor::Key(key)) => object.get(key).next(),
+			(Self::Array(array), Accessor::Index(i)) => array.get(i),
+			_ => None,
+		}
+	}
+
+	/// Returns a mutable reference to the (first) value associated to the
+	/// given key if this is an object, or the item at the given index if this
+	/// is an array.
+	///
+	/// See [`Self::get`].
+	pub fn get_mut<'a>(&mut self, accessor: impl Into<Accessor<'a>>) -> Option<&mut Value> {
+		match (self, accessor.into()) {
+			(Self::Object(object), Accessor::Key(key)) => object.get_mut(key).next(),
+			(Self::Array(array), Accessor::Index(i)) => array.get_mut(i),
+			_ => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{json, Value};
+
+	#[test]
+	fn index_mut() {
+		let mut value = json!({ "a": [1, 2] });
+		value["a"][1] = json!(3);
+		value["b"]["c"] = json!(true);
+		assert_eq!(value, json!({ "a": [1, 3], "b": { "c": true } }))
+	}
+
+	#[test]
+	fn get_mut() {
+		let mut value = json!({ "a": [1, 2] });
+		*value.get_mut("a").unwrap().get_mut(1).unwrap() = json!(3);
+		assert!(value.get_mut(0).is_none());
+		assert!(value.get_mut("b").is_none());
+		assert_eq!(value, json!({ "a": [1, 3] }))
+	}
+
+	#[test]
+	#[should_panic]
+	fn index_mut_out_of_bounds() {
+		let mut value = json!([1]);
+		value[1] = Value::Null
+	}
+}
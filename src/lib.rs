@@ -21,7 +21,16 @@
 //! - JSON Canonicalization Scheme implementation ([RFC 8785](https://www.rfc-editor.org/rfc/rfc8785))
 //!   enabled with the `canonicalization` feature.
 //! - `serde` support (by enabling the `serde` feature).
+//! - Drop-in replacement for the `serde_json` top-level API in
+//!   `compat::serde_json` (by enabling the `serde` feature).
 //! - Conversion from/to `serde_json::Value` (by enabling the `serde_json` feature).
+//! - Conversion from/to `simd_json` values (by enabling the `simd-json` feature).
+//! - Conversion from/to CBOR (by enabling the `cbor` feature).
+//! - Conversion from/to MessagePack (by enabling the `msgpack` feature).
+//! - Conversion from/to YAML (by enabling the `yaml` feature).
+//! - `Arbitrary` values for property testing (by enabling the `arbitrary` feature).
+//! - Diagnostics rendering (by enabling the `ariadne` or `codespan-reporting`
+//!   feature).
 //! - Thoroughly tested.
 //!
 //! # Usage
@@ -37,23 +46,52 @@
 //! ```
 pub use json_number::{InvalidNumber, Number};
 use smallvec::SmallVec;
-use std::{fmt, str::FromStr};
+use std::{fmt, ops::ControlFlow, str::FromStr};
 
+pub mod analysis;
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
 pub mod array;
+mod builder;
+#[cfg(feature = "cbor")]
+pub mod cbor;
 pub mod code_map;
+pub mod compat;
+#[cfg(any(feature = "ariadne", feature = "codespan-reporting"))]
+pub mod diagnostic;
+mod index;
+pub use index::Accessor;
+mod mapped;
+pub use mapped::{MappedRef, MappedValue};
+pub mod numeric;
+pub use numeric::NumericOrd;
 pub mod object;
 pub mod parse;
+pub mod patch;
+pub mod pattern;
+pub mod pointer;
+pub mod schema;
+pub mod shared;
 mod unordered;
+pub use builder::*;
 pub use code_map::CodeMap;
 pub use parse::Parse;
+pub use patch::diff;
+pub use shared::SharedValue;
 pub mod print;
+mod transform;
 pub use print::Print;
+pub use transform::*;
 pub mod kind;
+#[cfg(feature = "msgpack")]
+pub mod msgpack;
 pub use kind::{Kind, KindSet};
 mod convert;
 mod macros;
 mod try_from;
 pub use try_from::*;
+#[cfg(feature = "yaml")]
+pub mod yaml;
 
 pub mod number {
 	pub use json_number::Buffer;
@@ -150,7 +188,8 @@ pub type NumberBuf = json_number::SmallNumberBuf<NUMBER_CAPACITY>;
 /// options.indent = json_syntax::print::Indent::Tabs(1);
 /// println!("{}", value.print_with(options)); // multi line, indent with tabs
 /// ```
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(not(feature = "compact-debug"), derive(Debug))]
 pub enum Value {
 	/// `null`.
 	Null,
@@ -171,7 +210,7 @@ pub enum Value {
 	Object(Object),
 }
 
-pub fn get_array_fragment(array: &[Value], mut index: usize) -> Result<FragmentRef, usize> {
+pub fn get_array_fragment(array: &[Value], mut index: usize) -> Result<FragmentRef<'_>, usize> {
 	for v in array {
 		match v.get_fragment(index) {
 			Ok(value) => return Ok(value),
@@ -183,7 +222,28 @@ pub fn get_array_fragment(array: &[Value], mut index: usize) -> Result<FragmentR
 }
 
 impl Value {
-	pub fn get_fragment(&self, index: usize) -> Result<FragmentRef, usize> {
+	/// Creates a number value from its decimal representation.
+	///
+	/// The lexical form is preserved as is (trailing zeros, exponent, etc.),
+	/// after checking that it is a valid JSON number.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::Value;
+	///
+	/// let value = Value::number("123.450").unwrap();
+	/// assert_eq!(value.as_number().unwrap().as_str(), "123.450");
+	///
+	/// assert!(Value::number("0x10").is_err());
+	/// assert!(Value::number("+1").is_err());
+	/// ```
+	#[inline]
+	pub fn number(s: &str) -> Result<Self, InvalidNumber<&str>> {
+		Number::new(s).map(Self::from)
+	}
+
+	pub fn get_fragment(&self, index: usize) -> Result<FragmentRef<'_>, usize> {
 		if index == 0 {
 			Ok(FragmentRef::Value(self))
 		} else {
@@ -284,6 +344,32 @@ impl Value {
 		}
 	}
 
+	/// Returns the value as an `i64`, if it is a number that fits.
+	#[inline]
+	pub fn as_i64(&self) -> Option<i64> {
+		self.as_number().and_then(Number::as_i64)
+	}
+
+	/// Returns the value as an `u64`, if it is a number that fits.
+	#[inline]
+	pub fn as_u64(&self) -> Option<u64> {
+		self.as_number().and_then(Number::as_u64)
+	}
+
+	/// Returns the value as an `usize`, if it is a number that fits.
+	#[inline]
+	pub fn as_usize(&self) -> Option<usize> {
+		self.as_u64().and_then(|n| n.try_into().ok())
+	}
+
+	/// Returns the value as an `f64`, if it is a number.
+	///
+	/// The conversion may lose precision.
+	#[inline]
+	pub fn as_f64(&self) -> Option<f64> {
+		self.as_number().map(Number::as_f64_lossy)
+	}
+
 	#[inline]
 	pub fn as_string(&self) -> Option<&str> {
 		match self {
@@ -390,12 +476,87 @@ impl Value {
 		}
 	}
 
-	pub fn traverse(&self) -> Traverse {
+	pub fn traverse(&self) -> Traverse<'_> {
 		let mut stack = SmallVec::new();
 		stack.push(FragmentRef::Value(self));
 		Traverse { offset: 0, stack }
 	}
 
+	/// Recursively visits this value and all its nested values, allowing them
+	/// to be modified in place.
+	///
+	/// The function `f` is called with the [JSON Pointer](crate::pointer) to
+	/// each value, before its nested values are visited. This means that
+	/// nested values are visited *after* being potentially modified by `f`.
+	/// Object entries and array items are visited in order. The traversal stops
+	/// as soon as `f` returns [`ControlFlow::Break`].
+	///
+	/// The traversal is implemented without recursion, so it cannot overflow
+	/// the stack even on deeply nested values.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use std::ops::ControlFlow;
+	/// use json_syntax::{json, Value};
+	///
+	/// let mut value = json!({
+	///   "user": { "name": "Alice", "password": "hunter2" },
+	///   "tokens": [ { "password": "1234" } ]
+	/// });
+	///
+	/// value.traverse_mut(|pointer, value| {
+	///   if pointer.ends_with("/password") {
+	///     *value = json!("***")
+	///   }
+	///
+	///   ControlFlow::Continue(())
+	/// });
+	///
+	/// assert_eq!(value, json!({
+	///   "user": { "name": "Alice", "password": "***" },
+	///   "tokens": [ { "password": "***" } ]
+	/// }));
+	/// ```
+	pub fn traverse_mut(&mut self, mut f: impl FnMut(&str, &mut Value) -> ControlFlow<()>) {
+		let mut pointer = std::string::String::new();
+		let mut stack: Vec<(usize, Option<TokenRef<'_>>, &mut Value)> = vec![(0, None, self)];
+
+		while let Some((len, token, value)) = stack.pop() {
+			pointer.truncate(len);
+			if let Some(token) = token {
+				pointer.push('/');
+				match token {
+					TokenRef::Key(key) => pointer.push_str(&pointer::escape_token(key)),
+					TokenRef::Index(i) => pointer.push_str(&i.to_string()),
+				}
+			}
+
+			if f(&pointer, value).is_break() {
+				break;
+			}
+
+			let start = stack.len();
+			let len = pointer.len();
+			match value {
+				Self::Array(items) => stack.extend(
+					items
+						.iter_mut()
+						.enumerate()
+						.map(|(i, item)| (len, Some(TokenRef::Index(i)), item)),
+				),
+				Self::Object(object) => stack.extend(
+					object
+						.iter_mut()
+						.map(|(key, value)| (len, Some(TokenRef::Key(key)), value)),
+				),
+				_ => (),
+			}
+
+			stack[start..].reverse()
+		}
+	}
+
 	/// Recursively count the number of values for which `f` returns `true`.
 	pub fn count(&self, mut f: impl FnMut(usize, FragmentRef) -> bool) -> usize {
 		self.traverse().filter(|(i, q)| f(*i, *q)).count()
@@ -411,6 +572,97 @@ impl Value {
 		self.traverse().filter(|(_, f)| f.is_value()).count()
 	}
 
+	/// Returns the maximum nesting depth of the value.
+	///
+	/// Each array or object adds one level of nesting, so the depth of a
+	/// literal (`null`, boolean, number, string) is `0`, and the depth of
+	/// `[[]]` is `2`. The depth is computed without recursion, using
+	/// [`Self::traverse`].
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::json;
+	///
+	/// assert_eq!(json!("a").depth(), 0);
+	/// assert_eq!(json!([]).depth(), 1);
+	/// assert_eq!(json!({ "a": [1, { "b": null }], "c": 2 }).depth(), 3);
+	/// ```
+	pub fn depth(&self) -> usize {
+		// Number of fragments remaining to be visited in each array, object
+		// or entry being traversed, and whether it is an array or object.
+		let mut stack: SmallVec<[(usize, bool); 8]> = SmallVec::new();
+		let mut depth = 0;
+		let mut max = 0;
+
+		for (_, fragment) in self.traverse() {
+			while let Some((0, collection)) = stack.last() {
+				depth -= *collection as usize;
+				stack.pop();
+			}
+
+			if let Some((remaining, _)) = stack.last_mut() {
+				*remaining -= 1
+			}
+
+			let (remaining, collection) = match fragment {
+				FragmentRef::Value(Self::Array(items)) => (items.len(), true),
+				FragmentRef::Value(Self::Object(object)) => (object.len(), true),
+				FragmentRef::Entry(_) => (2, false),
+				_ => continue,
+			};
+
+			depth += collection as usize;
+			max = max.max(depth);
+			stack.push((remaining, collection))
+		}
+
+		max
+	}
+
+	/// Returns the approximate number of bytes used to store this value in
+	/// memory, including nested values.
+	///
+	/// This accounts for the size of the value itself, strings and numbers
+	/// spilled on the heap (inline small strings are free), the capacity of
+	/// arrays and objects, and the object key indexes. Allocator overhead is
+	/// not taken into account.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, Value};
+	///
+	/// let small = json!("a");
+	/// let large = json!("a string too long to be stored inline");
+	///
+	/// assert_eq!(small.approximate_byte_size(), std::mem::size_of::<Value>());
+	/// assert!(large.approximate_byte_size() > small.approximate_byte_size());
+	/// ```
+	pub fn approximate_byte_size(&self) -> usize {
+		let mut size = std::mem::size_of::<Self>();
+		let mut stack: SmallVec<[&Value; 8]> = SmallVec::new();
+		stack.push(self);
+
+		while let Some(value) = stack.pop() {
+			match value {
+				Self::Number(n) if n.buffer().spilled() => size += n.buffer().capacity(),
+				Self::String(s) if s.spilled() => size += s.capacity(),
+				Self::Array(items) => {
+					size += items.capacity() * std::mem::size_of::<Self>();
+					stack.extend(items.iter())
+				}
+				Self::Object(object) => {
+					size += object.shallow_heap_size();
+					stack.extend(object.iter().map(|entry| &entry.value))
+				}
+				_ => (),
+			}
+		}
+
+		size
+	}
+
 	/// Move and return the value, leaves `null` in its place.
 	#[inline(always)]
 	pub fn take(&mut self) -> Self {
@@ -419,6 +671,22 @@ impl Value {
 		result
 	}
 
+	/// Moves the value into an [`Arc`](std::sync::Arc), so it can be shared
+	/// between threads.
+	#[inline(always)]
+	pub fn into_shared(self) -> std::sync::Arc<Self> {
+		std::sync::Arc::new(self)
+	}
+
+	/// Makes a mutable reference into the given shared value.
+	///
+	/// If the value is shared with other `Arc` pointers, it is cloned first
+	/// (copy-on-write). See [`Arc::make_mut`](std::sync::Arc::make_mut).
+	#[inline(always)]
+	pub fn make_mut(this: &mut std::sync::Arc<Self>) -> &mut Self {
+		std::sync::Arc::make_mut(this)
+	}
+
 	/// Puts this JSON value in canonical form according to
 	/// [RFC 8785](https://www.rfc-editor.org/rfc/rfc8785).
 	///
@@ -444,6 +712,46 @@ impl Value {
 		let mut buffer = ryu_js::Buffer::new();
 		self.canonicalize_with(&mut buffer)
 	}
+
+	/// Checks that this JSON value is in canonical form according to
+	/// [RFC 8785](https://www.rfc-editor.org/rfc/rfc8785), as produced by
+	/// [`Self::canonicalize_with`], without modifying it.
+	///
+	/// The given `buffer` is used to canonicalize the number values.
+	#[cfg(feature = "canonicalize")]
+	pub fn is_canonical_with(&self, buffer: &mut ryu_js::Buffer) -> bool {
+		match self {
+			Self::Number(n) => n.canonical_with(buffer).as_str() == n.as_str(),
+			Self::Array(a) => a.iter().all(|item| item.is_canonical_with(buffer)),
+			Self::Object(o) => o.is_canonical_with(buffer),
+			_ => true,
+		}
+	}
+
+	/// Checks that this JSON value is in canonical form according to
+	/// [RFC 8785](https://www.rfc-editor.org/rfc/rfc8785), without modifying
+	/// it.
+	///
+	/// This is equivalent to checking that [`Self::canonicalize`] would
+	/// leave the value unchanged, and is useful to reject non-canonical
+	/// signed payloads.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, Parse, Value};
+	///
+	/// assert!(json!({ "a": [1, 0.002], "b": null }).is_canonical());
+	/// assert!(!json!({ "b": null, "a": 1 }).is_canonical());
+	///
+	/// let (value, _) = Value::parse_str("[4.50]").unwrap();
+	/// assert!(!value.is_canonical());
+	/// ```
+	#[cfg(feature = "canonicalize")]
+	pub fn is_canonical(&self) -> bool {
+		let mut buffer = ryu_js::Buffer::new();
+		self.is_canonical_with(&mut buffer)
+	}
 }
 
 impl UnorderedPartialEq for Value {
@@ -553,6 +861,48 @@ from_integer! {
 	i64
 }
 
+macro_rules! from_wide_integer {
+	($($ty:ident),*) => {
+		$(
+			impl From<$ty> for Value {
+				fn from(n: $ty) -> Self {
+					// SAFETY: the decimal representation of an integer is a
+					//         valid JSON number.
+					Value::Number(unsafe {
+						NumberBuf::new_unchecked(SmallVec::from_vec(n.to_string().into_bytes()))
+					})
+				}
+			}
+		)*
+	};
+}
+
+from_wide_integer! {
+	u128,
+	i128
+}
+
+macro_rules! try_from_size {
+	($($ty:ident => $target:ident),*) => {
+		$(
+			impl TryFrom<$ty> for Value {
+				type Error = std::num::TryFromIntError;
+
+				/// Converts the integer into a number value, failing only on
+				/// platforms where it does not fit in 64 bits.
+				fn try_from(n: $ty) -> Result<Self, Self::Error> {
+					Ok(Value::Number($target::try_from(n)?.into()))
+				}
+			}
+		)*
+	};
+}
+
+try_from_size! {
+	usize => u64,
+	isize => i64
+}
+
 macro_rules! try_from_float {
 	($($ty:ident),*) => {
 		$(
@@ -676,6 +1026,12 @@ impl<'a> DoubleEndedIterator for SubFragments<'a> {
 	}
 }
 
+/// Reference to a JSON Pointer reference token.
+enum TokenRef<'a> {
+	Key(&'a str),
+	Index(usize),
+}
+
 pub struct Traverse<'a> {
 	offset: usize,
 	stack: SmallVec<[FragmentRef<'a>; 8]>,
@@ -720,6 +1076,19 @@ mod tests {
 		)
 	}
 
+	#[cfg(feature = "canonicalize")]
+	#[test]
+	fn is_canonical() {
+		use super::*;
+		let (mut value, _) =
+			Value::parse_str("{\"b\": [1E30, {\"d\": 1, \"c\": 2}], \"a\": -0.0, \"a\": \"x\"}")
+				.unwrap();
+		assert!(!value.is_canonical());
+
+		value.canonicalize();
+		assert!(value.is_canonical());
+	}
+
 	#[cfg(feature = "canonicalize")]
 	#[test]
 	fn canonicalize_02() {
@@ -733,6 +1102,11 @@ mod tests {
 		)
 		.unwrap();
 
+		assert_eq!(
+			value.canonical_print().to_string(),
+			"{\"literals\":[null,true,false],\"numbers\":[333333333.3333333,1e+30,4.5,0.002,1e-27],\"string\":\"€$\\u000f\\nA'B\\\"\\\\\\\\\\\"/\"}"
+		);
+
 		value.canonicalize();
 
 		assert_eq!(
@@ -740,4 +1114,120 @@ mod tests {
 			"{\"literals\":[null,true,false],\"numbers\":[333333333.3333333,1e+30,4.5,0.002,1e-27],\"string\":\"€$\\u000f\\nA'B\\\"\\\\\\\\\\\"/\"}"
 		)
 	}
+
+	#[cfg(feature = "canonicalize")]
+	#[test]
+	fn canonical_print_duplicate_keys() {
+		use super::*;
+		let (mut value, _) =
+			Value::parse_str(r#"{"a":5E-1,"a":0.7,"b":[{"c":1E1,"c":9}]}"#).unwrap();
+		let printed = value.canonical_print().to_string();
+
+		value.canonicalize();
+		assert_eq!(printed, value.compact_print().to_string());
+		assert_eq!(printed, r#"{"a":0.5,"a":0.7,"b":[{"c":10,"c":9}]}"#)
+	}
+
+	#[test]
+	fn numeric_accessors() {
+		use super::*;
+		let value = json!([-1, 2, 2.5, "3"]);
+		assert_eq!(value[0].as_i64(), Some(-1));
+		assert_eq!(value[0].as_u64(), None);
+		assert_eq!(value[1].as_usize(), Some(2));
+		assert_eq!(value[2].as_i64(), None);
+		assert_eq!(value[2].as_f64(), Some(2.5));
+		assert_eq!(value[3].as_f64(), None);
+	}
+
+	#[test]
+	fn wide_integers() {
+		use super::*;
+		let n = u128::MAX;
+		let value = Value::from(n);
+		assert_eq!(value.compact_print().to_string(), n.to_string());
+		assert_eq!(
+			u128::try_from_json(&value, &CodeMap::default()).ok(),
+			Some(n)
+		);
+
+		let n = i128::MIN;
+		let value = Value::from(n);
+		assert_eq!(value.compact_print().to_string(), n.to_string());
+		assert_eq!(
+			i128::try_from_json(&value, &CodeMap::default()).ok(),
+			Some(n)
+		);
+	}
+
+	#[test]
+	fn number_constructors() {
+		use super::*;
+		assert_eq!(
+			Value::number("-0.5e10")
+				.unwrap()
+				.as_number()
+				.unwrap()
+				.as_str(),
+			"-0.5e10"
+		);
+		assert!(Value::number("").is_err());
+		assert!(Value::number("01").is_err());
+		assert!(Value::number("1.").is_err());
+		assert_eq!(
+			Value::try_from(usize::MAX).unwrap(),
+			json!(usize::MAX as u64)
+		);
+		assert_eq!(
+			Value::try_from(isize::MIN).unwrap(),
+			json!(isize::MIN as i64)
+		);
+	}
+
+	#[test]
+	fn traverse_mut() {
+		use super::*;
+		let mut value = json!({ "a/b": [1, { "c": 2 }], "d": 3 });
+		let mut pointers = Vec::new();
+		value.traverse_mut(|pointer, value| {
+			pointers.push(pointer.to_owned());
+			if let Some(n) = value.as_i64() {
+				*value = (n * 10).into()
+			}
+
+			if pointer == "/a~1b/1" {
+				ControlFlow::Break(())
+			} else {
+				ControlFlow::Continue(())
+			}
+		});
+
+		assert_eq!(pointers, ["", "/a~1b", "/a~1b/0", "/a~1b/1"]);
+		assert_eq!(value, json!({ "a/b": [10, { "c": 2 }], "d": 3 }))
+	}
+
+	#[test]
+	fn depth_nested() {
+		use super::*;
+		let input =
+			std::fs::read_to_string("tests/inputs/y_structure_500_nested_arrays.json").unwrap();
+		let (value, _) = Value::parse_str(&input).unwrap();
+		assert_eq!(value.depth(), 500);
+
+		assert_eq!(json!([[], [[{ "a": {} }]], 1]).depth(), 5);
+		assert_eq!(json!({ "a": [], "b": { "c": [1] }, "d": 2 }).depth(), 3)
+	}
+
+	#[test]
+	fn approximate_byte_size() {
+		use super::*;
+		let value_size = std::mem::size_of::<Value>();
+
+		let mut array = Vec::with_capacity(4);
+		array.push(json!(null));
+		assert_eq!(Value::Array(array).approximate_byte_size(), 5 * value_size);
+
+		let object = json!({ "a": [], "a long key that is not inline": null });
+		assert!(object.approximate_byte_size() > 3 * value_size + 30)
+	}
 }
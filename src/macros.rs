@@ -30,6 +30,27 @@
 /// });
 /// ```
 ///
+/// Keys can be computed from any expression, optionally parenthesized.
+///
+/// ```
+/// # use json_syntax::json;
+/// let key = "code";
+/// let value = json!({ key: 200, (format!("{key}_text")): "OK" });
+/// assert_eq!(value, json!({ "code": 200, "code_text": "OK" }));
+/// ```
+///
+/// An entry can be made optional by adding `?` after its key. The value
+/// must then be an `Option`, and the entry is omitted if it is `None`.
+///
+/// ```
+/// # use json_syntax::json;
+/// let name: Option<&str> = None;
+/// let age = Some(30);
+///
+/// let value = json!({ "name"?: name, "age"?: age, "id": 0 });
+/// assert_eq!(value, json!({ "age": 30, "id": 0 }));
+/// ```
+///
 /// Trailing commas are allowed inside both arrays and objects.
 ///
 /// ```
@@ -121,12 +142,32 @@ macro_rules! json {
 
 	// Done with trailing comma.
 	(@object [$($elems:expr,)*] () () ()) => {
-		$crate::Object::from_vec(json_vec![$($elems,)*])
+		json!(@entries [$($elems),*])
 	};
 
 	// Done without trailing comma.
 	(@object [$($elems:expr),*] () () ()) => {
-		$crate::Object::from_vec(json_vec![$($elems),*])
+		json!(@entries [$($elems),*])
+	};
+
+	// Collect the optional entries.
+	(@entries [$($elems:expr),*]) => {{
+		let entries = [$($elems),*];
+		let mut vec = ::std::vec::Vec::with_capacity(entries.len());
+		vec.extend(::core::iter::IntoIterator::into_iter(entries).flatten());
+		$crate::Object::from_vec(vec)
+	}};
+
+	// Create an entry.
+	(@entry ($($key:tt)+) $value:expr) => {
+		::core::option::Option::Some($crate::object::Entry::new(json!(@key ($($key)+)), $value))
+	};
+
+	// Create an optional entry, omitted if the value is `None`.
+	(@optional ($($key:tt)+) $value:expr) => {
+		::core::option::Option::map($value, |value| {
+			$crate::object::Entry::new(json!(@key ($($key)+)), $crate::Value::from(value))
+		})
 	};
 
 	// Create an entry literal key.
@@ -141,42 +182,52 @@ macro_rules! json {
 
 	// Next value is `null`.
 	(@object [$($elems:expr,)*] ($($key:tt)+) (: null $($rest:tt)*) $copy:tt) => {
-		json!(@object [$($elems,)* $crate::object::Entry::new(json!(@key ($($key)+)), json!(null))] () ($($rest)*) ($($rest)*))
+		json!(@object [$($elems,)* json!(@entry ($($key)+) json!(null))] () ($($rest)*) ($($rest)*))
 	};
 
 	// Next value is `true`.
 	(@object [$($elems:expr,)*] ($($key:tt)+) (: true $($rest:tt)*) $copy:tt) => {
-		json!(@object [$($elems,)* $crate::object::Entry::new(json!(@key ($($key)+)), json!(true))] () ($($rest)*) ($($rest)*))
+		json!(@object [$($elems,)* json!(@entry ($($key)+) json!(true))] () ($($rest)*) ($($rest)*))
 	};
 
 	// Next value is `false`.
 	(@object [$($elems:expr,)*] ($($key:tt)+) (: false $($rest:tt)*) $copy:tt) => {
-		json!(@object [$($elems,)* $crate::object::Entry::new(json!(@key ($($key)+)), json!(false))] () ($($rest)*) ($($rest)*))
+		json!(@object [$($elems,)* json!(@entry ($($key)+) json!(false))] () ($($rest)*) ($($rest)*))
 	};
 
 	// Next value is a literal.
 	(@object [$($elems:expr,)*] ($($key:tt)+) (: $lit:literal $($rest:tt)*) $copy:tt) => {
-		json!(@object [$($elems,)* $crate::object::Entry::new(json!(@key ($($key)+)), json!($lit))] () ($($rest)*) ($($rest)*))
+		json!(@object [$($elems,)* json!(@entry ($($key)+) json!($lit))] () ($($rest)*) ($($rest)*))
 	};
 
 	// Next value is a array.
 	(@object [$($elems:expr,)*] ($($key:tt)+) (: [$($array:tt)*] $($rest:tt)*) $copy:tt) => {
-		json!(@object [$($elems,)* $crate::object::Entry::new(json!(@key ($($key)+)), json!([$($array)*]))] () ($($rest)*) ($($rest)*))
+		json!(@object [$($elems,)* json!(@entry ($($key)+) json!([$($array)*]))] () ($($rest)*) ($($rest)*))
 	};
 
 	// Next value is a map.
 	(@object [$($elems:expr,)*] ($($key:tt)+) (: {$($map:tt)*} $($rest:tt)*) $copy:tt) => {
-		json!(@object [$($elems,)* $crate::object::Entry::new(json!(@key ($($key)+)), json!({$($map)*}))] () ($($rest)*) ($($rest)*))
+		json!(@object [$($elems,)* json!(@entry ($($key)+) json!({$($map)*}))] () ($($rest)*) ($($rest)*))
 	};
 
 	// Next value is an expression followed by comma.
 	(@object [$($elems:expr,)*] ($($key:tt)+) (: $next:expr, $($rest:tt)*) $copy:tt) => {
-		json!(@object [$($elems,)* $crate::object::Entry::new(json!(@key ($($key)+)), json!($next)),] () ($($rest)*) ($($rest)*))
+		json!(@object [$($elems,)* json!(@entry ($($key)+) json!($next)),] () ($($rest)*) ($($rest)*))
 	};
 
 	// Last value is an expression with no trailing comma.
 	(@object [$($elems:expr,)*] ($($key:tt)+) (: $last:expr) $copy:tt) => {
-		json!(@object [$($elems,)* $crate::object::Entry::new(json!(@key ($($key)+)), json!($last))] () () ())
+		json!(@object [$($elems,)* json!(@entry ($($key)+) json!($last))] () () ())
+	};
+
+	// Next optional value is an expression followed by comma.
+	(@object [$($elems:expr,)*] ($($key:tt)+) (? : $next:expr, $($rest:tt)*) $copy:tt) => {
+		json!(@object [$($elems,)* json!(@optional ($($key)+) $next),] () ($($rest)*) ($($rest)*))
+	};
+
+	// Last optional value is an expression with no trailing comma.
+	(@object [$($elems:expr,)*] ($($key:tt)+) (? : $last:expr) $copy:tt) => {
+		json!(@object [$($elems,)* json!(@optional ($($key)+) $last)] () () ())
 	};
 
 	// Comma after the most recent element.
@@ -268,6 +319,225 @@ macro_rules! json {
 	};
 }
 
+/// Destructures a `json_syntax::Value` against a pattern.
+///
+/// `json_match!(value, pattern => body)` evaluates `body` with the binders
+/// of `pattern` in scope, and returns `Ok(body)`, or a
+/// [`MatchError`](crate::pattern::MatchError) if `value` does not match the
+/// pattern. Patterns are:
+/// - `_`, matching any value;
+/// - `null`, `true`, `false`, or a string or number literal, matching an
+///   equal value (numbers are compared lexically, as with `==`);
+/// - `name`, binding any value to `name` as a `&Value`;
+/// - `name as Type`, binding any value to `name` after converting it to
+///   `Type` (see [`Extract`](crate::pattern::Extract));
+/// - `{ "key": pattern, ... }`, matching an object containing (at least)
+///   the given keys, and the value of each key (the first one if there
+///   are duplicates) against the given pattern;
+/// - `[pattern, ...]`, matching an array with exactly the given number of
+///   items, against the given patterns;
+/// - `[pattern, ..., ..]`, matching an array with at least the given
+///   number of items.
+///
+/// ```
+/// # use json_syntax::{json, json_match, Value};
+/// let value = json!({ "id": 42, "tags": ["a", "b"], "meta": { "owner": "x" } });
+///
+/// let (id, first, owner) = json_match!(value, {
+///   "id": id as u64,
+///   "tags": [first as &str, ..],
+///   "meta": { "owner": owner }
+/// } => (id, first, owner)).unwrap();
+///
+/// assert_eq!(id, 42);
+/// assert_eq!(first, "a");
+/// assert_eq!(*owner, json!("x"));
+///
+/// assert!(json_match!(value, [_, ..] => ()).is_err());
+/// ```
+#[macro_export]
+macro_rules! json_match {
+	($value:expr, $name:ident as $ty:ty => $body:expr) => {
+		match &$value {
+			value => 'json_match: {
+				let value: &$crate::Value = ::core::borrow::Borrow::borrow(value);
+				$crate::json_match!(@bind 'json_match, value, $name as $ty);
+				::core::result::Result::Ok::<_, $crate::pattern::MatchError>($body)
+			}
+		}
+	};
+
+	($value:expr, $pattern:tt => $body:expr) => {
+		match &$value {
+			value => 'json_match: {
+				let value: &$crate::Value = ::core::borrow::Borrow::borrow(value);
+				$crate::json_match!(@bind 'json_match, value, $pattern);
+				::core::result::Result::Ok::<_, $crate::pattern::MatchError>($body)
+			}
+		}
+	};
+
+	// Unwraps the given result, or breaks out of the match with its error.
+	(@try $l:lifetime, $e:expr) => {
+		match $e {
+			::core::result::Result::Ok(value) => value,
+			::core::result::Result::Err(e) => break $l ::core::result::Result::Err(e),
+		}
+	};
+
+	//////////////////////////////////////////////////////////////////////////
+	// Bind a value against a pattern.
+	//////////////////////////////////////////////////////////////////////////
+
+	(@bind $l:lifetime, $v:ident, _) => {
+		let _ = $v;
+	};
+
+	(@bind $l:lifetime, $v:ident, null) => {
+		$crate::json_match!(@try $l, $crate::pattern::expect_value($v, &$crate::Value::Null));
+	};
+
+	(@bind $l:lifetime, $v:ident, $lit:literal) => {
+		$crate::json_match!(@try $l, $crate::pattern::expect_value($v, &$crate::json!($lit)));
+	};
+
+	(@bind $l:lifetime, $v:ident, { $($entries:tt)* }) => {
+		let object = $crate::json_match!(@try $l, $crate::pattern::expect_object($v));
+		$crate::json_match!(@object $l, object, $($entries)*);
+	};
+
+	(@bind $l:lifetime, $v:ident, [ $($items:tt)* ]) => {
+		let array = $crate::json_match!(@try $l, $crate::pattern::expect_array($v));
+		$crate::json_match!(@array $l, array, [] () $($items)*);
+	};
+
+	(@bind $l:lifetime, $v:ident, $name:ident as $ty:ty) => {
+		let $name: $ty = $crate::json_match!(@try $l, $crate::pattern::Extract::extract($v));
+	};
+
+	(@bind $l:lifetime, $v:ident, $name:ident) => {
+		let $name: &$crate::Value = $v;
+	};
+
+	//////////////////////////////////////////////////////////////////////////
+	// TT muncher for object patterns.
+	//////////////////////////////////////////////////////////////////////////
+
+	(@object $l:lifetime, $o:ident, ) => {};
+
+	(@object $l:lifetime, $o:ident, $key:literal : $($rest:tt)*) => {
+		$crate::json_match!(@entry $l, $o, $key, () $($rest)*);
+	};
+
+	// Entry pattern followed by a comma.
+	(@entry $l:lifetime, $o:ident, $key:literal, ($($pattern:tt)+) , $($rest:tt)*) => {
+		let item = $crate::json_match!(@try $l, $crate::pattern::get($o, $key));
+		$crate::json_match!(@bind $l, item, $($pattern)+);
+		$crate::json_match!(@object $l, $o, $($rest)*);
+	};
+
+	// Last entry pattern.
+	(@entry $l:lifetime, $o:ident, $key:literal, ($($pattern:tt)+)) => {
+		let item = $crate::json_match!(@try $l, $crate::pattern::get($o, $key));
+		$crate::json_match!(@bind $l, item, $($pattern)+);
+	};
+
+	// Munch a token into the entry pattern.
+	(@entry $l:lifetime, $o:ident, $key:literal, ($($pattern:tt)*) $next:tt $($rest:tt)*) => {
+		$crate::json_match!(@entry $l, $o, $key, ($($pattern)* $next) $($rest)*);
+	};
+
+	//////////////////////////////////////////////////////////////////////////
+	// TT muncher for array patterns.
+	//////////////////////////////////////////////////////////////////////////
+
+	// Rest pattern.
+	(@array $l:lifetime, $a:ident, [$($done:tt)*] () .. $(,)?) => {
+		$crate::json_match!(@items $l, $a, false, [$($done)*]);
+	};
+
+	// Done.
+	(@array $l:lifetime, $a:ident, [$($done:tt)*] ()) => {
+		$crate::json_match!(@items $l, $a, true, [$($done)*]);
+	};
+
+	// Item pattern followed by a comma.
+	(@array $l:lifetime, $a:ident, [$($done:tt)*] ($($pattern:tt)+) , $($rest:tt)*) => {
+		$crate::json_match!(@array $l, $a, [$($done)* ($($pattern)+)] () $($rest)*);
+	};
+
+	// Last item pattern.
+	(@array $l:lifetime, $a:ident, [$($done:tt)*] ($($pattern:tt)+)) => {
+		$crate::json_match!(@array $l, $a, [$($done)* ($($pattern)+)] ());
+	};
+
+	// Munch a token into the item pattern.
+	(@array $l:lifetime, $a:ident, [$($done:tt)*] ($($pattern:tt)*) $next:tt $($rest:tt)*) => {
+		$crate::json_match!(@array $l, $a, [$($done)*] ($($pattern)* $next) $($rest)*);
+	};
+
+	(@items $l:lifetime, $a:ident, $exact:expr, [$(($($pattern:tt)+))*]) => {
+		let expected = <[()]>::len(&[$($crate::json_match!(@unit $($pattern)+)),*]);
+		$crate::json_match!(@try $l, $crate::pattern::check_len($a, expected, $exact));
+
+		#[allow(unused_mut, unused_variables)]
+		let mut items = $a.iter();
+		$(
+			let item = items.next().unwrap();
+			$crate::json_match!(@bind $l, item, $($pattern)+);
+		)*
+	};
+
+	(@unit $($tt:tt)*) => {
+		()
+	};
+}
+
+/// Matches a `json_syntax::Value` against a list of patterns.
+///
+/// `match_json!(value, pattern => body, ...)` evaluates the `body` of the
+/// first arm whose `pattern` matches `value`, with the binders of `pattern`
+/// in scope. It returns `Some(body)`, or `None` if no pattern matches.
+/// Patterns are the same as those of [`json_match!`](crate::json_match).
+///
+/// ```
+/// # use json_syntax::{json, match_json, Value};
+/// fn describe(value: &Value) -> String {
+///   match_json!(value,
+///     { "type": "user", "id": id as u64 } => format!("user {id}"),
+///     { "type": "group", "members": members as &[Value] } => {
+///       format!("group of {}", members.len())
+///     },
+///     [first, ..] => format!("list starting with {first}"),
+///     _ => "unknown".to_owned()
+///   )
+///   .unwrap()
+/// }
+///
+/// assert_eq!(describe(&json!({ "type": "user", "id": 1 })), "user 1");
+/// assert_eq!(describe(&json!({ "type": "group", "members": [1, 2] })), "group of 2");
+/// assert_eq!(describe(&json!(["a", "b"])), "list starting with \"a\"");
+/// assert_eq!(describe(&json!({ "type": "user" })), "unknown");
+///
+/// assert_eq!(match_json!(json!(true), false => ()), None);
+/// ```
+#[macro_export]
+macro_rules! match_json {
+	($value:expr, $($pattern:tt => $body:expr),+ $(,)?) => {
+		match &$value {
+			value => 'match_json: {
+				let value: &$crate::Value = ::core::borrow::Borrow::borrow(value);
+				$(
+					if let ::core::result::Result::Ok(result) = $crate::json_match!(value, $pattern => $body) {
+						break 'match_json ::core::option::Option::Some(result);
+					}
+				)+
+				::core::option::Option::None
+			}
+		}
+	};
+}
+
 // The json_internal macro above cannot invoke vec directly because it uses
 // local_inner_macros. A vec invocation there would resolve to $crate::vec.
 // Instead invoke vec here outside of local_inner_macros.
@@ -0,0 +1,205 @@
+use locspan::Span;
+
+use crate::{array::JsonArray, code_map::Mapped, parse, Accessor, CodeMap, Parse, Value};
+
+/// Value bundled with its code map, and optionally its source text.
+///
+/// Provides navigation methods returning [`MappedRef`]s, keeping track of the
+/// fragment offset of each visited value in the code map.
+///
+/// # Example
+///
+/// ```
+/// use json_syntax::MappedValue;
+///
+/// let doc = MappedValue::parse_str(r#"{ "users": [{ "name": 42 }] }"#).unwrap();
+///
+/// let name = doc.get("users").and_then(|users| users.get(0)?.get("name")).unwrap();
+/// assert_eq!(name.value().as_u64(), Some(42));
+/// assert_eq!(name.span().unwrap().range(), 22..24);
+/// assert_eq!(name.text(), Some("42"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MappedValue {
+	value: Value,
+	code_map: CodeMap,
+	source: Option<String>,
+}
+
+impl MappedValue {
+	/// Bundles a value with its code map.
+	pub fn new(value: Value, code_map: CodeMap) -> Self {
+		Self {
+			value,
+			code_map,
+			source: None,
+		}
+	}
+
+	/// Parses the given source text, keeping a copy of it.
+	pub fn parse_str(source: &str) -> Result<Self, parse::Error> {
+		let (value, code_map) = Value::parse_str(source)?;
+		Ok(Self::new(value, code_map).with_source(source.to_owned()))
+	}
+
+	/// Attaches the source text of the value.
+	pub fn with_source(mut self, source: String) -> Self {
+		self.source = Some(source);
+		self
+	}
+
+	pub fn value(&self) -> &Value {
+		&self.value
+	}
+
+	pub fn code_map(&self) -> &CodeMap {
+		&self.code_map
+	}
+
+	/// Returns the source text of the value, if any.
+	pub fn source(&self) -> Option<&str> {
+		self.source.as_deref()
+	}
+
+	pub fn into_parts(self) -> (Value, CodeMap, Option<String>) {
+		(self.value, self.code_map, self.source)
+	}
+
+	/// Returns a reference to the root value.
+	pub fn root(&self) -> MappedRef<'_> {
+		MappedRef {
+			value: &self.value,
+			code_map: &self.code_map,
+			source: self.source.as_deref(),
+			offset: 0,
+		}
+	}
+
+	/// Returns the (first) value associated to the given key, or the array
+	/// item at the given index, in the root value.
+	///
+	/// See [`MappedRef::get`].
+	pub fn get<'a>(&self, accessor: impl Into<Accessor<'a>>) -> Option<MappedRef<'_>> {
+		self.root().get(accessor)
+	}
+
+	/// Returns the value referenced by the given JSON Pointer.
+	///
+	/// See [`Value::pointer`].
+	pub fn pointer(&self, pointer: &str) -> Option<MappedRef<'_>> {
+		let offset = self.code_map.fragment_at_pointer(&self.value, pointer)?;
+		Some(MappedRef {
+			value: self.value.pointer(pointer)?,
+			code_map: &self.code_map,
+			source: self.source.as_deref(),
+			offset,
+		})
+	}
+}
+
+impl From<(Value, CodeMap)> for MappedValue {
+	fn from((value, code_map): (Value, CodeMap)) -> Self {
+		Self::new(value, code_map)
+	}
+}
+
+/// Reference to a value of a [`MappedValue`].
+#[derive(Debug, Clone, Copy)]
+pub struct MappedRef<'a> {
+	value: &'a Value,
+	code_map: &'a CodeMap,
+	source: Option<&'a str>,
+	offset: usize,
+}
+
+impl<'a> MappedRef<'a> {
+	pub fn value(&self) -> &'a Value {
+		self.value
+	}
+
+	/// Returns the fragment offset of the value in the code map.
+	pub fn offset(&self) -> usize {
+		self.offset
+	}
+
+	/// Returns the span of the value in the source text.
+	pub fn span(&self) -> Option<Span> {
+		self.code_map.get(self.offset).map(|entry| entry.span)
+	}
+
+	/// Returns the source text of the value, if the source is available.
+	pub fn text(&self) -> Option<&'a str> {
+		self.source?.get(self.span()?.range())
+	}
+
+	/// Returns the (first) value associated to the given key if this is an
+	/// object, or the item at the given index if this is an array.
+	pub fn get<'k>(&self, accessor: impl Into<Accessor<'k>>) -> Option<Self> {
+		let mapped = match (self.value, accessor.into()) {
+			(Value::Object(object), Accessor::Key(key)) => {
+				object.get_mapped(self.code_map, self.offset, key).next()
+			}
+			(Value::Array(items), Accessor::Index(i)) => {
+				items.iter_mapped(self.code_map, self.offset).nth(i)
+			}
+			_ => None,
+		}?;
+
+		Some(self.with(mapped))
+	}
+
+	/// Returns an iterator over the items of this value, if it is an array.
+	pub fn items(&self) -> Option<impl 'a + Iterator<Item = MappedRef<'a>>> {
+		let this = *self;
+		let items = self.value.as_array()?;
+		Some(
+			items
+				.iter_mapped(self.code_map, self.offset)
+				.map(move |mapped| this.with(mapped)),
+		)
+	}
+
+	fn with(&self, mapped: Mapped<&'a Value>) -> Self {
+		Self {
+			value: mapped.value,
+			offset: mapped.offset,
+			..*self
+		}
+	}
+}
+
+impl<'a> From<MappedRef<'a>> for Mapped<&'a Value> {
+	fn from(value: MappedRef<'a>) -> Self {
+		Mapped::new(value.offset, value.value)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn navigation() {
+		let doc = MappedValue::parse_str(r#"[{ "a": [1, 2] }, { "a": { "b": 3 } }]"#).unwrap();
+		let texts: Vec<_> = doc
+			.root()
+			.items()
+			.unwrap()
+			.map(|item| item.get("a").unwrap().text().unwrap())
+			.collect();
+		assert_eq!(texts, ["[1, 2]", r#"{ "b": 3 }"#]);
+
+		let b = doc.pointer("/1/a/b").unwrap();
+		assert_eq!(b.offset(), 13);
+		assert_eq!(b.text(), Some("3"));
+		assert_eq!(Mapped::from(b), Mapped::new(13, &crate::json!(3)));
+
+		assert!(doc.get(2).is_none());
+		assert!(doc.get("a").is_none());
+		assert!(doc.get(0).unwrap().get(0).is_none());
+
+		let doc = MappedValue::from(Value::parse_str("[1]").unwrap());
+		assert_eq!(doc.get(0).unwrap().span(), Some(Span::new(1, 2)));
+		assert_eq!(doc.get(0).unwrap().text(), None);
+	}
+}
@@ -0,0 +1,525 @@
+//! [MessagePack](https://msgpack.org/) conversion.
+//!
+//! Numbers are encoded as integers when they fit in 64 bits, and as double
+//! precision floats otherwise (which may lose precision). Numbers beyond the
+//! range of double precision floats (such as `1e400`) are clamped to the
+//! largest finite float of the same sign, since MessagePack decoding rejects
+//! non-finite floats. Object entries are encoded in order, including
+//! duplicate entries, unless specified otherwise with [`Options`].
+//!
+//! MessagePack lengths are 32 bits: encoding a string longer than
+//! `u32::MAX` bytes, or an array or object with more than `u32::MAX` items,
+//! panics.
+use core::fmt;
+use std::collections::HashSet;
+
+use crate::{object::Key, Number, NumberBuf, Object, Value};
+
+/// MessagePack encoding options.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Options {
+	/// Sort object entries by key (the sort is stable).
+	///
+	/// By default entries are encoded in order.
+	pub sort_keys: bool,
+
+	/// Only encode the first entry of each key.
+	///
+	/// By default duplicate entries are encoded.
+	pub dedup_keys: bool,
+}
+
+/// MessagePack decoding error.
+///
+/// Each variant (but `UnexpectedEnd`) holds the offset of the offending
+/// object in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Error {
+	/// Unexpected end of input.
+	UnexpectedEnd,
+
+	/// Input bytes remain after the decoded object.
+	TrailingBytes(usize),
+
+	/// Malformed object.
+	Malformed(usize),
+
+	/// Object with no JSON equivalent, such as binary or extension objects.
+	Unsupported(usize),
+
+	/// Map key that is not a string.
+	InvalidKey(usize),
+
+	/// String that is not valid UTF-8.
+	InvalidUtf8(usize),
+
+	/// Infinite or NaN float.
+	NonFiniteFloat(usize),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::UnexpectedEnd => write!(f, "unexpected end of MessagePack input"),
+			Self::TrailingBytes(i) => write!(f, "trailing bytes at offset {i}"),
+			Self::Malformed(i) => write!(f, "malformed MessagePack object at offset {i}"),
+			Self::Unsupported(i) => write!(f, "unsupported MessagePack object at offset {i}"),
+			Self::InvalidKey(i) => write!(f, "non-string map key at offset {i}"),
+			Self::InvalidUtf8(i) => write!(f, "invalid UTF-8 string at offset {i}"),
+			Self::NonFiniteFloat(i) => write!(f, "non-finite float at offset {i}"),
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+impl Value {
+	/// Encodes this value as MessagePack, preserving entries order and
+	/// duplicate entries.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, Value};
+	///
+	/// let value = json!({ "b": [1, -200, 0.5, "c", null, true], "a": {} });
+	/// let bytes = value.to_msgpack_bytes();
+	/// assert_eq!(Value::from_msgpack_slice(&bytes).unwrap(), value);
+	/// ```
+	pub fn to_msgpack_bytes(&self) -> Vec<u8> {
+		self.to_msgpack_bytes_with(Options::default())
+	}
+
+	/// Encodes this value as MessagePack with the given options.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, Value, msgpack::Options};
+	///
+	/// let value = json!({ "b": 0, "a": 1, "b": 2 });
+	/// let bytes = value.to_msgpack_bytes_with(Options { sort_keys: true, dedup_keys: true });
+	/// assert_eq!(Value::from_msgpack_slice(&bytes).unwrap(), json!({ "a": 1, "b": 0 }));
+	/// ```
+	pub fn to_msgpack_bytes_with(&self, options: Options) -> Vec<u8> {
+		let mut bytes = Vec::new();
+		self.write_msgpack(&mut bytes, options);
+		bytes
+	}
+
+	/// Encodes this value as MessagePack, appending the bytes to `bytes`.
+	///
+	/// # Panics
+	///
+	/// Panics if a string, array or object is too long to be encoded (more
+	/// than `u32::MAX` bytes or items).
+	pub fn write_msgpack(&self, bytes: &mut Vec<u8>, options: Options) {
+		enum Item<'a> {
+			Key(&'a Key),
+			Value(&'a Value),
+		}
+
+		let mut stack = vec![Item::Value(self)];
+		while let Some(item) = stack.pop() {
+			match item {
+				Item::Key(key) => write_str(bytes, key),
+				Item::Value(Self::Null) => bytes.push(0xc0),
+				Item::Value(Self::Boolean(false)) => bytes.push(0xc2),
+				Item::Value(Self::Boolean(true)) => bytes.push(0xc3),
+				Item::Value(Self::Number(n)) => write_number(bytes, n),
+				Item::Value(Self::String(s)) => write_str(bytes, s),
+				Item::Value(Self::Array(items)) => {
+					write_len(bytes, 0x90, 0xdc, items.len());
+					stack.extend(items.iter().rev().map(Item::Value))
+				}
+				Item::Value(Self::Object(object)) => {
+					let mut entries: Vec<_> = if options.dedup_keys {
+						let mut seen = HashSet::new();
+						object.iter().filter(|e| seen.insert(&*e.key)).collect()
+					} else {
+						object.iter().collect()
+					};
+
+					if options.sort_keys {
+						entries.sort_by(|a, b| a.key.cmp(&b.key))
+					}
+
+					write_len(bytes, 0x80, 0xde, entries.len());
+					for entry in entries.into_iter().rev() {
+						stack.push(Item::Value(&entry.value));
+						stack.push(Item::Key(&entry.key))
+					}
+				}
+			}
+		}
+	}
+
+	/// Decodes a value from MessagePack bytes.
+	///
+	/// The input must contain exactly one object. Binary and extension
+	/// objects are not supported, and map keys must be strings. Entries order
+	/// and duplicate entries are preserved.
+	pub fn from_msgpack_slice(bytes: &[u8]) -> Result<Self, Error> {
+		Decoder { bytes, pos: 0 }.decode()
+	}
+}
+
+/// Writes an array or map length, using the fix format when possible.
+///
+/// The 16 and 32 bits formats immediately follow `marker16`.
+fn write_len(bytes: &mut Vec<u8>, fix: u8, marker16: u8, len: usize) {
+	if len < 16 {
+		bytes.push(fix | len as u8)
+	} else if len <= u16::MAX as usize {
+		bytes.push(marker16);
+		bytes.extend((len as u16).to_be_bytes())
+	} else {
+		bytes.push(marker16 + 1);
+		bytes.extend(len32(len).to_be_bytes())
+	}
+}
+
+/// Converts a length to 32 bits, the largest length format.
+fn len32(len: usize) -> u32 {
+	u32::try_from(len).expect("length too large for MessagePack")
+}
+
+fn write_str(bytes: &mut Vec<u8>, s: &str) {
+	let len = s.len();
+	if len < 32 {
+		bytes.push(0xa0 | len as u8)
+	} else if len <= u8::MAX as usize {
+		bytes.push(0xd9);
+		bytes.push(len as u8)
+	} else if len <= u16::MAX as usize {
+		bytes.push(0xda);
+		bytes.extend((len as u16).to_be_bytes())
+	} else {
+		bytes.push(0xdb);
+		bytes.extend(len32(len).to_be_bytes())
+	}
+
+	bytes.extend(s.as_bytes())
+}
+
+fn write_number(bytes: &mut Vec<u8>, n: &Number) {
+	if let Some(i) = n.as_u64() {
+		if i < 0x80 {
+			bytes.push(i as u8)
+		} else if i <= u8::MAX as u64 {
+			bytes.push(0xcc);
+			bytes.push(i as u8)
+		} else if i <= u16::MAX as u64 {
+			bytes.push(0xcd);
+			bytes.extend((i as u16).to_be_bytes())
+		} else if i <= u32::MAX as u64 {
+			bytes.push(0xce);
+			bytes.extend((i as u32).to_be_bytes())
+		} else {
+			bytes.push(0xcf);
+			bytes.extend(i.to_be_bytes())
+		}
+	} else if let Some(i) = n.as_i64().filter(|i| *i < 0) {
+		if i >= -32 {
+			bytes.push(i as u8)
+		} else if i >= i8::MIN as i64 {
+			bytes.push(0xd0);
+			bytes.push(i as u8)
+		} else if i >= i16::MIN as i64 {
+			bytes.push(0xd1);
+			bytes.extend((i as i16).to_be_bytes())
+		} else if i >= i32::MIN as i64 {
+			bytes.push(0xd2);
+			bytes.extend((i as i32).to_be_bytes())
+		} else {
+			bytes.push(0xd3);
+			bytes.extend(i.to_be_bytes())
+		}
+	} else {
+		let f = n.as_f64_lossy().clamp(f64::MIN, f64::MAX);
+		bytes.push(0xcb);
+		bytes.extend(f.to_bits().to_be_bytes())
+	}
+}
+
+enum Frame {
+	Array {
+		remaining: usize,
+		items: Vec<Value>,
+	},
+	Object {
+		remaining: usize,
+		object: Object,
+		key: Option<Key>,
+	},
+}
+
+/// Decoded object.
+enum Item {
+	Value(Value),
+	Array(usize),
+	Object(usize),
+}
+
+struct Decoder<'a> {
+	bytes: &'a [u8],
+	pos: usize,
+}
+
+impl<'a> Decoder<'a> {
+	fn decode(mut self) -> Result<Value, Error> {
+		let mut stack: Vec<Frame> = Vec::new();
+
+		loop {
+			let completed = matches!(
+				stack.last(),
+				Some(Frame::Array { remaining: 0, .. })
+					| Some(Frame::Object {
+						remaining: 0,
+						key: None,
+						..
+					})
+			);
+
+			let value = if completed {
+				match stack.pop().unwrap() {
+					Frame::Array { items, .. } => Value::Array(items),
+					Frame::Object { object, .. } => Value::Object(object),
+				}
+			} else {
+				if let Some(Frame::Object {
+					key: key @ None, ..
+				}) = stack.last_mut()
+				{
+					*key = Some(self.read_key()?);
+				}
+
+				match self.read_item()? {
+					Item::Value(value) => value,
+					Item::Array(remaining) => {
+						stack.push(Frame::Array {
+							remaining,
+							items: Vec::with_capacity(self.capacity(remaining)),
+						});
+						continue;
+					}
+					Item::Object(remaining) => {
+						stack.push(Frame::Object {
+							remaining,
+							object: Object::with_capacity(self.capacity(remaining)),
+							key: None,
+						});
+						continue;
+					}
+				}
+			};
+
+			match stack.last_mut() {
+				Some(Frame::Array { remaining, items }) => {
+					items.push(value);
+					*remaining -= 1
+				}
+				Some(Frame::Object {
+					remaining,
+					object,
+					key,
+				}) => {
+					object.push(key.take().unwrap(), value);
+					*remaining -= 1
+				}
+				None => {
+					return if self.pos < self.bytes.len() {
+						Err(Error::TrailingBytes(self.pos))
+					} else {
+						Ok(value)
+					};
+				}
+			}
+		}
+	}
+
+	/// Initial capacity for an array or map with the given length.
+	///
+	/// Bounded by the remaining input length to avoid large allocations on
+	/// malformed inputs.
+	fn capacity(&self, len: usize) -> usize {
+		len.min(self.bytes.len() - self.pos)
+	}
+
+	fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], Error> {
+		let end = self
+			.pos
+			.checked_add(len)
+			.filter(|end| *end <= self.bytes.len())
+			.ok_or(Error::UnexpectedEnd)?;
+		let bytes = &self.bytes[self.pos..end];
+		self.pos = end;
+		Ok(bytes)
+	}
+
+	fn read_array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+		Ok(self.read_bytes(N)?.try_into().unwrap())
+	}
+
+	/// Reads a length, given the marker of the 8 bits length format, the 16
+	/// and 32 bits formats being the next markers.
+	fn read_len(&mut self, marker: u8, marker8: u8) -> Result<usize, Error> {
+		match marker - marker8 {
+			0 => Ok(self.read_array::<1>()?[0] as usize),
+			1 => Ok(u16::from_be_bytes(self.read_array()?) as usize),
+			_ => Ok(u32::from_be_bytes(self.read_array()?) as usize),
+		}
+	}
+
+	fn read_key(&mut self) -> Result<Key, Error> {
+		let offset = self.pos;
+		match self.read_item()? {
+			Item::Value(Value::String(s)) => Ok(s.as_str().into()),
+			_ => Err(Error::InvalidKey(offset)),
+		}
+	}
+
+	fn read_str(&mut self, offset: usize, len: usize) -> Result<Value, Error> {
+		let bytes = self.read_bytes(len)?;
+		std::str::from_utf8(bytes)
+			.map(|s| Value::String(s.into()))
+			.map_err(|_| Error::InvalidUtf8(offset))
+	}
+
+	fn read_item(&mut self) -> Result<Item, Error> {
+		let offset = self.pos;
+		let marker = self.read_array::<1>()?[0];
+
+		let value = match marker {
+			0x00..=0x7f => Value::from(marker),
+			0x80..=0x8f => return Ok(Item::Object((marker & 0x0f) as usize)),
+			0x90..=0x9f => return Ok(Item::Array((marker & 0x0f) as usize)),
+			0xa0..=0xbf => {
+				return self
+					.read_str(offset, (marker & 0x1f) as usize)
+					.map(Item::Value)
+			}
+			0xc0 => Value::Null,
+			0xc2 => Value::Boolean(false),
+			0xc3 => Value::Boolean(true),
+			0xc4..=0xc9 | 0xd4..=0xd8 => return Err(Error::Unsupported(offset)),
+			0xca => float(offset, f32::from_be_bytes(self.read_array()?) as f64)?,
+			0xcb => float(offset, f64::from_be_bytes(self.read_array()?))?,
+			0xcc => Value::from(self.read_array::<1>()?[0]),
+			0xcd => Value::from(u16::from_be_bytes(self.read_array()?)),
+			0xce => Value::from(u32::from_be_bytes(self.read_array()?)),
+			0xcf => Value::from(u64::from_be_bytes(self.read_array()?)),
+			0xd0 => Value::from(i8::from_be_bytes(self.read_array()?)),
+			0xd1 => Value::from(i16::from_be_bytes(self.read_array()?)),
+			0xd2 => Value::from(i32::from_be_bytes(self.read_array()?)),
+			0xd3 => Value::from(i64::from_be_bytes(self.read_array()?)),
+			0xd9..=0xdb => {
+				let len = self.read_len(marker, 0xd9)?;
+				self.read_str(offset, len)?
+			}
+			0xdc | 0xdd => return Ok(Item::Array(self.read_len(marker, 0xdb)?)),
+			0xde | 0xdf => return Ok(Item::Object(self.read_len(marker, 0xdd)?)),
+			0xe0..=0xff => Value::from(marker as i8),
+			0xc1 => return Err(Error::Malformed(offset)),
+		};
+
+		Ok(Item::Value(value))
+	}
+}
+
+fn float(offset: usize, f: f64) -> Result<Value, Error> {
+	NumberBuf::try_from(f)
+		.map(Value::Number)
+		.map_err(|_| Error::NonFiniteFloat(offset))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Parse, Print};
+
+	fn round_trip(input: &str) -> String {
+		let (value, _) = Value::parse_str(input).unwrap();
+		Value::from_msgpack_slice(&value.to_msgpack_bytes())
+			.unwrap()
+			.compact_print()
+			.to_string()
+	}
+
+	#[test]
+	fn integers() {
+		for input in [
+			"0",
+			"127",
+			"128",
+			"65536",
+			"18446744073709551615",
+			"-1",
+			"-32",
+			"-33",
+			"-129",
+			"-9223372036854775808",
+		] {
+			assert_eq!(round_trip(input), input)
+		}
+	}
+
+	#[test]
+	fn floats() {
+		assert_eq!(round_trip("0.5"), "0.5");
+		assert_eq!(round_trip("-1.25"), "-1.25");
+
+		for (input, expected) in [("1e400", f64::MAX), ("-1e400", f64::MIN)] {
+			let (value, _) = Value::parse_str(&round_trip(input)).unwrap();
+			assert_eq!(value.as_number().unwrap().as_f64_lossy(), expected)
+		}
+	}
+
+	#[test]
+	fn large_collections() {
+		let input = format!(
+			"[{}]",
+			(0..70000)
+				.map(|i| i.to_string())
+				.collect::<Vec<_>>()
+				.join(",")
+		);
+		assert_eq!(round_trip(&input), input);
+
+		let input = format!(
+			"{{{}}}",
+			(0..20)
+				.map(|i| format!("\"{i}\":{i}"))
+				.collect::<Vec<_>>()
+				.join(",")
+		);
+		assert_eq!(round_trip(&input), input)
+	}
+
+	#[test]
+	fn duplicate_keys() {
+		let input = r#"{"a":1,"a":[{"b":null}]}"#;
+		assert_eq!(round_trip(input), input)
+	}
+
+	#[test]
+	fn errors() {
+		assert_eq!(
+			Value::from_msgpack_slice(&[0xc4, 0x00]),
+			Err(Error::Unsupported(0))
+		);
+		assert_eq!(
+			Value::from_msgpack_slice(&[0x81, 0x01, 0x02]),
+			Err(Error::InvalidKey(1))
+		);
+		assert_eq!(
+			Value::from_msgpack_slice(&[0xc0, 0xc0]),
+			Err(Error::TrailingBytes(1))
+		);
+		assert_eq!(
+			Value::from_msgpack_slice(&[0x92, 0x01]),
+			Err(Error::UnexpectedEnd)
+		);
+		assert_eq!(Value::from_msgpack_slice(&[0xc1]), Err(Error::Malformed(0)));
+	}
+}
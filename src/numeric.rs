@@ -0,0 +1,246 @@
+use core::{borrow::Borrow, cmp::Ordering};
+
+use crate::{Number, Value};
+
+/// Decimal number decomposed as `sign * 0.digits * 10^exponent`.
+///
+/// The digits have no leading or trailing zeros. Zero has no digits.
+struct Decimal<'a> {
+	negative: bool,
+	digits: Vec<&'a [u8]>,
+	exponent: i64,
+}
+
+impl<'a> Decimal<'a> {
+	fn new(n: &'a Number) -> Self {
+		let bytes = n.as_bytes();
+		let (negative, bytes) = match bytes.split_first() {
+			Some((b'-', rest)) => (true, rest),
+			_ => (false, bytes),
+		};
+
+		let e = bytes
+			.iter()
+			.position(|b| matches!(b, b'e' | b'E'))
+			.unwrap_or(bytes.len());
+		let (mantissa, exponent) = bytes.split_at(e);
+		let (integer, fraction) = match mantissa.iter().position(|b| *b == b'.') {
+			Some(i) => (&mantissa[..i], &mantissa[(i + 1)..]),
+			None => (mantissa, &[][..]),
+		};
+
+		// Leading zeros are only found in the integer part if it is `0`.
+		let (integer, fraction, mut point) = if integer == b"0" {
+			let zeros = fraction.iter().take_while(|b| **b == b'0').count();
+			(&[][..], &fraction[zeros..], -(zeros as i64))
+		} else {
+			(integer, fraction, integer.len() as i64)
+		};
+
+		let mut digits = vec![integer, fraction];
+		while let Some(last) = digits.last_mut() {
+			let len = last.iter().rposition(|b| *b != b'0').map_or(0, |i| i + 1);
+			*last = &last[..len];
+			if last.is_empty() {
+				digits.pop();
+			} else {
+				break;
+			}
+		}
+
+		digits.retain(|d| !d.is_empty());
+
+		if !exponent.is_empty() {
+			let (negative_exponent, exponent) = match &exponent[1..] {
+				[b'-', rest @ ..] => (true, rest),
+				[b'+', rest @ ..] => (false, rest),
+				rest => (false, rest),
+			};
+
+			let exponent = exponent.iter().fold(0i64, |e, b| {
+				e.saturating_mul(10).saturating_add((b - b'0') as i64)
+			});
+
+			point = if negative_exponent {
+				point.saturating_sub(exponent)
+			} else {
+				point.saturating_add(exponent)
+			}
+		}
+
+		Self {
+			negative,
+			digits,
+			exponent: point,
+		}
+	}
+
+	fn is_zero(&self) -> bool {
+		self.digits.is_empty()
+	}
+
+	fn cmp_magnitude(&self, other: &Self) -> Ordering {
+		self.exponent.cmp(&other.exponent).then_with(|| {
+			self.digits
+				.iter()
+				.flat_map(|d| d.iter())
+				.cmp(other.digits.iter().flat_map(|d| d.iter()))
+		})
+	}
+}
+
+/// Compares two numbers by value.
+///
+/// Unlike the default ordering of numbers, which compares their lexical
+/// representation, numbers with different representations of the same
+/// value (such as `1`, `1.0` and `10e-1`) are considered equal.
+/// The comparison is exact, whatever the precision of the numbers.
+pub fn numeric_cmp(a: &Number, b: &Number) -> Ordering {
+	let a = Decimal::new(a);
+	let b = Decimal::new(b);
+
+	match (a.is_zero(), b.is_zero()) {
+		(true, true) => Ordering::Equal,
+		(true, false) => {
+			if b.negative {
+				Ordering::Greater
+			} else {
+				Ordering::Less
+			}
+		}
+		(false, true) => {
+			if a.negative {
+				Ordering::Less
+			} else {
+				Ordering::Greater
+			}
+		}
+		(false, false) => match (a.negative, b.negative) {
+			(false, false) => a.cmp_magnitude(&b),
+			(true, true) => b.cmp_magnitude(&a),
+			(false, true) => Ordering::Greater,
+			(true, false) => Ordering::Less,
+		},
+	}
+}
+
+impl Value {
+	/// Compares two values, comparing numbers by value.
+	///
+	/// This is the same total ordering as the default [`Ord`] implementation,
+	/// except that numbers (including the ones nested in arrays and objects)
+	/// are compared using [`numeric_cmp`]. Numbers with the same value but
+	/// different representations are hence considered equal.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, Value};
+	///
+	/// let mut values = vec![json!(10), json!(9.5), json!(1e1), json!(-3), json!(2)];
+	/// values.sort_by(Value::numeric_cmp);
+	///
+	/// assert_eq!(values, [json!(-3), json!(2), json!(9.5), json!(10), json!(1e1)]);
+	/// ```
+	pub fn numeric_cmp(&self, other: &Self) -> Ordering {
+		match (self, other) {
+			(Self::Number(a), Self::Number(b)) => numeric_cmp(a, b),
+			(Self::Array(a), Self::Array(b)) => {
+				for (a, b) in a.iter().zip(b) {
+					match a.numeric_cmp(b) {
+						Ordering::Equal => (),
+						ordering => return ordering,
+					}
+				}
+
+				a.len().cmp(&b.len())
+			}
+			(Self::Object(a), Self::Object(b)) => {
+				for (a, b) in a.iter().zip(b) {
+					match a
+						.key
+						.cmp(&b.key)
+						.then_with(|| a.value.numeric_cmp(&b.value))
+					{
+						Ordering::Equal => (),
+						ordering => return ordering,
+					}
+				}
+
+				a.len().cmp(&b.len())
+			}
+			_ => self.cmp(other),
+		}
+	}
+}
+
+/// Wrapper ordering values with [`Value::numeric_cmp`].
+///
+/// Equality is also numeric: `NumericOrd(json!(1))` is equal to
+/// `NumericOrd(json!(1.0))`.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::BTreeSet;
+/// use json_syntax::{json, NumericOrd};
+///
+/// let set: BTreeSet<_> = [json!(1.0), json!(0.5), json!(1), json!(10)]
+///   .into_iter()
+///   .map(NumericOrd)
+///   .collect();
+///
+/// assert_eq!(set.len(), 3);
+/// assert_eq!(set.first().unwrap().0, json!(0.5));
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct NumericOrd<T = Value>(pub T);
+
+impl<T: Borrow<Value>> PartialEq for NumericOrd<T> {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl<T: Borrow<Value>> Eq for NumericOrd<T> {}
+
+impl<T: Borrow<Value>> PartialOrd for NumericOrd<T> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl<T: Borrow<Value>> Ord for NumericOrd<T> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.0.borrow().numeric_cmp(other.0.borrow())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn cmp(a: &str, b: &str) -> Ordering {
+		numeric_cmp(Number::new(a).unwrap(), Number::new(b).unwrap())
+	}
+
+	#[test]
+	fn numbers() {
+		assert_eq!(cmp("1", "1.000"), Ordering::Equal);
+		assert_eq!(cmp("0", "-0.0e5"), Ordering::Equal);
+		assert_eq!(cmp("100", "1E2"), Ordering::Equal);
+		assert_eq!(cmp("0.01", "1e-2"), Ordering::Equal);
+		assert_eq!(cmp("10.5", "105e-1"), Ordering::Equal);
+		assert_eq!(cmp("9.99", "10"), Ordering::Less);
+		assert_eq!(cmp("0.1", "0.09"), Ordering::Greater);
+		assert_eq!(cmp("-2", "-10"), Ordering::Greater);
+		assert_eq!(cmp("-0.5", "0"), Ordering::Less);
+		assert_eq!(cmp("0", "1e-400"), Ordering::Less);
+		assert_eq!(
+			cmp("18446744073709551616", "18446744073709551615.9"),
+			Ordering::Greater
+		);
+		assert_eq!(cmp("0.30000000000000000001", "0.3"), Ordering::Greater);
+	}
+}
@@ -0,0 +1,75 @@
+use super::{Key, Object};
+use crate::Value;
+
+impl Object {
+	/// Creates a new object builder.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, Object, Value};
+	///
+	/// let nickname: Option<&str> = None;
+	/// let object = Object::builder()
+	///   .push("name", "Ferris")
+	///   .push_opt("nickname", nickname)
+	///   .push("traits", Object::builder().push("legs", 10))
+	///   .build();
+	///
+	/// assert_eq!(
+	///   Value::Object(object),
+	///   json!({ "name": "Ferris", "traits": { "legs": 10 } })
+	/// );
+	/// ```
+	pub fn builder() -> ObjectBuilder {
+		ObjectBuilder::new()
+	}
+}
+
+/// Object builder.
+///
+/// Fluent API to build objects. Nested builders can be passed directly as
+/// values. See [`Object::builder`].
+#[derive(Debug, Default, Clone)]
+pub struct ObjectBuilder(Object);
+
+impl ObjectBuilder {
+	/// Creates a new builder for an empty object.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Pushes the given entry.
+	///
+	/// Duplicates are preserved, as with [`Object::push`]. Use
+	/// [`Object::insert`] on the built object to replace existing entries.
+	pub fn push(mut self, key: impl Into<Key>, value: impl Into<Value>) -> Self {
+		self.0.push(key.into(), value.into());
+		self
+	}
+
+	/// Pushes the given entry if `value` is `Some`, skips it otherwise.
+	pub fn push_opt(self, key: impl Into<Key>, value: Option<impl Into<Value>>) -> Self {
+		match value {
+			Some(value) => self.push(key, value),
+			None => self,
+		}
+	}
+
+	/// Returns the built object.
+	pub fn build(self) -> Object {
+		self.0
+	}
+}
+
+impl From<ObjectBuilder> for Object {
+	fn from(value: ObjectBuilder) -> Self {
+		value.build()
+	}
+}
+
+impl From<ObjectBuilder> for Value {
+	fn from(value: ObjectBuilder) -> Self {
+		Value::Object(value.build())
+	}
+}
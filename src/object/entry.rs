@@ -0,0 +1,156 @@
+use super::{Entry, Key, Object};
+use crate::Value;
+
+impl Object {
+	/// Gets the entry of the given key for in-place manipulation.
+	///
+	/// If the key appears more than once in the object, the first entry
+	/// with this key is used.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, Object, Value};
+	///
+	/// let mut object = Object::new();
+	/// object.entry("count".into()).or_insert(json!(0));
+	/// object
+	///   .entry("count".into())
+	///   .and_modify(|v| *v = json!(1))
+	///   .or_insert(json!(0));
+	/// object
+	///   .entry("list".into())
+	///   .or_insert_with(|| Value::Array(Vec::new()))
+	///   .as_array_mut()
+	///   .unwrap()
+	///   .push(json!("a"));
+	///
+	/// assert_eq!(Value::Object(object), json!({ "count": 1, "list": ["a"] }));
+	/// ```
+	pub fn entry(&mut self, key: Key) -> ObjectEntry<'_> {
+		match self.index_of(&key) {
+			Some(index) => ObjectEntry::Occupied(OccupiedEntry {
+				object: self,
+				index,
+			}),
+			None => ObjectEntry::Vacant(VacantEntry { object: self, key }),
+		}
+	}
+}
+
+/// Object entry, occupied or vacant.
+///
+/// Returned by [`Object::entry`].
+pub enum ObjectEntry<'a> {
+	Occupied(OccupiedEntry<'a>),
+	Vacant(VacantEntry<'a>),
+}
+
+impl<'a> ObjectEntry<'a> {
+	/// Returns the key of this entry.
+	pub fn key(&self) -> &Key {
+		match self {
+			Self::Occupied(e) => e.key(),
+			Self::Vacant(e) => e.key(),
+		}
+	}
+
+	/// Ensures a value is in the entry by inserting `default` if it is
+	/// vacant, and returns a mutable reference to the value.
+	pub fn or_insert(self, default: Value) -> &'a mut Value {
+		match self {
+			Self::Occupied(e) => e.into_mut(),
+			Self::Vacant(e) => e.insert(default),
+		}
+	}
+
+	/// Ensures a value is in the entry by inserting the result of `f` if it
+	/// is vacant, and returns a mutable reference to the value.
+	pub fn or_insert_with(self, f: impl FnOnce() -> Value) -> &'a mut Value {
+		match self {
+			Self::Occupied(e) => e.into_mut(),
+			Self::Vacant(e) => e.insert(f()),
+		}
+	}
+
+	/// Modifies the value of the entry if it is occupied.
+	pub fn and_modify(mut self, f: impl FnOnce(&mut Value)) -> Self {
+		if let Self::Occupied(e) = &mut self {
+			f(e.get_mut())
+		}
+
+		self
+	}
+}
+
+/// Occupied object entry.
+pub struct OccupiedEntry<'a> {
+	object: &'a mut Object,
+	index: usize,
+}
+
+impl<'a> OccupiedEntry<'a> {
+	/// Returns the index of the entry in the object.
+	pub fn index(&self) -> usize {
+		self.index
+	}
+
+	/// Returns the key of the entry.
+	pub fn key(&self) -> &Key {
+		&self.object.entries[self.index].key
+	}
+
+	/// Returns a reference to the value of the entry.
+	pub fn get(&self) -> &Value {
+		&self.object.entries[self.index].value
+	}
+
+	/// Returns a mutable reference to the value of the entry.
+	pub fn get_mut(&mut self) -> &mut Value {
+		&mut self.object.entries[self.index].value
+	}
+
+	/// Converts the entry into a mutable reference to its value, bound to
+	/// the object lifetime.
+	pub fn into_mut(self) -> &'a mut Value {
+		&mut self.object.entries[self.index].value
+	}
+
+	/// Sets the value of the entry, returning the previous value.
+	pub fn insert(&mut self, value: Value) -> Value {
+		core::mem::replace(self.get_mut(), value)
+	}
+
+	/// Removes the entry from the object.
+	///
+	/// Other entries with the same key, if any, are left untouched.
+	pub fn remove(self) -> Entry {
+		self.object.remove_at(self.index).unwrap()
+	}
+}
+
+/// Vacant object entry.
+pub struct VacantEntry<'a> {
+	object: &'a mut Object,
+	key: Key,
+}
+
+impl<'a> VacantEntry<'a> {
+	/// Returns the key of the entry.
+	pub fn key(&self) -> &Key {
+		&self.key
+	}
+
+	/// Takes ownership of the key.
+	pub fn into_key(self) -> Key {
+		self.key
+	}
+
+	/// Inserts a value at the end of the object with the entry key, and
+	/// returns a mutable reference to it.
+	pub fn insert(self, value: Value) -> &'a mut Value {
+		let index = self.object.entries.len();
+		self.object.push(self.key, value);
+		&mut self.object.entries[index].value
+	}
+}
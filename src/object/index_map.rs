@@ -126,7 +126,7 @@ impl Indexes {
 		}
 	}
 
-	pub fn iter(&self) -> super::Indexes {
+	pub fn iter(&self) -> super::Indexes<'_> {
 		super::Indexes::Some {
 			first: Some(self.rep),
 			other: self.other.iter(),
@@ -166,6 +166,38 @@ impl<S> IndexMap<S> {
 		Self::default()
 	}
 
+	pub fn with_capacity(capacity: usize) -> Self
+	where
+		S: Default,
+	{
+		Self {
+			hash_builder: S::default(),
+			table: RawTable::with_capacity(capacity),
+		}
+	}
+
+	/// Returns the approximate number of bytes allocated on the heap by this
+	/// map.
+	pub fn heap_size(&self) -> usize {
+		// An unallocated table has a single (static) bucket.
+		let buckets = self.table.buckets();
+		if buckets > 1 {
+			// Each bucket has a value and a control byte, plus one group of
+			// trailing control bytes.
+			let table = buckets * (core::mem::size_of::<Indexes>() + 1) + 16;
+			let indexes: usize = unsafe {
+				self.table
+					.iter()
+					.map(|bucket| bucket.as_ref().other.capacity() * core::mem::size_of::<usize>())
+					.sum()
+			};
+
+			table + indexes
+		} else {
+			0
+		}
+	}
+
 	pub fn contains_duplicate_keys(&self) -> bool {
 		unsafe {
 			for bucket in self.table.iter() {
@@ -210,6 +242,18 @@ impl<S: BuildHasher> IndexMap<S> {
 		}
 	}
 
+	/// Reserves capacity for at least `additional` more keys.
+	pub fn reserve(&mut self, entries: &[Entry], additional: usize) {
+		self.table
+			.reserve(additional, make_hasher::<S>(entries, &self.hash_builder))
+	}
+
+	/// Shrinks the capacity of the table as much as possible.
+	pub fn shrink_to_fit(&mut self, entries: &[Entry]) {
+		self.table
+			.shrink_to(0, make_hasher::<S>(entries, &self.hash_builder))
+	}
+
 	/// Removes the association between the given key and index.
 	pub fn remove(&mut self, entries: &[Entry], index: usize) {
 		let key = &entries[index].key;
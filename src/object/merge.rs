@@ -0,0 +1,180 @@
+use super::Object;
+use crate::Value;
+
+/// Array merge strategy.
+///
+/// Used by [`Object::deep_merge`] to combine arrays found under the same
+/// key in both objects.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArrayMergeStrategy {
+	/// The items of the other array are appended to the first one.
+	Concatenate,
+
+	/// The other array replaces the first one.
+	#[default]
+	Replace,
+
+	/// Items at the same index are deep-merged. Extra items of the other
+	/// array are appended to the first one.
+	MergeByIndex,
+}
+
+/// Deep merge options.
+///
+/// Used by [`Value::deep_merge`] and [`Object::deep_merge_with`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MergeOptions {
+	/// How arrays are combined.
+	pub arrays: ArrayMergeStrategy,
+
+	/// If `true`, an object entry whose value is `null` in the other object
+	/// removes the entries with the same key instead of setting them to
+	/// `null`, as in JSON Merge Patch
+	/// ([RFC 7396](https://www.rfc-editor.org/rfc/rfc7396)).
+	pub null_removes: bool,
+}
+
+impl MergeOptions {
+	/// Creates new options with the given array merge strategy.
+	pub fn new(arrays: ArrayMergeStrategy) -> Self {
+		Self {
+			arrays,
+			null_removes: false,
+		}
+	}
+
+	/// Sets the `null_removes` option.
+	pub fn with_null_removes(mut self, value: bool) -> Self {
+		self.null_removes = value;
+		self
+	}
+}
+
+impl From<ArrayMergeStrategy> for MergeOptions {
+	fn from(value: ArrayMergeStrategy) -> Self {
+		Self::new(value)
+	}
+}
+
+impl Object {
+	/// Merges `other` into this object.
+	///
+	/// Each entry of `other` replaces the value of the first entry with the
+	/// same key in `self`, or is appended to `self` if there is no such
+	/// entry.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, Value};
+	///
+	/// let mut value = json!({ "a": { "b": 0 }, "c": 1 });
+	/// let other = json!({ "a": { "d": 2 }, "e": 3 });
+	///
+	/// value
+	///   .as_object_mut()
+	///   .unwrap()
+	///   .merge(other.into_object().unwrap());
+	///
+	/// assert_eq!(value, json!({ "a": { "d": 2 }, "c": 1, "e": 3 }));
+	/// ```
+	pub fn merge(&mut self, other: Object) {
+		for entry in other {
+			match self.index_of(&entry.key) {
+				Some(i) => self.entries[i].value = entry.value,
+				None => {
+					self.push_entry(entry);
+				}
+			}
+		}
+	}
+
+	/// Recursively merges `other` into this object.
+	///
+	/// Works like [`Self::merge`], except when both values found under the
+	/// same key are objects, in which case they are deep-merged, or arrays,
+	/// in which case they are combined following the given `strategy`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, object::ArrayMergeStrategy, Value};
+	///
+	/// let mut value = json!({ "a": { "b": 0, "l": [1] }, "c": 1 });
+	/// let other = json!({ "a": { "d": 2, "l": [2] }, "e": 3 });
+	///
+	/// value
+	///   .as_object_mut()
+	///   .unwrap()
+	///   .deep_merge(other.into_object().unwrap(), ArrayMergeStrategy::Concatenate);
+	///
+	/// assert_eq!(value, json!({ "a": { "b": 0, "l": [1, 2], "d": 2 }, "c": 1, "e": 3 }));
+	/// ```
+	pub fn deep_merge(&mut self, other: Object, strategy: ArrayMergeStrategy) {
+		self.deep_merge_with(other, MergeOptions::new(strategy))
+	}
+
+	/// Recursively merges `other` into this object, with the given options.
+	///
+	/// See [`Self::deep_merge`] and [`MergeOptions`].
+	pub fn deep_merge_with(&mut self, other: Object, options: MergeOptions) {
+		for entry in other {
+			if options.null_removes && entry.value.is_null() {
+				self.remove(&*entry.key);
+				continue;
+			}
+
+			match self.index_of(&entry.key) {
+				Some(i) => self.entries[i].value.deep_merge(entry.value, options),
+				None => {
+					self.push_entry(entry);
+				}
+			}
+		}
+	}
+}
+
+impl Value {
+	/// Recursively merges `other` into this value.
+	///
+	/// If both values are objects, they are deep-merged with
+	/// [`Object::deep_merge_with`]. If both values are arrays, they are
+	/// combined following [`MergeOptions::arrays`]. Otherwise `other`
+	/// replaces this value.
+	///
+	/// This is typically used for layered configuration loading.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, object::{ArrayMergeStrategy, MergeOptions}};
+	///
+	/// let mut config = json!({ "log": { "level": "info", "file": "a.log" }, "ports": [80, 443] });
+	/// let layer = json!({ "log": { "file": null }, "ports": [8080] });
+	///
+	/// config.deep_merge(
+	///   layer,
+	///   MergeOptions::new(ArrayMergeStrategy::MergeByIndex).with_null_removes(true)
+	/// );
+	///
+	/// assert_eq!(config, json!({ "log": { "level": "info" }, "ports": [8080, 443] }));
+	/// ```
+	pub fn deep_merge(&mut self, other: Value, options: MergeOptions) {
+		match (self, other) {
+			(Value::Object(a), Value::Object(b)) => a.deep_merge_with(b, options),
+			(Value::Array(a), Value::Array(b)) => match options.arrays {
+				ArrayMergeStrategy::Concatenate => a.extend(b),
+				ArrayMergeStrategy::Replace => *a = b,
+				ArrayMergeStrategy::MergeByIndex => {
+					let mut b = b.into_iter();
+					for (a_item, b_item) in a.iter_mut().zip(b.by_ref()) {
+						a_item.deep_merge(b_item, options)
+					}
+
+					a.extend(b)
+				}
+			},
+			(value, other) => *value = other,
+		}
+	}
+}
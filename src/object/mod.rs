@@ -1,13 +1,32 @@
-use crate::code_map::Mapped;
+use crate::code_map::{Mapped, Span};
 use crate::{CodeMap, FragmentRef, UnorderedEq, UnorderedPartialEq, Value};
 use core::cmp::Ordering;
 use core::fmt;
 use core::hash::{Hash, Hasher};
+use core::ops::RangeBounds;
 
+mod builder;
+mod entry;
 mod index_map;
+mod merge;
+mod path;
+mod sorted;
+mod typed;
 
+#[cfg(feature = "rayon")]
+mod parallel;
+
+#[cfg(feature = "rayon")]
+pub use parallel::*;
+
+pub use builder::*;
+pub use entry::*;
 pub use index_map::Equivalent;
 use index_map::IndexMap;
+pub use merge::*;
+pub use path::*;
+pub use sorted::*;
+pub use typed::*;
 
 /// Object key stack capacity.
 ///
@@ -18,6 +37,15 @@ pub const KEY_CAPACITY: usize = 16;
 /// Object key.
 pub type Key = smallstr::SmallString<[u8; KEY_CAPACITY]>;
 
+/// Returns the number of bytes allocated on the heap by a key.
+fn key_heap_size(key: &Key) -> usize {
+	if key.spilled() {
+		key.capacity()
+	} else {
+		0
+	}
+}
+
 /// Object entry.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 pub struct Entry<K = Key, V = Value> {
@@ -71,7 +99,7 @@ impl<K, V> Entry<K, V> {
 }
 
 impl Entry {
-	pub fn get_fragment(&self, index: usize) -> Result<FragmentRef, usize> {
+	pub fn get_fragment(&self, index: usize) -> Result<FragmentRef<'_>, usize> {
 		match index {
 			0 => Ok(FragmentRef::Entry(self)),
 			1 => Ok(FragmentRef::Key(&self.key)),
@@ -86,6 +114,21 @@ pub type IndexedMappedEntry<'a> = (usize, MappedEntry<'a>);
 
 pub type IndexedMappedValue<'a> = (usize, Mapped<&'a Value>);
 
+/// Spans of an object entry, its key and its value.
+///
+/// Returned by [`Object::span_of`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EntrySpans {
+	/// Span of the whole entry (key and value).
+	pub entry: Span,
+
+	/// Span of the key (including quotes).
+	pub key: Span,
+
+	/// Span of the value.
+	pub value: Span,
+}
+
 /// Object.
 #[derive(Clone)]
 pub struct Object {
@@ -110,8 +153,17 @@ impl Object {
 		Self::default()
 	}
 
+	/// Creates a new empty object with enough capacity to hold `capacity`
+	/// entries without reallocating.
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self {
+			entries: Vec::with_capacity(capacity),
+			indexes: IndexMap::with_capacity(capacity),
+		}
+	}
+
 	pub fn from_vec(entries: Vec<Entry>) -> Self {
-		let mut indexes = IndexMap::new();
+		let mut indexes = IndexMap::with_capacity(entries.len());
 		for i in 0..entries.len() {
 			indexes.insert(&entries, i);
 		}
@@ -123,6 +175,33 @@ impl Object {
 		self.entries.capacity()
 	}
 
+	/// Reserves capacity for at least `additional` more entries, in both the
+	/// entries list and the key index.
+	pub fn reserve(&mut self, additional: usize) {
+		self.entries.reserve(additional);
+		self.indexes.reserve(&self.entries, additional)
+	}
+
+	/// Shrinks the capacity of the object as much as possible.
+	pub fn shrink_to_fit(&mut self) {
+		self.entries.shrink_to_fit();
+		self.indexes.shrink_to_fit(&self.entries)
+	}
+
+	/// Returns the approximate number of bytes allocated on the heap by this
+	/// object, excluding its nested values.
+	///
+	/// This accounts for the entries capacity, the keys and the key index.
+	pub(crate) fn shallow_heap_size(&self) -> usize {
+		self.entries.capacity() * core::mem::size_of::<Entry>()
+			+ self
+				.entries
+				.iter()
+				.map(|e| key_heap_size(&e.key))
+				.sum::<usize>()
+			+ self.indexes.heap_size()
+	}
+
 	pub fn len(&self) -> usize {
 		self.entries.len()
 	}
@@ -131,7 +210,7 @@ impl Object {
 		self.entries.is_empty()
 	}
 
-	pub fn get_fragment(&self, mut index: usize) -> Result<FragmentRef, usize> {
+	pub fn get_fragment(&self, mut index: usize) -> Result<FragmentRef<'_>, usize> {
 		for e in &self.entries {
 			match e.get_fragment(index) {
 				Ok(value) => return Ok(value),
@@ -146,14 +225,71 @@ impl Object {
 		&self.entries
 	}
 
-	pub fn iter(&self) -> Iter {
+	/// Returns a mutable reference to the entries vector, allowing bulk
+	/// vector operations.
+	///
+	/// The key index is **not** updated when the entries are modified:
+	/// [`Self::rebuild_index`] must be called before using the object again.
+	/// Until then, key lookups return unspecified results, and may panic.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, Value};
+	///
+	/// let mut value = json!({ "b": 1, "a": 0 });
+	/// let object = value.as_object_mut().unwrap();
+	///
+	/// object.as_entries_mut().reverse();
+	/// object.rebuild_index();
+	///
+	/// assert_eq!(object.index_of("a"), Some(0));
+	/// ```
+	pub fn as_entries_mut(&mut self) -> &mut Vec<Entry> {
+		&mut self.entries
+	}
+
+	/// Consumes the object and returns its entries, without reallocation.
+	pub fn into_entries(self) -> Vec<Entry> {
+		self.entries
+	}
+
+	pub fn iter(&self) -> Iter<'_> {
 		self.entries.iter()
 	}
 
-	pub fn iter_mut(&mut self) -> IterMut {
+	pub fn iter_mut(&mut self) -> IterMut<'_> {
 		IterMut(self.entries.iter_mut())
 	}
 
+	/// Returns an iterator over the keys of the object, in order.
+	///
+	/// Duplicate keys are yielded as many times as they appear.
+	pub fn keys(&self) -> Keys<'_> {
+		Keys(self.entries.iter())
+	}
+
+	/// Returns an iterator over the values of the object, in order.
+	pub fn values(&self) -> ValuesIter<'_> {
+		ValuesIter(self.entries.iter())
+	}
+
+	/// Returns an iterator over mutable references to the values of the
+	/// object, in order.
+	pub fn values_mut(&mut self) -> ValuesIterMut<'_> {
+		ValuesIterMut(self.entries.iter_mut())
+	}
+
+	/// Consumes the object and returns an iterator over its keys.
+	pub fn into_keys(self) -> IntoKeys {
+		IntoKeys(self.entries.into_iter())
+	}
+
+	/// Consumes the object and returns an iterator over its values.
+	pub fn into_values(self) -> IntoValues {
+		IntoValues(self.entries.into_iter())
+	}
+
 	pub fn iter_mapped<'m>(&self, code_map: &'m CodeMap, offset: usize) -> IterMapped<'_, 'm> {
 		IterMapped {
 			entries: self.entries.iter(),
@@ -172,10 +308,40 @@ impl Object {
 		self.indexes.get(&self.entries, key).is_some()
 	}
 
+	/// Checks if at least one key appears more than once in this object.
+	///
+	/// Runs in `O(k)` where `k` is the number of distinct keys.
+	pub fn has_duplicate_keys(&self) -> bool {
+		self.indexes.contains_duplicate_keys()
+	}
+
+	/// Checks if this object contains an entry with the given key and value.
+	///
+	/// Runs in `O(k)` (average) where `k` is the number of entries with the
+	/// given key.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::json;
+	///
+	/// let value = json!({ "a": 0, "a": 1 });
+	/// let object = value.as_object().unwrap();
+	///
+	/// assert!(object.contains_entry("a", &json!(1)));
+	/// assert!(!object.contains_entry("a", &json!(2)));
+	/// ```
+	pub fn contains_entry<Q>(&self, key: &Q, value: &Value) -> bool
+	where
+		Q: ?Sized + Hash + Equivalent<Key>,
+	{
+		self.get(key).any(|v| v == value)
+	}
+
 	/// Returns an iterator over the values matching the given key.
 	///
 	/// Runs in `O(1)` (average).
-	pub fn get<Q>(&self, key: &Q) -> Values
+	pub fn get<Q>(&self, key: &Q) -> Values<'_>
 	where
 		Q: ?Sized + Hash + Equivalent<Key>,
 	{
@@ -193,7 +359,7 @@ impl Object {
 	/// Returns an iterator over the values matching the given key.
 	///
 	/// Runs in `O(1)` (average).
-	pub fn get_mut<Q>(&mut self, key: &Q) -> ValuesMut
+	pub fn get_mut<Q>(&mut self, key: &Q) -> ValuesMut<'_>
 	where
 		Q: ?Sized + Hash + Equivalent<Key>,
 	{
@@ -204,10 +370,89 @@ impl Object {
 			.unwrap_or_default();
 		ValuesMut {
 			indexes,
-			entries: &mut self.entries,
+			entries: self.entries.iter_mut(),
+			offset: 0,
+		}
+	}
+
+	/// Returns an iterator over the values matching the given key, with their
+	/// index.
+	///
+	/// Runs in `O(1)` (average).
+	pub fn get_mut_with_index<Q>(&mut self, key: &Q) -> ValuesMutWithIndex<'_>
+	where
+		Q: ?Sized + Hash + Equivalent<Key>,
+	{
+		let indexes = self
+			.indexes
+			.get(&self.entries, key)
+			.map(IntoIterator::into_iter)
+			.unwrap_or_default();
+		ValuesMutWithIndex {
+			indexes,
+			entries: self.entries.iter_mut(),
+			offset: 0,
 		}
 	}
 
+	/// Returns mutable references to the (first) values matching each of the
+	/// given keys, at once.
+	///
+	/// Returns `None` for keys that are not in the object.
+	///
+	/// # Panics
+	///
+	/// Panics if two keys are equal, even if they are not in the object.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, Value};
+	///
+	/// let mut value = json!({ "a": 1, "b": 2 });
+	/// let object = value.as_object_mut().unwrap();
+	///
+	/// let [a, b, c] = object.get_disjoint_mut(["a", "b", "c"]);
+	/// core::mem::swap(a.unwrap(), b.unwrap());
+	/// assert!(c.is_none());
+	///
+	/// assert_eq!(value, json!({ "a": 2, "b": 1 }));
+	/// ```
+	pub fn get_disjoint_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> [Option<&mut Value>; N]
+	where
+		Q: ?Sized + Hash + Equivalent<Key> + PartialEq,
+	{
+		for (i, key) in keys.iter().enumerate() {
+			if keys[..i].contains(key) {
+				panic!("duplicate keys")
+			}
+		}
+
+		let indexes = keys.map(|key| self.index_of(key));
+
+		let mut order: [usize; N] = core::array::from_fn(|i| i);
+		order.sort_unstable_by_key(|&i| indexes[i]);
+
+		let mut result: [Option<&mut Value>; N] = core::array::from_fn(|_| None);
+		let mut entries = self.entries.iter_mut();
+		let mut offset = 0;
+		let mut last = None;
+		for i in order {
+			if let Some(index) = indexes[i] {
+				if last == Some(index) {
+					panic!("duplicate keys")
+				}
+
+				let entry = entries.nth(index - offset).unwrap();
+				offset = index + 1;
+				last = Some(index);
+				result[i] = Some(&mut entry.value)
+			}
+		}
+
+		result
+	}
+
 	/// Returns the unique entry value matching the given key.
 	///
 	/// Returns an error if multiple entries match the key.
@@ -258,7 +503,7 @@ impl Object {
 	/// Returns an iterator over the entries matching the given key.
 	///
 	/// Runs in `O(1)` (average).
-	pub fn get_entries<Q>(&self, key: &Q) -> Entries
+	pub fn get_entries<Q>(&self, key: &Q) -> Entries<'_>
 	where
 		Q: ?Sized + Hash + Equivalent<Key>,
 	{
@@ -296,7 +541,7 @@ impl Object {
 	/// Returns an iterator over the values matching the given key.
 	///
 	/// Runs in `O(1)` (average).
-	pub fn get_with_index<Q>(&self, key: &Q) -> ValuesWithIndex
+	pub fn get_with_index<Q>(&self, key: &Q) -> ValuesWithIndex<'_>
 	where
 		Q: ?Sized + Hash + Equivalent<Key>,
 	{
@@ -314,7 +559,7 @@ impl Object {
 	/// Returns an iterator over the entries matching the given key.
 	///
 	/// Runs in `O(1)` (average).
-	pub fn get_entries_with_index<Q>(&self, key: &Q) -> EntriesWithIndex
+	pub fn get_entries_with_index<Q>(&self, key: &Q) -> EntriesWithIndex<'_>
 	where
 		Q: ?Sized + Hash + Equivalent<Key>,
 	{
@@ -386,7 +631,7 @@ impl Object {
 			.and_then(index_map::Indexes::redundant)
 	}
 
-	pub fn indexes_of<Q>(&self, key: &Q) -> Indexes
+	pub fn indexes_of<Q>(&self, key: &Q) -> Indexes<'_>
 	where
 		Q: ?Sized + Hash + Equivalent<Key>,
 	{
@@ -432,7 +677,7 @@ impl Object {
 		code_map: &CodeMap,
 		offset: usize,
 		key: &Q,
-	) -> Result<Option<MappedEntry>, Duplicate<MappedEntry>>
+	) -> Result<Option<MappedEntry<'_>>, Duplicate<MappedEntry<'_>>>
 	where
 		Q: ?Sized + Hash + Equivalent<Key>,
 	{
@@ -484,7 +729,7 @@ impl Object {
 		code_map: &CodeMap,
 		offset: usize,
 		key: &Q,
-	) -> Result<Option<IndexedMappedEntry>, Duplicate<IndexedMappedEntry>>
+	) -> Result<Option<IndexedMappedEntry<'_>>, Duplicate<IndexedMappedEntry<'_>>>
 	where
 		Q: ?Sized + Hash + Equivalent<Key>,
 	{
@@ -550,6 +795,41 @@ impl Object {
 		}
 	}
 
+	/// Returns the spans of the (first) entry matching the given key, of its
+	/// key and of its value, where `offset` is the fragment offset of this
+	/// object in the code map.
+	///
+	/// Returns `None` if there is no such entry, or if it is not in the code
+	/// map.
+	///
+	/// Runs in `O(n)` (average). `O(1)` to find the entry, `O(n)` to compute
+	/// the entry fragment offset.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{Parse, Value};
+	///
+	/// let source = r#"{ "a": 0, "b": [1] }"#;
+	/// let (value, code_map) = Value::parse_str(source).unwrap();
+	///
+	/// let spans = value.as_object().unwrap().span_of(&code_map, 0, "b").unwrap();
+	/// assert_eq!(&source[spans.entry.range()], r#""b": [1]"#);
+	/// assert_eq!(&source[spans.key.range()], r#""b""#);
+	/// assert_eq!(&source[spans.value.range()], "[1]");
+	/// ```
+	pub fn span_of<Q>(&self, code_map: &CodeMap, offset: usize, key: &Q) -> Option<EntrySpans>
+	where
+		Q: ?Sized + Hash + Equivalent<Key>,
+	{
+		let entry = self.get_mapped_entries(code_map, offset, key).next()?;
+		Some(EntrySpans {
+			entry: code_map.get(entry.offset)?.span,
+			key: code_map.get(entry.value.key.offset)?.span,
+			value: code_map.get(entry.value.value.offset)?.span,
+		})
+	}
+
 	/// Returns an iterator over the mapped values matching the given key, with
 	/// their index.
 	///
@@ -587,7 +867,7 @@ impl Object {
 		code_map: &CodeMap,
 		offset: usize,
 		key: &Q,
-	) -> Result<Option<IndexedMappedValue>, Duplicate<IndexedMappedValue>>
+	) -> Result<Option<IndexedMappedValue<'_>>, Duplicate<IndexedMappedValue<'_>>>
 	where
 		Q: ?Sized + Hash + Equivalent<Key>,
 	{
@@ -641,9 +921,40 @@ impl Object {
 	}
 
 	pub fn push_entry_front(&mut self, entry: Entry) -> bool {
-		self.entries.insert(0, entry);
-		self.indexes.shift_up(0);
-		self.indexes.insert(&self.entries, 0)
+		self.insert_entry_at(0, entry)
+	}
+
+	/// Inserts the given key-value pair at position `index`, shifting all
+	/// the following entries.
+	///
+	/// Returns `true` if the key was not already present in the object,
+	/// and `false` otherwise.
+	/// Any previous entry matching the key is **not** overridden: duplicates
+	/// are preserved, in order.
+	///
+	/// Runs in `O(n)`.
+	///
+	/// # Panics
+	///
+	/// Panics if `index > len`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, Value};
+	///
+	/// let mut value = json!({ "a": 0, "c": 2 });
+	/// value.as_object_mut().unwrap().insert_at(1, "b".into(), json!(1));
+	/// assert_eq!(value, json!({ "a": 0, "b": 1, "c": 2 }));
+	/// ```
+	pub fn insert_at(&mut self, index: usize, key: Key, value: Value) -> bool {
+		self.insert_entry_at(index, Entry::new(key, value))
+	}
+
+	pub fn insert_entry_at(&mut self, index: usize, entry: Entry) -> bool {
+		self.entries.insert(index, entry);
+		self.indexes.shift_up(index);
+		self.indexes.insert(&self.entries, index)
 	}
 
 	/// Removes the entry at the given index.
@@ -657,12 +968,231 @@ impl Object {
 		}
 	}
 
+	/// Removes the first entry of the object and returns it, or `None` if
+	/// the object is empty.
+	///
+	/// Runs in `O(n)`.
+	pub fn pop_first(&mut self) -> Option<Entry> {
+		self.remove_at(0)
+	}
+
+	/// Removes the last entry of the object and returns it, or `None` if
+	/// the object is empty.
+	///
+	/// Runs in `O(1)`.
+	pub fn pop_last(&mut self) -> Option<Entry> {
+		let last = self.entries.len().checked_sub(1)?;
+		self.indexes.remove(&self.entries, last);
+		self.entries.pop()
+	}
+
+	/// Removes the entry at the given index by swapping it with the last
+	/// entry.
+	///
+	/// Contrarily to [`Self::remove_at`], this does not shift the following
+	/// entries, making this operation `O(1)`, but it does not preserve the
+	/// order of the entries.
+	pub fn swap_remove_at(&mut self, index: usize) -> Option<Entry> {
+		if index < self.entries.len() {
+			self.indexes.remove(&self.entries, index);
+
+			let last = self.entries.len() - 1;
+			if index != last {
+				self.indexes.remove(&self.entries, last);
+				let entry = self.entries.swap_remove(index);
+				self.indexes.insert(&self.entries, index);
+				Some(entry)
+			} else {
+				self.entries.pop()
+			}
+		} else {
+			None
+		}
+	}
+
+	/// Renames the entries matching the key `old` to `new`, preserving their
+	/// position.
+	///
+	/// Returns `true` if at least one entry has been renamed, and `false` if
+	/// no entry matches `old`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, Value};
+	///
+	/// let mut value = json!({ "a": 0, "b": 1 });
+	/// let object = value.as_object_mut().unwrap();
+	///
+	/// assert!(object.rename_key("a", "c".into()));
+	/// assert!(!object.rename_key("a", "d".into()));
+	/// assert_eq!(value, json!({ "c": 0, "b": 1 }));
+	/// ```
+	pub fn rename_key<Q>(&mut self, old: &Q, new: Key) -> bool
+	where
+		Q: ?Sized + Hash + Equivalent<Key>,
+	{
+		let indexes: Vec<usize> = self.indexes_of(old).collect();
+
+		for &i in &indexes {
+			self.indexes.remove(&self.entries, i);
+		}
+
+		for &i in &indexes {
+			self.entries[i].key = new.clone();
+			self.indexes.insert(&self.entries, i);
+		}
+
+		!indexes.is_empty()
+	}
+
+	/// Shortens the object, keeping the first `len` entries and dropping the
+	/// rest.
+	///
+	/// Has no effect if `len` is greater than or equal to the current length.
+	pub fn truncate(&mut self, len: usize) {
+		for i in (len..self.entries.len()).rev() {
+			self.indexes.remove(&self.entries, i);
+		}
+
+		self.entries.truncate(len)
+	}
+
+	/// Splits the object in two at the given index.
+	///
+	/// Returns a new object containing the entries from `index` onward,
+	/// leaving the first `index` entries in `self`.
+	///
+	/// # Panics
+	///
+	/// Panics if `index > len`.
+	pub fn split_off(&mut self, index: usize) -> Object {
+		for i in (index..self.entries.len()).rev() {
+			self.indexes.remove(&self.entries, i);
+		}
+
+		Self::from_vec(self.entries.split_off(index))
+	}
+
+	/// Removes the entries in the given range, and returns them in order.
+	///
+	/// # Panics
+	///
+	/// Panics if the range is out of bounds.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, Value};
+	///
+	/// let mut value = json!({ "a": 0, "b": 1, "c": 2, "d": 3 });
+	/// let object = value.as_object_mut().unwrap();
+	///
+	/// let removed: Vec<_> = object.drain(1..3).map(|e| e.key).collect();
+	/// assert_eq!(removed, ["b", "c"]);
+	/// assert_eq!(object.index_of("d"), Some(1));
+	/// ```
+	pub fn drain(&mut self, range: impl RangeBounds<usize>) -> std::vec::IntoIter<Entry> {
+		let removed: Vec<_> = self.entries.drain(range).collect();
+		if !removed.is_empty() {
+			self.rebuild_index()
+		}
+
+		removed.into_iter()
+	}
+
+	/// Retains only the entries for which `f` returns `true`.
+	///
+	/// The order of the remaining entries is preserved. Contrarily to
+	/// repeated calls to [`Self::remove_at`], the index map is rebuilt only
+	/// once, making this operation `O(n)`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, Value};
+	///
+	/// let mut value = json!({ "a": 1, "b": null, "c": 2 });
+	/// value.as_object_mut().unwrap().retain(|_, value| !value.is_null());
+	/// assert_eq!(value, json!({ "a": 1, "c": 2 }));
+	/// ```
+	pub fn retain(&mut self, mut f: impl FnMut(&Key, &mut Value) -> bool) {
+		let len = self.entries.len();
+		self.entries.retain_mut(|e| f(&e.key, &mut e.value));
+		if self.entries.len() != len {
+			self.rebuild_index()
+		}
+	}
+
+	/// Removes duplicate entries in a single pass, according to the given
+	/// policy, and returns the removed entries in their original order.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, object::DedupPolicy, Value};
+	///
+	/// let mut value = json!({ "a": 1, "b": 2, "a": 3 });
+	/// let object = value.as_object_mut().unwrap();
+	/// let removed = object.dedup_keys(DedupPolicy::KeepLast);
+	///
+	/// assert_eq!(removed.len(), 1);
+	/// assert_eq!(value, json!({ "b": 2, "a": 3 }));
+	/// ```
+	pub fn dedup_keys(&mut self, policy: DedupPolicy) -> Vec<Entry> {
+		if !self.indexes.contains_duplicate_keys() {
+			return Vec::new();
+		}
+
+		let keep: Vec<bool> = self
+			.entries
+			.iter()
+			.enumerate()
+			.map(|(i, entry)| {
+				let indexes = self.indexes.get(&self.entries, &entry.key).unwrap();
+				match policy {
+					DedupPolicy::KeepLast => {
+						i == indexes
+							.redundants()
+							.last()
+							.copied()
+							.unwrap_or(indexes.first())
+					}
+					_ => i == indexes.first(),
+				}
+			})
+			.collect();
+
+		if let DedupPolicy::MergeWith(merge) = policy {
+			for (i, keep) in keep.iter().enumerate() {
+				if !keep {
+					let first = self.index_of(&self.entries[i].key).unwrap();
+					let (kept, duplicates) = self.entries.split_at_mut(i);
+					merge(&mut kept[first].value, &duplicates[0].value)
+				}
+			}
+		}
+
+		let mut removed = Vec::new();
+		let entries = core::mem::take(&mut self.entries);
+		for (entry, keep) in entries.into_iter().zip(keep) {
+			if keep {
+				self.entries.push(entry)
+			} else {
+				removed.push(entry)
+			}
+		}
+
+		self.rebuild_index();
+		removed
+	}
+
 	/// Inserts the given key-value pair.
 	///
 	/// If one or more entries are already matching the given key,
 	/// all of them are removed and returned in the resulting iterator.
 	/// Otherwise, `None` is returned.
-	pub fn insert(&mut self, key: Key, value: Value) -> Option<RemovedByInsertion> {
+	pub fn insert(&mut self, key: Key, value: Value) -> Option<RemovedByInsertion<'_>> {
 		match self.index_of(&key) {
 			Some(index) => {
 				let mut entry = Entry::new(key, value);
@@ -684,7 +1214,7 @@ impl Object {
 	///
 	/// If one or more entries are already matching the given key,
 	/// all of them are removed and returned in the resulting iterator.
-	pub fn insert_front(&mut self, key: Key, value: Value) -> RemovedByInsertFront {
+	pub fn insert_front(&mut self, key: Key, value: Value) -> RemovedByInsertFront<'_> {
 		if let Some(first) = self.entries.first_mut() {
 			if first.key == key {
 				let first = core::mem::replace(first, Entry::new(key, value));
@@ -717,6 +1247,7 @@ impl Object {
 	/// Returns an error if multiple entries match the key.
 	///
 	/// Runs in `O(n)` time (average).
+	#[allow(clippy::result_large_err)]
 	pub fn remove_unique<Q>(&mut self, key: &Q) -> Result<Option<Entry>, Duplicate<Entry>>
 	where
 		Q: ?Sized + Hash + Equivalent<Key>,
@@ -732,12 +1263,64 @@ impl Object {
 		}
 	}
 
+	/// Makes a mutable reference into the given shared object.
+	///
+	/// If the object is shared with other `Arc` pointers, it is cloned first
+	/// (copy-on-write). See [`Arc::make_mut`](std::sync::Arc::make_mut).
+	#[inline(always)]
+	pub fn make_mut(this: &mut std::sync::Arc<Self>) -> &mut Self {
+		std::sync::Arc::make_mut(this)
+	}
+
 	/// Sort the entries by key name.
 	///
 	/// Entries with the same key are sorted by value.
 	pub fn sort(&mut self) {
 		use locspan::BorrowStripped;
 		self.entries.sort_by(|a, b| a.stripped().cmp(b.stripped()));
+		self.rebuild_index()
+	}
+
+	/// Sorts the entries with the given comparator function.
+	///
+	/// The sort is stable.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, Print};
+	///
+	/// // Schema-defined key order.
+	/// let order = ["id", "name"];
+	/// let rank = |key: &str| order.iter().position(|k| *k == key).unwrap_or(order.len());
+	///
+	/// let mut value = json!({ "extra": true, "name": "a", "id": 1 });
+	/// let object = value.as_object_mut().unwrap();
+	/// object.sort_by(|a, b| rank(&a.key).cmp(&rank(&b.key)));
+	///
+	/// assert_eq!(
+	///   value.compact_print().to_string(),
+	///   r#"{"id":1,"name":"a","extra":true}"#
+	/// )
+	/// ```
+	pub fn sort_by(&mut self, compare: impl FnMut(&Entry, &Entry) -> Ordering) {
+		self.entries.sort_by(compare);
+		self.rebuild_index()
+	}
+
+	/// Sorts the entries with the given key extraction function.
+	///
+	/// The sort is stable.
+	pub fn sort_by_key<K: Ord>(&mut self, f: impl FnMut(&Entry) -> K) {
+		self.entries.sort_by_key(f);
+		self.rebuild_index()
+	}
+
+	/// Rebuilds the key index from scratch.
+	///
+	/// This must be called after modifying the entries through
+	/// [`Self::as_entries_mut`].
+	pub fn rebuild_index(&mut self) {
 		self.indexes.clear();
 
 		for i in 0..self.entries.len() {
@@ -766,6 +1349,28 @@ impl Object {
 		let mut buffer = ryu_js::Buffer::new();
 		self.canonicalize_with(&mut buffer)
 	}
+
+	/// Checks that this JSON object is in canonical form according to
+	/// [RFC 8785](https://www.rfc-editor.org/rfc/rfc8785#name-generation-of-canonical-jso),
+	/// as produced by [`Self::canonicalize_with`].
+	///
+	/// The given `buffer` is used to canonicalize the number values.
+	#[cfg(feature = "canonicalize")]
+	pub fn is_canonical_with(&self, buffer: &mut ryu_js::Buffer) -> bool {
+		self.entries.windows(2).all(|w| w[0] <= w[1])
+			&& self
+				.entries
+				.iter()
+				.all(|entry| entry.value.is_canonical_with(buffer))
+	}
+
+	/// Checks that this JSON object is in canonical form according to
+	/// [RFC 8785](https://www.rfc-editor.org/rfc/rfc8785#name-generation-of-canonical-jso).
+	#[cfg(feature = "canonicalize")]
+	pub fn is_canonical(&self) -> bool {
+		let mut buffer = ryu_js::Buffer::new();
+		self.is_canonical_with(&mut buffer)
+	}
 }
 
 pub type Iter<'a> = core::slice::Iter<'a, Entry>;
@@ -780,6 +1385,71 @@ impl<'a> Iterator for IterMut<'a> {
 	}
 }
 
+/// Iterator over the keys of an object.
+///
+/// Returned by [`Object::keys`].
+pub struct Keys<'a>(std::slice::Iter<'a, Entry>);
+
+impl<'a> Iterator for Keys<'a> {
+	type Item = &'a Key;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.0.next().map(|entry| &entry.key)
+	}
+}
+
+/// Iterator over the values of an object.
+///
+/// Returned by [`Object::values`].
+pub struct ValuesIter<'a>(std::slice::Iter<'a, Entry>);
+
+impl<'a> Iterator for ValuesIter<'a> {
+	type Item = &'a Value;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.0.next().map(|entry| &entry.value)
+	}
+}
+
+/// Iterator over mutable references to the values of an object.
+///
+/// Returned by [`Object::values_mut`].
+pub struct ValuesIterMut<'a>(std::slice::IterMut<'a, Entry>);
+
+impl<'a> Iterator for ValuesIterMut<'a> {
+	type Item = &'a mut Value;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.0.next().map(|entry| &mut entry.value)
+	}
+}
+
+/// Owning iterator over the keys of an object.
+///
+/// Returned by [`Object::into_keys`].
+pub struct IntoKeys(std::vec::IntoIter<Entry>);
+
+impl Iterator for IntoKeys {
+	type Item = Key;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.0.next().map(Entry::into_key)
+	}
+}
+
+/// Owning iterator over the values of an object.
+///
+/// Returned by [`Object::into_values`].
+pub struct IntoValues(std::vec::IntoIter<Entry>);
+
+impl Iterator for IntoValues {
+	type Item = Value;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.0.next().map(Entry::into_value)
+	}
+}
+
 pub struct IterMapped<'a, 'm> {
 	entries: std::slice::Iter<'a, Entry>,
 	code_map: &'m CodeMap,
@@ -841,7 +1511,7 @@ impl UnorderedEq for Object {}
 
 impl PartialOrd for Object {
 	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-		Some(self.entries.cmp(&other.entries))
+		Some(self.cmp(other))
 	}
 }
 
@@ -857,6 +1527,7 @@ impl Hash for Object {
 	}
 }
 
+#[cfg(not(feature = "compact-debug"))]
 impl fmt::Debug for Object {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		f.debug_map()
@@ -930,20 +1601,16 @@ impl FromIterator<(Key, Value)> for Object {
 	}
 }
 
+#[derive(Default)]
 pub enum Indexes<'a> {
 	Some {
 		first: Option<usize>,
 		other: core::slice::Iter<'a, usize>,
 	},
+	#[default]
 	None,
 }
 
-impl<'a> Default for Indexes<'a> {
-	fn default() -> Self {
-		Self::None
-	}
-}
-
 impl<'a> Iterator for Indexes<'a> {
 	type Item = usize;
 
@@ -1011,19 +1678,26 @@ macro_rules! entries_iter_mut {
 	($($id:ident <$lft:lifetime> {
 		type Item = $item:ty ;
 
-		fn next(&mut $self:ident, $index:ident) { $e:expr }
+		fn next(&mut $self:ident, $index:ident, $entry:ident) { $e:expr }
 	})*) => {
 		$(
 			pub struct $id<$lft> {
 				indexes: Indexes<$lft>,
-				entries: &$lft mut [Entry]
+				entries: core::slice::IterMut<$lft, Entry>,
+				offset: usize
 			}
 
 			impl<$lft> Iterator for $id<$lft> {
 				type Item = $item;
 
 				fn next(&mut $self) -> Option<Self::Item> {
-					$self.indexes.next().map(|$index| $e)
+					// Indexes are yielded in increasing order, so we can split
+					// the borrow by advancing the underlying iterator.
+					$self.indexes.next().and_then(|$index| {
+						let $entry = $self.entries.nth($index - $self.offset)?;
+						$self.offset = $index + 1;
+						Some($e)
+					})
 				}
 			}
 		)*
@@ -1034,19 +1708,13 @@ entries_iter_mut! {
 	ValuesMut<'a> {
 		type Item = &'a mut Value;
 
-		fn next(&mut self, index) {
-			// This is safe because there is no aliasing between the values.
-			unsafe { core::mem::transmute(&mut self.entries[index].value) }
-		}
+		fn next(&mut self, index, entry) { &mut entry.value }
 	}
 
 	ValuesMutWithIndex<'a> {
 		type Item = (usize, &'a mut Value);
 
-		fn next(&mut self, index) {
-			// This is safe because there is no aliasing between the values.
-			unsafe { (index, core::mem::transmute::<&mut Value, &'a mut Value>(&mut self.entries[index].value)) }
-		}
+		fn next(&mut self, index, entry) { (index, &mut entry.value) }
 	}
 }
 
@@ -1226,6 +1894,22 @@ where
 	}
 }
 
+/// Duplicate key deduplication policy.
+///
+/// Used by [`Object::dedup_keys`].
+#[derive(Debug, Clone, Copy)]
+pub enum DedupPolicy {
+	/// Keep the first entry of each key.
+	KeepFirst,
+
+	/// Keep the last entry of each key.
+	KeepLast,
+
+	/// Keep the first entry of each key, merging the values of the following
+	/// duplicate entries into it, in order.
+	MergeWith(fn(&mut Value, &Value)),
+}
+
 #[derive(Debug)]
 pub struct Duplicate<T>(pub T, pub T);
 
@@ -1255,6 +1939,214 @@ mod tests {
 		object.remove("a");
 	}
 
+	#[test]
+	fn retain() {
+		let mut object = Object::new();
+		object.push("a".into(), Value::Null);
+		object.push("b".into(), Value::Boolean(true));
+		object.push("a".into(), Value::Boolean(false));
+		object.push("c".into(), Value::Null);
+
+		object.retain(|_, value| !value.is_null());
+
+		assert_eq!(object.len(), 2);
+		assert_eq!(object.index_of("a"), Some(1));
+		assert_eq!(object.index_of("b"), Some(0));
+		assert!(object.index_of("c").is_none())
+	}
+
+	#[test]
+	fn sort_by_key() {
+		let mut object = Object::new();
+		object.push("aaa".into(), Value::Null);
+		object.push("b".into(), Value::Null);
+		object.push("cc".into(), Value::Null);
+
+		object.sort_by_key(|e| e.key.len());
+
+		assert_eq!(object.index_of("b"), Some(0));
+		assert_eq!(object.index_of("cc"), Some(1));
+		assert_eq!(object.index_of("aaa"), Some(2))
+	}
+
+	#[test]
+	fn dedup_keys() {
+		fn add(a: &mut Value, b: &Value) {
+			let sum =
+				a.as_number().unwrap().as_u64().unwrap() + b.as_number().unwrap().as_u64().unwrap();
+			*a = Value::Number(sum.into())
+		}
+
+		let build = || {
+			let mut object = Object::new();
+			object.push("a".into(), Value::Number(1u64.into()));
+			object.push("b".into(), Value::Null);
+			object.push("a".into(), Value::Number(2u64.into()));
+			object.push("a".into(), Value::Number(3u64.into()));
+			object
+		};
+
+		let mut object = build();
+		assert_eq!(object.dedup_keys(DedupPolicy::KeepFirst).len(), 2);
+		assert_eq!(object.entries()[0].value, Value::Number(1u64.into()));
+		assert_eq!(object.index_of("b"), Some(1));
+
+		let mut object = build();
+		assert_eq!(object.dedup_keys(DedupPolicy::KeepLast).len(), 2);
+		assert_eq!(object.index_of("b"), Some(0));
+		assert_eq!(object.entries()[1].value, Value::Number(3u64.into()));
+
+		let mut object = build();
+		assert_eq!(object.dedup_keys(DedupPolicy::MergeWith(add)).len(), 2);
+		assert_eq!(object.entries()[0].value, Value::Number(6u64.into()));
+		assert!(object.dedup_keys(DedupPolicy::KeepFirst).is_empty())
+	}
+
+	#[test]
+	fn swap_remove_at() {
+		let mut object = Object::new();
+		object.push("a".into(), Value::Null);
+		object.push("b".into(), Value::Null);
+		object.push("c".into(), Value::Null);
+		object.push("b".into(), Value::Null);
+
+		assert_eq!(object.swap_remove_at(0).unwrap().key, "a");
+		assert_eq!(object.indexes_of("b").collect::<Vec<_>>(), [0, 1]);
+		assert_eq!(object.index_of("c"), Some(2));
+		assert!(object.index_of("a").is_none());
+
+		assert_eq!(object.swap_remove_at(2).unwrap().key, "c");
+		assert!(object.swap_remove_at(2).is_none());
+		assert_eq!(object.len(), 2)
+	}
+
+	#[test]
+	fn keys_values() {
+		let mut object = Object::new();
+		object.push("a".into(), Value::Null);
+		object.push("b".into(), Value::Boolean(true));
+
+		assert_eq!(object.keys().collect::<Vec<_>>(), ["a", "b"]);
+		for value in object.values_mut() {
+			*value = Value::Boolean(false)
+		}
+		assert!(object.values().all(|v| *v == Value::Boolean(false)));
+		assert_eq!(object.clone().into_keys().collect::<Vec<_>>(), ["a", "b"]);
+		assert_eq!(object.into_values().count(), 2)
+	}
+
+	#[test]
+	fn capacity() {
+		let mut object = Object::with_capacity(4);
+		assert!(object.capacity() >= 4);
+
+		object.push("a".into(), Value::Null);
+		object.reserve(100);
+		assert!(object.capacity() >= 101);
+
+		object.shrink_to_fit();
+		assert_eq!(object.capacity(), 1);
+		assert_eq!(object.index_of("a"), Some(0))
+	}
+
+	#[test]
+	fn truncate() {
+		let mut object = Object::new();
+		object.push("a".into(), Value::Null);
+		object.push("b".into(), Value::Null);
+		object.push("a".into(), Value::Null);
+
+		object.truncate(2);
+		assert_eq!(object.indexes_of("a").collect::<Vec<_>>(), [0]);
+		object.truncate(1);
+		assert!(object.index_of("b").is_none());
+		assert_eq!(object.len(), 1)
+	}
+
+	#[test]
+	fn insert_at() {
+		let mut object = Object::new();
+		object.push("a".into(), Value::Null);
+		object.push("c".into(), Value::Null);
+
+		assert!(object.insert_at(1, "b".into(), Value::Null));
+		assert!(!object.insert_at(3, "a".into(), Value::Null));
+		assert!(!object.insert_at(0, "c".into(), Value::Null));
+
+		assert_eq!(object.keys().collect::<Vec<_>>(), ["c", "a", "b", "c", "a"]);
+		assert_eq!(object.indexes_of("a").collect::<Vec<_>>(), [1, 4]);
+		assert_eq!(object.indexes_of("b").collect::<Vec<_>>(), [2]);
+		assert_eq!(object.indexes_of("c").collect::<Vec<_>>(), [0, 3])
+	}
+
+	#[test]
+	fn rename_key() {
+		let mut object = Object::new();
+		object.push("a".into(), Value::Null);
+		object.push("b".into(), Value::Null);
+		object.push("a".into(), Value::Null);
+
+		assert!(object.rename_key("a", "b".into()));
+		assert!(object.index_of("a").is_none());
+		assert_eq!(object.indexes_of("b").collect::<Vec<_>>(), [0, 1, 2])
+	}
+
+	#[test]
+	fn pop() {
+		let mut object = Object::new();
+		object.push("a".into(), Value::Null);
+		object.push("b".into(), Value::Null);
+		object.push("a".into(), Value::Null);
+
+		assert_eq!(object.pop_first().unwrap().key, "a");
+		assert_eq!(object.indexes_of("a").collect::<Vec<_>>(), [1]);
+		assert_eq!(object.pop_last().unwrap().key, "a");
+		assert!(object.index_of("a").is_none());
+		assert_eq!(object.pop_last().unwrap().key, "b");
+		assert!(object.pop_first().is_none());
+		assert!(object.pop_last().is_none())
+	}
+
+	#[test]
+	fn split_off() {
+		let mut object = Object::new();
+		object.push("a".into(), Value::Null);
+		object.push("b".into(), Value::Null);
+		object.push("a".into(), Value::Null);
+
+		let tail = object.split_off(1);
+		assert_eq!(object.indexes_of("a").collect::<Vec<_>>(), [0]);
+		assert!(object.index_of("b").is_none());
+		assert_eq!(tail.index_of("b"), Some(0));
+		assert_eq!(tail.index_of("a"), Some(1))
+	}
+
+	#[test]
+	fn entry() {
+		let mut object = Object::new();
+		object.push("a".into(), Value::Null);
+
+		assert!(matches!(object.entry("a".into()), ObjectEntry::Occupied(_)));
+		*object.entry("b".into()).or_insert(Value::Null) = Value::Boolean(true);
+		object
+			.entry("a".into())
+			.and_modify(|v| *v = Value::Boolean(false))
+			.or_insert(Value::Null);
+
+		assert_eq!(
+			object.get_unique("a").unwrap(),
+			Some(&Value::Boolean(false))
+		);
+		assert_eq!(object.get_unique("b").unwrap(), Some(&Value::Boolean(true)));
+
+		if let ObjectEntry::Occupied(e) = object.entry("a".into()) {
+			e.remove();
+		}
+
+		assert_eq!(object.len(), 1);
+		assert!(object.index_of("a").is_none());
+	}
+
 	#[test]
 	fn unordered_eq1() {
 		let mut a = Object::new();
@@ -1314,6 +2206,64 @@ mod tests {
 		assert_eq!(a, b);
 	}
 
+	#[test]
+	fn get_mut() {
+		let mut object = Object::new();
+		object.push("a".into(), Value::Null);
+		object.push("b".into(), Value::Null);
+		object.push("a".into(), Value::Null);
+		object.push("a".into(), Value::Null);
+
+		let values: Vec<_> = object.get_mut("a").collect();
+		assert_eq!(values.len(), 3);
+		for (i, value) in values.into_iter().enumerate() {
+			*value = Value::from(i as u64)
+		}
+
+		for (i, value) in object.get_mut_with_index("a") {
+			*value = Value::from(i as u64)
+		}
+
+		let values: Vec<_> = object.get("a").cloned().collect();
+		assert_eq!(
+			values,
+			[Value::from(0u64), Value::from(2u64), Value::from(3u64)]
+		);
+		assert_eq!(object.get_mut("c").next(), None)
+	}
+
+	#[test]
+	fn get_disjoint_mut() {
+		let mut object = Object::new();
+		object.push("a".into(), Value::Null);
+		object.push("b".into(), Value::Null);
+		object.push("a".into(), Value::Null);
+
+		let [b, c, a] = object.get_disjoint_mut(["b", "c", "a"]);
+		*a.unwrap() = Value::from(0u64);
+		*b.unwrap() = Value::from(1u64);
+		assert!(c.is_none());
+
+		let values: Vec<_> = object.iter().map(|e| e.value.clone()).collect();
+		assert_eq!(values, [Value::from(0u64), Value::from(1u64), Value::Null])
+	}
+
+	#[test]
+	#[should_panic]
+	fn get_disjoint_mut_overlap() {
+		let mut object = Object::new();
+		object.push("a".into(), Value::Null);
+		object.get_disjoint_mut(["a", "a"]);
+	}
+
+	#[test]
+	#[should_panic]
+	fn get_disjoint_mut_missing_overlap() {
+		let mut object = Object::new();
+		object.push("a".into(), Value::Null);
+		object.get_disjoint_mut(["a", "b", "b"]);
+	}
+
 	#[test]
 	fn mapped_entries() {
 		use crate::Parse;
@@ -1344,4 +2294,18 @@ mod tests {
 
 		assert_eq!(offsets, [(1, 2, 3), (6, 7, 8), (15, 16, 17)]);
 	}
+
+	#[test]
+	fn span_of() {
+		use crate::Parse;
+		let source = r#"[0, { "a": {}, "a": 1 }]"#;
+		let (json, code_map) = crate::Value::parse_str(source).unwrap();
+		let object = json[1].as_object().unwrap();
+
+		let spans = object.span_of(&code_map, 2, "a").unwrap();
+		assert_eq!(spans.entry.range(), 6..13);
+		assert_eq!(spans.key.range(), 6..9);
+		assert_eq!(&source[spans.value.range()], "{}");
+		assert!(object.span_of(&code_map, 2, "b").is_none());
+	}
 }
@@ -0,0 +1,104 @@
+//! Parallel iterators, using [`rayon`].
+//!
+//! `&Object`, `&mut Object` and `Object` implement
+//! [`IntoParallelIterator`], providing the `par_iter`, `par_iter_mut` and
+//! `into_par_iter` methods. Arrays are plain `Vec<Value>`, for which `rayon`
+//! already provides parallel iterators.
+use rayon::iter::{
+	IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator,
+	IntoParallelRefMutIterator, ParallelIterator,
+};
+
+use super::{Entry, Key, Object};
+use crate::Value;
+
+type MapEntryMut = for<'a> fn(&'a mut Entry) -> (&'a Key, &'a mut Value);
+
+/// Parallel iterator over the entries of an object.
+pub type ParIter<'a> = rayon::slice::Iter<'a, Entry>;
+
+/// Parallel iterator over the entries of an object, with mutable values.
+pub struct ParIterMut<'a>(rayon::iter::Map<rayon::slice::IterMut<'a, Entry>, MapEntryMut>);
+
+impl<'a> ParallelIterator for ParIterMut<'a> {
+	type Item = (&'a Key, &'a mut Value);
+
+	fn drive_unindexed<C>(self, consumer: C) -> C::Result
+	where
+		C: rayon::iter::plumbing::UnindexedConsumer<Self::Item>,
+	{
+		self.0.drive_unindexed(consumer)
+	}
+
+	fn opt_len(&self) -> Option<usize> {
+		self.0.opt_len()
+	}
+}
+
+impl<'a> IndexedParallelIterator for ParIterMut<'a> {
+	fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	fn drive<C: rayon::iter::plumbing::Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+		self.0.drive(consumer)
+	}
+
+	fn with_producer<CB: rayon::iter::plumbing::ProducerCallback<Self::Item>>(
+		self,
+		callback: CB,
+	) -> CB::Output {
+		self.0.with_producer(callback)
+	}
+}
+
+impl<'a> IntoParallelIterator for &'a Object {
+	type Item = &'a Entry;
+	type Iter = ParIter<'a>;
+
+	fn into_par_iter(self) -> Self::Iter {
+		self.entries.par_iter()
+	}
+}
+
+impl<'a> IntoParallelIterator for &'a mut Object {
+	type Item = (&'a Key, &'a mut Value);
+	type Iter = ParIterMut<'a>;
+
+	fn into_par_iter(self) -> Self::Iter {
+		fn map_entry(entry: &mut Entry) -> (&Key, &mut Value) {
+			(&entry.key, &mut entry.value)
+		}
+
+		ParIterMut(self.entries.par_iter_mut().map(map_entry))
+	}
+}
+
+impl IntoParallelIterator for Object {
+	type Item = Entry;
+	type Iter = rayon::vec::IntoIter<Entry>;
+
+	fn into_par_iter(self) -> Self::Iter {
+		self.entries.into_par_iter()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use rayon::prelude::*;
+
+	use crate::{json, Value};
+
+	#[test]
+	fn par_iter_mut() {
+		let mut value = json!({ "a": 1, "b": 2, "c": 3 });
+		let object = value.as_object_mut().unwrap();
+
+		object
+			.par_iter_mut()
+			.for_each(|(_, v)| *v = Value::Boolean(v.as_number().is_some()));
+
+		assert!(object.par_iter().all(|e| e.value == Value::Boolean(true)));
+		assert_eq!(value, json!({ "a": true, "b": true, "c": true }))
+	}
+}
@@ -0,0 +1,144 @@
+use super::Object;
+use crate::Value;
+
+/// Dotted path syntax.
+///
+/// Used by [`Object::get_path_with`] and [`Object::get_path_mut_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PathSyntax {
+	/// Segment separator.
+	pub separator: char,
+
+	/// Escape character, allowing segments to contain the separator (or the
+	/// escape character itself).
+	pub escape: Option<char>,
+}
+
+impl PathSyntax {
+	/// Creates a new path syntax with the given separator, and `\` as escape
+	/// character.
+	pub fn new(separator: char) -> Self {
+		Self {
+			separator,
+			escape: Some('\\'),
+		}
+	}
+
+	/// Splits the given path into segments.
+	pub fn segments(&self, path: &str) -> Vec<String> {
+		let mut segments = Vec::new();
+		let mut segment = String::new();
+		let mut chars = path.chars();
+
+		while let Some(c) = chars.next() {
+			if Some(c) == self.escape {
+				if let Some(c) = chars.next() {
+					segment.push(c)
+				}
+			} else if c == self.separator {
+				segments.push(std::mem::take(&mut segment))
+			} else {
+				segment.push(c)
+			}
+		}
+
+		segments.push(segment);
+		segments
+	}
+}
+
+impl Default for PathSyntax {
+	fn default() -> Self {
+		Self::new('.')
+	}
+}
+
+impl Object {
+	/// Returns the value at the given dotted path.
+	///
+	/// Each segment of the path is either an object key (the first entry
+	/// with this key is selected) or an array index. The `.` separator can
+	/// be escaped with `\`. Use [`Self::get_path_with`] to configure the
+	/// syntax.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::json;
+	///
+	/// let value = json!({ "a": { "b": [{ "c": 1 }], "d.e": 2 } });
+	/// let object = value.as_object().unwrap();
+	///
+	/// assert_eq!(object.get_path("a.b.0.c"), Some(&json!(1)));
+	/// assert_eq!(object.get_path("a.d\\.e"), Some(&json!(2)));
+	/// assert_eq!(object.get_path("a.b.1"), None);
+	/// ```
+	pub fn get_path(&self, path: &str) -> Option<&Value> {
+		self.get_path_with(path, PathSyntax::default())
+	}
+
+	/// Returns the value at the given path, using the given path syntax.
+	pub fn get_path_with(&self, path: &str, syntax: PathSyntax) -> Option<&Value> {
+		let mut segments = syntax.segments(path).into_iter();
+		let first = segments.next()?;
+		let mut value = self.get(first.as_str()).next()?;
+
+		for segment in segments {
+			value = match value {
+				Value::Object(object) => object.get(segment.as_str()).next()?,
+				Value::Array(items) => items.get(segment.parse::<usize>().ok()?)?,
+				_ => return None,
+			}
+		}
+
+		Some(value)
+	}
+
+	/// Returns a mutable reference to the value at the given dotted path.
+	///
+	/// See [`Self::get_path`].
+	pub fn get_path_mut(&mut self, path: &str) -> Option<&mut Value> {
+		self.get_path_mut_with(path, PathSyntax::default())
+	}
+
+	/// Returns a mutable reference to the value at the given path, using the
+	/// given path syntax.
+	pub fn get_path_mut_with(&mut self, path: &str, syntax: PathSyntax) -> Option<&mut Value> {
+		let mut segments = syntax.segments(path).into_iter();
+		let first = segments.next()?;
+		let mut value = self.get_mut(first.as_str()).next()?;
+
+		for segment in segments {
+			value = match value {
+				Value::Object(object) => object.get_mut(segment.as_str()).next()?,
+				Value::Array(items) => items.get_mut(segment.parse::<usize>().ok()?)?,
+				_ => return None,
+			}
+		}
+
+		Some(value)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::json;
+
+	#[test]
+	fn segments() {
+		let syntax = PathSyntax::new('/');
+		assert_eq!(syntax.segments("a/b\\/c/\\\\"), ["a", "b/c", "\\"]);
+		assert_eq!(syntax.segments(""), [""]);
+	}
+
+	#[test]
+	fn get_path_mut() {
+		let mut value = json!({ "a": [{ "b": null }] });
+		let object = value.as_object_mut().unwrap();
+		*object
+			.get_path_mut_with("a/0/b", PathSyntax::new('/'))
+			.unwrap() = json!(true);
+		assert_eq!(value, json!({ "a": [{ "b": true }] }))
+	}
+}
@@ -0,0 +1,113 @@
+use super::{Entry, Object};
+use crate::Value;
+
+impl Object {
+	/// Sorts the object and converts it into a [`SortedObject`], dropping
+	/// the key index.
+	///
+	/// Entries are sorted by key. Entries with the same key keep their
+	/// relative order. Sorting an already sorted object (for instance with
+	/// [`Self::sort`] or `canonicalize`) runs in `O(n)`.
+	pub fn into_sorted(mut self) -> SortedObject {
+		self.entries.sort_by(|a, b| a.key.cmp(&b.key));
+		SortedObject(self.entries)
+	}
+}
+
+/// Read-only object with entries sorted by key.
+///
+/// Contrarily to [`Object`], it has no hash index: key lookups use a binary
+/// search on the entries instead. This saves the index memory for huge
+/// read-only objects.
+///
+/// # Example
+///
+/// ```
+/// use json_syntax::json;
+///
+/// let value = json!({ "b": 1, "a": 0, "c": 2 });
+/// let object = value.into_object().unwrap().into_sorted();
+///
+/// assert_eq!(object.get_sorted("b"), Some(&json!(1)));
+/// assert_eq!(object.get_sorted("d"), None);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct SortedObject(Vec<Entry>);
+
+impl SortedObject {
+	/// Returns the number of entries.
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	/// Checks if the object is empty.
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// Returns the entries, sorted by key.
+	pub fn entries(&self) -> &[Entry] {
+		&self.0
+	}
+
+	/// Returns the entries matching the given key.
+	///
+	/// Runs in `O(log n)`.
+	pub fn get_sorted_entries(&self, key: &str) -> &[Entry] {
+		let start = self.0.partition_point(|e| &*e.key < key);
+		let end = start + self.0[start..].partition_point(|e| &*e.key == key);
+		&self.0[start..end]
+	}
+
+	/// Returns the value of the first entry matching the given key.
+	///
+	/// Runs in `O(log n)`.
+	pub fn get_sorted(&self, key: &str) -> Option<&Value> {
+		self.get_sorted_entries(key).first().map(|e| &e.value)
+	}
+
+	/// Checks if this object contains the given key.
+	///
+	/// Runs in `O(log n)`.
+	pub fn contains_key(&self, key: &str) -> bool {
+		!self.get_sorted_entries(key).is_empty()
+	}
+
+	/// Converts this object back into an [`Object`], rebuilding the key
+	/// index.
+	pub fn into_object(self) -> Object {
+		Object::from_vec(self.0)
+	}
+}
+
+impl From<Object> for SortedObject {
+	fn from(value: Object) -> Self {
+		value.into_sorted()
+	}
+}
+
+impl From<SortedObject> for Object {
+	fn from(value: SortedObject) -> Self {
+		value.into_object()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn duplicates() {
+		let mut object = Object::new();
+		object.push("b".into(), Value::Null);
+		object.push("a".into(), Value::Boolean(true));
+		object.push("b".into(), Value::Boolean(false));
+
+		let sorted = object.into_sorted();
+		assert_eq!(sorted.get_sorted_entries("b").len(), 2);
+		assert_eq!(sorted.get_sorted("b"), Some(&Value::Null));
+		assert!(sorted.contains_key("a"));
+		assert!(!sorted.contains_key("c"));
+		assert_eq!(sorted.into_object().index_of("a"), Some(0))
+	}
+}
@@ -0,0 +1,193 @@
+use core::{fmt, hash::Hash};
+
+use super::{Equivalent, Key, Object};
+use crate::{Array, KindSet, Unexpected, Value};
+
+fn unexpected(expected: KindSet, value: &Value) -> Unexpected {
+	Unexpected {
+		expected,
+		found: value.kind(),
+	}
+}
+
+/// Error returned by [`Object::get_i64`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GetNumberError {
+	/// The value is not a number.
+	Unexpected(Unexpected),
+
+	/// Number does not fit in the requested type.
+	OutOfBounds(&'static str),
+}
+
+impl fmt::Display for GetNumberError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Unexpected(e) => e.fmt(f),
+			Self::OutOfBounds(ty) => write!(f, "number out of `{ty}` bounds"),
+		}
+	}
+}
+
+impl std::error::Error for GetNumberError {}
+
+impl From<Unexpected> for GetNumberError {
+	fn from(value: Unexpected) -> Self {
+		Self::Unexpected(value)
+	}
+}
+
+impl Object {
+	/// Returns the string value of the first entry matching the given key.
+	///
+	/// Returns `Ok(None)` if there is no such entry, and an error if its
+	/// value is not a string.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::json;
+	///
+	/// let value = json!({ "name": "Ferris", "age": 7, "legs": [] });
+	/// let object = value.as_object().unwrap();
+	///
+	/// assert_eq!(object.get_str("name"), Ok(Some("Ferris")));
+	/// assert_eq!(object.get_i64("age"), Ok(Some(7)));
+	/// assert_eq!(object.get_bool("shiny"), Ok(None));
+	/// assert!(object.get_object("legs").is_err());
+	/// ```
+	pub fn get_str<Q>(&self, key: &Q) -> Result<Option<&str>, Unexpected>
+	where
+		Q: ?Sized + Hash + Equivalent<Key>,
+	{
+		self.get(key)
+			.next()
+			.map(|v| v.as_str().ok_or_else(|| unexpected(KindSet::STRING, v)))
+			.transpose()
+	}
+
+	/// Returns the integer value of the first entry matching the given key.
+	///
+	/// Returns `Ok(None)` if there is no such entry, and an error if its
+	/// value is not a number. A number that is not an `i64` integer is
+	/// reported as [`GetNumberError::OutOfBounds`].
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, object::GetNumberError};
+	///
+	/// let value = json!({ "small": 7, "big": 1e30 });
+	/// let object = value.as_object().unwrap();
+	///
+	/// assert_eq!(object.get_i64("small"), Ok(Some(7)));
+	/// assert_eq!(object.get_i64("big"), Err(GetNumberError::OutOfBounds("i64")));
+	/// ```
+	pub fn get_i64<Q>(&self, key: &Q) -> Result<Option<i64>, GetNumberError>
+	where
+		Q: ?Sized + Hash + Equivalent<Key>,
+	{
+		self.get(key)
+			.next()
+			.map(|v| match v.as_number() {
+				Some(n) => n.as_i64().ok_or(GetNumberError::OutOfBounds("i64")),
+				None => Err(unexpected(KindSet::NUMBER, v).into()),
+			})
+			.transpose()
+	}
+
+	/// Returns the boolean value of the first entry matching the given key.
+	///
+	/// Returns `Ok(None)` if there is no such entry, and an error if its
+	/// value is not a boolean.
+	pub fn get_bool<Q>(&self, key: &Q) -> Result<Option<bool>, Unexpected>
+	where
+		Q: ?Sized + Hash + Equivalent<Key>,
+	{
+		self.get(key)
+			.next()
+			.map(|v| {
+				v.as_boolean()
+					.ok_or_else(|| unexpected(KindSet::BOOLEAN, v))
+			})
+			.transpose()
+	}
+
+	/// Returns the object value of the first entry matching the given key.
+	///
+	/// Returns `Ok(None)` if there is no such entry, and an error if its
+	/// value is not an object.
+	pub fn get_object<Q>(&self, key: &Q) -> Result<Option<&Object>, Unexpected>
+	where
+		Q: ?Sized + Hash + Equivalent<Key>,
+	{
+		self.get(key)
+			.next()
+			.map(|v| v.as_object().ok_or_else(|| unexpected(KindSet::OBJECT, v)))
+			.transpose()
+	}
+
+	/// Returns the array value of the first entry matching the given key.
+	///
+	/// Returns `Ok(None)` if there is no such entry, and an error if its
+	/// value is not an array.
+	pub fn get_array<Q>(&self, key: &Q) -> Result<Option<&[Value]>, Unexpected>
+	where
+		Q: ?Sized + Hash + Equivalent<Key>,
+	{
+		self.get(key)
+			.next()
+			.map(|v| v.as_array().ok_or_else(|| unexpected(KindSet::ARRAY, v)))
+			.transpose()
+	}
+
+	/// Returns a mutable reference to the object value of the first entry
+	/// matching the given key, inserting an empty object if there is no such
+	/// entry.
+	///
+	/// Returns an error if the existing value is not an object.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, Object, Value};
+	///
+	/// let mut config = Object::new();
+	/// config
+	///   .get_or_insert_object("server")
+	///   .unwrap()
+	///   .get_or_insert_array("ports")
+	///   .unwrap()
+	///   .push(json!(8080));
+	///
+	/// assert_eq!(Value::Object(config), json!({ "server": { "ports": [8080] } }));
+	/// ```
+	pub fn get_or_insert_object<Q>(&mut self, key: &Q) -> Result<&mut Object, Unexpected>
+	where
+		Q: ?Sized + Hash + Equivalent<Key> + ToOwned,
+		Q::Owned: Into<Key>,
+	{
+		let value = self.get_mut_or_insert_with(key, || Value::Object(Object::new()));
+		match value {
+			Value::Object(object) => Ok(object),
+			other => Err(unexpected(KindSet::OBJECT, other)),
+		}
+	}
+
+	/// Returns a mutable reference to the array value of the first entry
+	/// matching the given key, inserting an empty array if there is no such
+	/// entry.
+	///
+	/// Returns an error if the existing value is not an array.
+	pub fn get_or_insert_array<Q>(&mut self, key: &Q) -> Result<&mut Array, Unexpected>
+	where
+		Q: ?Sized + Hash + Equivalent<Key> + ToOwned,
+		Q::Owned: Into<Key>,
+	{
+		let value = self.get_mut_or_insert_with(key, || Value::Array(Array::new()));
+		match value {
+			Value::Array(array) => Ok(array),
+			other => Err(unexpected(KindSet::ARRAY, other)),
+		}
+	}
+}
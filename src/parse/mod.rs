@@ -1,6 +1,56 @@
+//! JSON parser.
+//!
+//! Most users only need the [`Parse`] trait methods (`parse_str`,
+//! `parse_slice`, etc.), that parse a whole document and return the
+//! value along with its [`CodeMap`].
+//!
+//! # Low-level API
+//!
+//! Crates building custom parse drivers (streaming adapters, metadata
+//! decorators, etc.) can use the following low-level API instead. It
+//! follows the same semver guarantees as the rest of the crate: breaking
+//! changes only happen in major releases.
+//!
+//! - [`Parser::new`] and [`Parser::new_with`] to build a parser from any
+//!   stream of [`DecodedChar`].
+//! - [`Parse::parse_in`] to parse a value with a given parser, in the given
+//!   [`Context`]. The returned metadata is the index of the value in the
+//!   parser's code-map. The end of the stream must follow the parsed
+//!   value.
+//! - [`Fragment`] and its [`Parse::parse_in`] implementation, to parse a
+//!   value step by step: a fragment is either a complete value, or the
+//!   beginning of a non-empty array, or of a non-empty object along with
+//!   its first key.
+//! - [`Parser::code_map`], [`Parser::into_code_map`], [`Parser::position`]
+//!   and [`Parser::at_end`] to inspect the parser state between calls.
+//! - [`Parser::reset`] to reuse a parser, and its code-map allocation, on a
+//!   new stream of the same type, and [`Parse::parse_str_in`] to reuse a
+//!   code-map allocation across strings of any lifetime.
+//!
+//! Other items of this module are not part of this tier.
+//!
+//! # Example
+//!
+//! ```
+//! use decoded_char::DecodedChar;
+//! use json_syntax::{json, parse::{Context, Options, Parser}, Parse, Value};
+//! use std::convert::Infallible;
+//!
+//! let input = "{ \"a\": [1, 2] }";
+//! let chars = input.chars().map(|c| Ok::<_, Infallible>(DecodedChar::from_utf8(c)));
+//! let mut parser = Parser::new_with(chars, Options::strict());
+//!
+//! let value = Value::parse_in(&mut parser, Context::None).unwrap();
+//! assert_eq!(*value.metadata(), 0); // index of the value in the code-map.
+//! assert!(parser.at_end().unwrap());
+//!
+//! let code_map = parser.into_code_map();
+//! assert_eq!(value.into_value(), json!({ "a": [1, 2] }));
+//! assert_eq!(code_map.len(), 6);
+//! ```
 use decoded_char::DecodedChar;
 use locspan::{Meta, Span};
-use std::{fmt, io};
+use std::{convert::Infallible, fmt, io, iter, str};
 
 mod array;
 mod boolean;
@@ -10,6 +60,8 @@ mod object;
 mod string;
 mod value;
 
+pub use value::Fragment;
+
 use crate::CodeMap;
 
 /// Parser options.
@@ -140,6 +192,39 @@ pub trait Parse: Sized {
 		Ok((value, parser.code_map))
 	}
 
+	/// Parses the given string, reusing the allocation of `code_map`.
+	///
+	/// The code-map is first cleared. After parsing, it contains the
+	/// code-map of `content`, even if parsing failed. This avoids
+	/// reallocating the code-map when parsing many documents in a row.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, parse::Options, CodeMap, Parse, Value};
+	///
+	/// let mut code_map = CodeMap::default();
+	///
+	/// for i in 0..3 {
+	///   let input = format!("[{i}]");
+	///   let value = Value::parse_str_in(&input, Options::strict(), &mut code_map).unwrap();
+	///   assert_eq!(value, json!([i]));
+	///   assert_eq!(code_map.first().unwrap().span.end(), input.len());
+	/// }
+	/// ```
+	fn parse_str_in(
+		content: &str,
+		options: Options,
+		code_map: &mut CodeMap,
+	) -> Result<Self, Error> {
+		let mut parser = Parser::new_with(str_chars(content), options);
+		parser.code_map = std::mem::take(code_map);
+		parser.code_map.clear();
+		let result = Self::parse_in(&mut parser, Context::None);
+		*code_map = parser.code_map;
+		Ok(result?.into_value())
+	}
+
 	fn parse_in<C, E>(
 		parser: &mut Parser<C, E>,
 		context: Context,
@@ -148,8 +233,17 @@ pub trait Parse: Sized {
 		C: Iterator<Item = Result<DecodedChar, E>>;
 }
 
+/// Character stream of a string slice.
+///
+/// Stream type of the parsers created by [`Parser::new_str`].
+pub type StrChars<'a> = iter::Map<str::Chars<'a>, fn(char) -> Result<DecodedChar, Infallible>>;
+
+fn str_chars(content: &str) -> StrChars<'_> {
+	content.chars().map(|c| Ok(DecodedChar::from_utf8(c)))
+}
+
 /// JSON parser.
-pub struct Parser<C: Iterator<Item = Result<DecodedChar, E>>, E> {
+pub struct Parser<C: Iterator<Item = Result<DecodedChar, E>>, E = Infallible> {
 	/// Character stream.
 	chars: C,
 
@@ -193,6 +287,45 @@ impl<C: Iterator<Item = Result<DecodedChar, E>>, E> Parser<C, E> {
 		}
 	}
 
+	/// Resets the parser to parse a new stream, keeping the options.
+	///
+	/// The code-map is cleared, but its memory is kept so that it can be
+	/// reused for the new stream.
+	pub fn reset(&mut self, chars: C) {
+		self.chars = chars;
+		self.pending = None;
+		self.position = 0;
+		self.code_map.clear()
+	}
+
+	/// Returns the parser options.
+	pub fn options(&self) -> &Options {
+		&self.options
+	}
+
+	/// Returns the current position in the stream, in bytes.
+	pub fn position(&self) -> usize {
+		self.position
+	}
+
+	/// Returns the code-map of all the fragments parsed so far.
+	pub fn code_map(&self) -> &CodeMap {
+		&self.code_map
+	}
+
+	/// Consumes the parser and returns the code-map of all the fragments
+	/// parsed.
+	pub fn into_code_map(self) -> CodeMap {
+		self.code_map
+	}
+
+	/// Skips whitespaces and checks if the end of the stream has been
+	/// reached.
+	pub fn at_end(&mut self) -> Result<bool, Error<E>> {
+		self.skip_whitespaces()?;
+		Ok(self.peek_char()?.is_none())
+	}
+
 	fn begin_fragment(&mut self) -> usize {
 		self.code_map.reserve(self.position)
 	}
@@ -250,6 +383,13 @@ impl<C: Iterator<Item = Result<DecodedChar, E>>, E> Parser<C, E> {
 	}
 }
 
+impl<'a> Parser<StrChars<'a>> {
+	/// Creates a new parser for the given string.
+	pub fn new_str(content: &'a str, options: Options) -> Self {
+		Self::new_with(str_chars(content), options)
+	}
+}
+
 /// Parse error.
 #[derive(Debug)]
 pub enum Error<E = core::convert::Infallible> {
@@ -0,0 +1,551 @@
+//! JSON Patch ([RFC 6902](https://www.rfc-editor.org/rfc/rfc6902))
+//! generation and structural diff reports.
+use core::fmt;
+
+use crate::{pointer::escape_token, Array, Object, UnorderedPartialEq, Value};
+
+/// JSON Patch operation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Operation {
+	/// Adds `value` at `path`.
+	Add { path: String, value: Value },
+
+	/// Removes the value at `path`.
+	Remove { path: String },
+
+	/// Replaces the value at `path` with `value`.
+	Replace { path: String, value: Value },
+
+	/// Moves the value at `from` to `path`.
+	Move { from: String, path: String },
+}
+
+impl Operation {
+	/// Returns the name of the operation (the `op` member).
+	pub fn name(&self) -> &'static str {
+		match self {
+			Self::Add { .. } => "add",
+			Self::Remove { .. } => "remove",
+			Self::Replace { .. } => "replace",
+			Self::Move { .. } => "move",
+		}
+	}
+
+	/// Returns the target path of the operation.
+	pub fn path(&self) -> &str {
+		match self {
+			Self::Add { path, .. }
+			| Self::Remove { path }
+			| Self::Replace { path, .. }
+			| Self::Move { path, .. } => path,
+		}
+	}
+
+	/// Converts this operation into its JSON representation.
+	pub fn into_value(self) -> Value {
+		let mut object = Object::new();
+		object.push("op".into(), self.name().into());
+		match self {
+			Self::Add { path, value } | Self::Replace { path, value } => {
+				object.push("path".into(), path.into());
+				object.push("value".into(), value);
+			}
+			Self::Remove { path } => {
+				object.push("path".into(), path.into());
+			}
+			Self::Move { from, path } => {
+				object.push("from".into(), from.into());
+				object.push("path".into(), path.into());
+			}
+		}
+
+		Value::Object(object)
+	}
+}
+
+impl From<Operation> for Value {
+	fn from(value: Operation) -> Self {
+		value.into_value()
+	}
+}
+
+/// JSON Patch, list of operations.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Patch(pub Vec<Operation>);
+
+impl Patch {
+	/// Checks if the patch contains no operation.
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// Returns the number of operations.
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	/// Returns the operations.
+	pub fn operations(&self) -> &[Operation] {
+		&self.0
+	}
+
+	/// Converts this patch into its JSON representation.
+	pub fn into_value(self) -> Value {
+		Value::Array(self.0.into_iter().map(Operation::into_value).collect())
+	}
+}
+
+impl From<Patch> for Value {
+	fn from(value: Patch) -> Self {
+		value.into_value()
+	}
+}
+
+impl IntoIterator for Patch {
+	type Item = Operation;
+	type IntoIter = std::vec::IntoIter<Operation>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.into_iter()
+	}
+}
+
+/// Diff options.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DiffOptions {
+	/// Emit `move` operations when an object entry is removed and an equal
+	/// value is added as an object entry elsewhere.
+	pub detect_moves: bool,
+}
+
+/// Computes a JSON Patch transforming `a` into `b`.
+///
+/// Equal subtrees produce no operation. Objects are compared key by key.
+/// Arrays are compared item by item once their common prefix and suffix
+/// are trimmed, items being added or removed at the end of the remaining
+/// range, so that a single insertion or removal produces a single
+/// operation. This is not an edit distance: the patch is not guaranteed to
+/// be minimal. When an object contains duplicate keys, it is replaced as a
+/// whole.
+///
+/// # Example
+///
+/// ```
+/// use json_syntax::{diff, json};
+///
+/// let a = json!({ "a": 1, "b": [1, 2], "c": null });
+/// let b = json!({ "a": 2, "b": [1, 2, 3], "d": true });
+///
+/// assert_eq!(
+///   diff(&a, &b).into_value(),
+///   json!([
+///     { "op": "replace", "path": "/a", "value": 2 },
+///     { "op": "add", "path": "/b/2", "value": 3 },
+///     { "op": "remove", "path": "/c" },
+///     { "op": "add", "path": "/d", "value": true }
+///   ])
+/// );
+/// ```
+pub fn diff(a: &Value, b: &Value) -> Patch {
+	diff_with(a, b, DiffOptions::default())
+}
+
+/// Computes a JSON Patch transforming `a` into `b`, with the given options.
+///
+/// See [`diff`].
+///
+/// # Example
+///
+/// ```
+/// use json_syntax::{json, patch::{diff_with, DiffOptions}};
+///
+/// let a = json!({ "a": { "big": [1, 2, 3] } });
+/// let b = json!({ "b": { "big": [1, 2, 3] } });
+///
+/// let options = DiffOptions { detect_moves: true };
+/// assert_eq!(
+///   diff_with(&a, &b, options).into_value(),
+///   json!([{ "op": "move", "from": "/a", "path": "/b" }])
+/// );
+/// ```
+pub fn diff_with(a: &Value, b: &Value, options: DiffOptions) -> Patch {
+	let mut differ = Differ {
+		operations: Vec::new(),
+		removed: Vec::new(),
+		added: Vec::new(),
+	};
+
+	differ.value(&mut String::new(), a, b);
+
+	if options.detect_moves {
+		differ.detect_moves()
+	}
+
+	Patch(differ.operations.into_iter().flatten().collect())
+}
+
+struct Differ<'a> {
+	/// Operations, `None` if discarded.
+	operations: Vec<Option<Operation>>,
+
+	/// Removed object entries, with the index of their `remove` operation.
+	removed: Vec<(usize, &'a Value)>,
+
+	/// Indexes of the `add` operations adding object entries.
+	added: Vec<usize>,
+}
+
+impl<'a> Differ<'a> {
+	fn value(&mut self, path: &mut String, a: &'a Value, b: &Value) {
+		match (a, b) {
+			(Value::Object(a), Value::Object(b))
+				if !a.has_duplicate_keys() && !b.has_duplicate_keys() =>
+			{
+				self.object(path, a, b)
+			}
+			(Value::Array(a), Value::Array(b)) => self.array(path, a, b),
+			(a, b) if a == b => (),
+			(_, b) => self.operations.push(Some(Operation::Replace {
+				path: path.clone(),
+				value: b.clone(),
+			})),
+		}
+	}
+
+	fn object(&mut self, path: &mut String, a: &'a Object, b: &Object) {
+		for entry in a {
+			with_token(path, &entry.key, |path| match b.get(&*entry.key).next() {
+				Some(b_value) => self.value(path, &entry.value, b_value),
+				None => {
+					self.removed.push((self.operations.len(), &entry.value));
+					self.operations
+						.push(Some(Operation::Remove { path: path.clone() }))
+				}
+			})
+		}
+
+		for entry in b {
+			if !a.contains_key(&*entry.key) {
+				with_token(path, &entry.key, |path| {
+					self.added.push(self.operations.len());
+					self.operations.push(Some(Operation::Add {
+						path: path.clone(),
+						value: entry.value.clone(),
+					}))
+				})
+			}
+		}
+	}
+
+	fn array(&mut self, path: &mut String, a: &'a Array, b: &Array) {
+		let prefix = a.iter().zip(b).take_while(|(a, b)| a == b).count();
+		let suffix = a[prefix..]
+			.iter()
+			.rev()
+			.zip(b[prefix..].iter().rev())
+			.take_while(|(a, b)| a == b)
+			.count();
+
+		let a_middle = &a[prefix..(a.len() - suffix)];
+		let b_middle = &b[prefix..(b.len() - suffix)];
+
+		for (i, (a_item, b_item)) in a_middle.iter().zip(b_middle).enumerate() {
+			with_token(path, &(prefix + i).to_string(), |path| {
+				self.value(path, a_item, b_item)
+			})
+		}
+
+		for i in (b_middle.len()..a_middle.len()).rev() {
+			with_token(path, &(prefix + i).to_string(), |path| {
+				self.operations
+					.push(Some(Operation::Remove { path: path.clone() }))
+			})
+		}
+
+		for (i, item) in b_middle.iter().enumerate().skip(a_middle.len()) {
+			with_token(path, &(prefix + i).to_string(), |path| {
+				self.operations.push(Some(Operation::Add {
+					path: path.clone(),
+					value: item.clone(),
+				}))
+			})
+		}
+	}
+
+	/// Replaces pairs of object entry removal and addition of equal values
+	/// with a `move` operation.
+	///
+	/// Object entry paths are not affected by other operations, so the
+	/// moved value is always found at its original path.
+	fn detect_moves(&mut self) {
+		for (remove_index, removed) in std::mem::take(&mut self.removed) {
+			let found = self.added.iter().position(
+				|&i| matches!(&self.operations[i], Some(Operation::Add { value, .. }) if value == removed),
+			);
+
+			if let Some(i) = found {
+				let add_index = self.added.remove(i);
+				let from = match self.operations[remove_index].take() {
+					Some(Operation::Remove { path }) => path,
+					_ => unreachable!(),
+				};
+
+				let path = self.operations[add_index].take().unwrap().path().to_owned();
+				self.operations[add_index] = Some(Operation::Move { from, path })
+			}
+		}
+	}
+}
+
+/// Kind of structural difference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DifferenceKind {
+	/// The value is only present on the right.
+	Added,
+
+	/// The value is only present on the left.
+	Removed,
+
+	/// The value differs between left and right.
+	Changed,
+}
+
+impl fmt::Display for DifferenceKind {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Added => f.write_str("added"),
+			Self::Removed => f.write_str("removed"),
+			Self::Changed => f.write_str("changed"),
+		}
+	}
+}
+
+/// Structural difference between two values.
+///
+/// Returned by [`Value::diff_report`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Difference<'a> {
+	/// JSON Pointer to the differing value.
+	pub pointer: String,
+
+	/// Difference kind.
+	pub kind: DifferenceKind,
+
+	/// Left value, if any.
+	pub left: Option<&'a Value>,
+
+	/// Right value, if any.
+	pub right: Option<&'a Value>,
+}
+
+impl<'a> fmt::Display for Difference<'a> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		use crate::Print;
+		// The root pointer is empty, while `/` points to the `""` key.
+		let pointer = if self.pointer.is_empty() {
+			"\"\""
+		} else {
+			&self.pointer
+		};
+
+		write!(f, "{pointer}: {}", self.kind)?;
+
+		if let Some(left) = self.left {
+			write!(f, " {}", left.compact_print())?
+		}
+
+		if let Some(right) = self.right {
+			if self.left.is_some() {
+				f.write_str(" ->")?
+			}
+
+			write!(f, " {}", right.compact_print())?
+		}
+
+		Ok(())
+	}
+}
+
+impl Value {
+	/// Returns the list of structural differences between this value and
+	/// `other`.
+	///
+	/// Objects are compared key by key, regardless of the order of their
+	/// entries. Arrays are compared item by item. Objects with duplicate keys
+	/// are compared as a whole.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, patch::DifferenceKind};
+	///
+	/// let a = json!({ "a": 1, "b": [1, 2], "c": null });
+	/// let b = json!({ "b": [1, 3], "a": 1, "d": true });
+	///
+	/// let report = a.diff_report(&b);
+	/// let report: Vec<_> = report.iter().map(|d| (d.pointer.as_str(), d.kind)).collect();
+	///
+	/// assert_eq!(
+	///   report,
+	///   [
+	///     ("/b/1", DifferenceKind::Changed),
+	///     ("/c", DifferenceKind::Removed),
+	///     ("/d", DifferenceKind::Added)
+	///   ]
+	/// );
+	/// ```
+	pub fn diff_report<'a>(&'a self, other: &'a Value) -> Vec<Difference<'a>> {
+		let mut report = Vec::new();
+		diff_report(&mut report, &mut String::new(), self, other);
+		report
+	}
+}
+
+fn diff_report<'a>(
+	report: &mut Vec<Difference<'a>>,
+	pointer: &mut String,
+	left: &'a Value,
+	right: &'a Value,
+) {
+	match (left, right) {
+		(Value::Object(a), Value::Object(b))
+			if !a.has_duplicate_keys() && !b.has_duplicate_keys() =>
+		{
+			for entry in a {
+				with_token(pointer, &entry.key, |pointer| {
+					match b.get(&*entry.key).next() {
+						Some(right) => diff_report(report, pointer, &entry.value, right),
+						None => report.push(Difference {
+							pointer: pointer.clone(),
+							kind: DifferenceKind::Removed,
+							left: Some(&entry.value),
+							right: None,
+						}),
+					}
+				})
+			}
+
+			for entry in b {
+				if !a.contains_key(&*entry.key) {
+					with_token(pointer, &entry.key, |pointer| {
+						report.push(Difference {
+							pointer: pointer.clone(),
+							kind: DifferenceKind::Added,
+							left: None,
+							right: Some(&entry.value),
+						})
+					})
+				}
+			}
+		}
+		(Value::Array(a), Value::Array(b)) => {
+			for i in 0..a.len().max(b.len()) {
+				with_token(pointer, &i.to_string(), |pointer| {
+					match (a.get(i), b.get(i)) {
+						(Some(left), Some(right)) => diff_report(report, pointer, left, right),
+						(left, right) => report.push(Difference {
+							pointer: pointer.clone(),
+							kind: if left.is_some() {
+								DifferenceKind::Removed
+							} else {
+								DifferenceKind::Added
+							},
+							left,
+							right,
+						}),
+					}
+				})
+			}
+		}
+		(left, right) if left.unordered_eq(right) => (),
+		(left, right) => report.push(Difference {
+			pointer: pointer.clone(),
+			kind: DifferenceKind::Changed,
+			left: Some(left),
+			right: Some(right),
+		}),
+	}
+}
+
+fn with_token<T>(path: &mut String, token: &str, f: impl FnOnce(&mut String) -> T) -> T {
+	let len = path.len();
+	path.push('/');
+	path.push_str(&escape_token(token));
+	let result = f(path);
+	path.truncate(len);
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::json;
+
+	#[test]
+	fn equal() {
+		let a = json!({ "a": [1, { "b": null }] });
+		assert!(diff(&a, &a).is_empty())
+	}
+
+	#[test]
+	fn array_shrink() {
+		let a = json!([1, 2, 3, 4]);
+		let b = json!([0, 2]);
+		assert_eq!(
+			diff(&a, &b).into_value(),
+			json!([
+				{ "op": "replace", "path": "/0", "value": 0 },
+				{ "op": "remove", "path": "/3" },
+				{ "op": "remove", "path": "/2" }
+			])
+		)
+	}
+
+	#[test]
+	fn array_insert_remove() {
+		let a = json!([1, 2, 3, 4]);
+		let b = json!([1, 5, 2, 3, 4]);
+		assert_eq!(
+			diff(&a, &b).into_value(),
+			json!([{ "op": "add", "path": "/1", "value": 5 }])
+		);
+		assert_eq!(
+			diff(&b, &a).into_value(),
+			json!([{ "op": "remove", "path": "/1" }])
+		);
+
+		let a = json!([1, 1, 1]);
+		let b = json!([1, 1]);
+		assert_eq!(
+			diff(&a, &b).into_value(),
+			json!([{ "op": "remove", "path": "/2" }])
+		)
+	}
+
+	#[test]
+	fn report_display() {
+		let a = json!({ "a": [1] });
+		let b = json!({ "a": [2, 3] });
+		let report: Vec<_> = a.diff_report(&b).iter().map(ToString::to_string).collect();
+		assert_eq!(report, ["/a/0: changed 1 -> 2", "/a/1: added 3"]);
+
+		let report: Vec<_> = json!(1)
+			.diff_report(&json!(2))
+			.iter()
+			.map(ToString::to_string)
+			.collect();
+		assert_eq!(report, ["\"\": changed 1 -> 2"]);
+
+		let a = json!({ "": 1 });
+		let b = json!({ "": 2 });
+		let report: Vec<_> = a.diff_report(&b).iter().map(ToString::to_string).collect();
+		assert_eq!(report, ["/: changed 1 -> 2"])
+	}
+
+	#[test]
+	fn escaped_keys() {
+		let a = json!({ "a/b": 0 });
+		let b = json!({ "a/b": 1 });
+		assert_eq!(diff(&a, &b).operations()[0].path(), "/a~1b")
+	}
+}
@@ -0,0 +1,198 @@
+//! Value destructuring.
+//!
+//! Support module for the [`json_match!`](crate::json_match) macro.
+use core::fmt;
+
+use crate::{KindSet, Object, Unexpected, Value};
+
+/// Pattern matching error.
+///
+/// Returned by the [`json_match!`](crate::json_match) macro when the value
+/// does not match the pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatchError {
+	/// Unexpected value kind.
+	Unexpected(Unexpected),
+
+	/// Missing object key.
+	MissingKey(&'static str),
+
+	/// Array length differs from the number of item patterns.
+	Length { expected: usize, found: usize },
+
+	/// Array is shorter than the number of item patterns (followed by `..`).
+	MinLength { expected: usize, found: usize },
+
+	/// Number does not fit in the requested type.
+	OutOfBounds(&'static str),
+
+	/// Value differs from the expected literal.
+	Mismatch(Value),
+}
+
+impl fmt::Display for MatchError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Unexpected(e) => e.fmt(f),
+			Self::MissingKey(key) => write!(f, "missing key `{key}`"),
+			Self::Length { expected, found } => {
+				write!(f, "expected {expected} items, found {found}")
+			}
+			Self::MinLength { expected, found } => {
+				write!(f, "expected at least {expected} items, found {found}")
+			}
+			Self::OutOfBounds(ty) => write!(f, "number out of `{ty}` bounds"),
+			Self::Mismatch(expected) => write!(f, "expected `{expected}`"),
+		}
+	}
+}
+
+impl std::error::Error for MatchError {}
+
+impl From<Unexpected> for MatchError {
+	fn from(value: Unexpected) -> Self {
+		Self::Unexpected(value)
+	}
+}
+
+fn unexpected(expected: KindSet, value: &Value) -> MatchError {
+	MatchError::Unexpected(Unexpected {
+		expected,
+		found: value.kind(),
+	})
+}
+
+/// Type that can be extracted from a value with an `as` binder of the
+/// [`json_match!`](crate::json_match) macro.
+pub trait Extract<'a>: Sized {
+	fn extract(value: &'a Value) -> Result<Self, MatchError>;
+}
+
+impl<'a> Extract<'a> for &'a Value {
+	fn extract(value: &'a Value) -> Result<Self, MatchError> {
+		Ok(value)
+	}
+}
+
+impl<'a> Extract<'a> for Value {
+	fn extract(value: &'a Value) -> Result<Self, MatchError> {
+		Ok(value.clone())
+	}
+}
+
+impl<'a> Extract<'a> for () {
+	fn extract(value: &'a Value) -> Result<Self, MatchError> {
+		match value {
+			Value::Null => Ok(()),
+			other => Err(unexpected(KindSet::NULL, other)),
+		}
+	}
+}
+
+impl<'a> Extract<'a> for bool {
+	fn extract(value: &'a Value) -> Result<Self, MatchError> {
+		match value {
+			Value::Boolean(b) => Ok(*b),
+			other => Err(unexpected(KindSet::BOOLEAN, other)),
+		}
+	}
+}
+
+macro_rules! extract_number {
+	($($ty:ident),*) => {
+		$(
+			impl<'a> Extract<'a> for $ty {
+				fn extract(value: &'a Value) -> Result<Self, MatchError> {
+					match value {
+						Value::Number(n) => n.parse().map_err(|_| MatchError::OutOfBounds(stringify!($ty))),
+						other => Err(unexpected(KindSet::NUMBER, other))
+					}
+				}
+			}
+		)*
+	};
+}
+
+extract_number!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+impl<'a> Extract<'a> for &'a str {
+	fn extract(value: &'a Value) -> Result<Self, MatchError> {
+		match value {
+			Value::String(s) => Ok(s),
+			other => Err(unexpected(KindSet::STRING, other)),
+		}
+	}
+}
+
+impl<'a> Extract<'a> for String {
+	fn extract(value: &'a Value) -> Result<Self, MatchError> {
+		<&str>::extract(value).map(ToOwned::to_owned)
+	}
+}
+
+impl<'a> Extract<'a> for &'a [Value] {
+	fn extract(value: &'a Value) -> Result<Self, MatchError> {
+		expect_array(value)
+	}
+}
+
+impl<'a> Extract<'a> for &'a Object {
+	fn extract(value: &'a Value) -> Result<Self, MatchError> {
+		expect_object(value)
+	}
+}
+
+impl<'a, T: Extract<'a>> Extract<'a> for Option<T> {
+	fn extract(value: &'a Value) -> Result<Self, MatchError> {
+		match value {
+			Value::Null => Ok(None),
+			other => T::extract(other).map(Some),
+		}
+	}
+}
+
+impl<'a, T: Extract<'a>> Extract<'a> for Vec<T> {
+	fn extract(value: &'a Value) -> Result<Self, MatchError> {
+		expect_array(value)?.iter().map(T::extract).collect()
+	}
+}
+
+#[doc(hidden)]
+pub fn expect_object(value: &Value) -> Result<&Object, MatchError> {
+	value
+		.as_object()
+		.ok_or_else(|| unexpected(KindSet::OBJECT, value))
+}
+
+#[doc(hidden)]
+pub fn expect_array(value: &Value) -> Result<&[Value], MatchError> {
+	value
+		.as_array()
+		.ok_or_else(|| unexpected(KindSet::ARRAY, value))
+}
+
+#[doc(hidden)]
+pub fn get<'a>(object: &'a Object, key: &'static str) -> Result<&'a Value, MatchError> {
+	object.get(key).next().ok_or(MatchError::MissingKey(key))
+}
+
+#[doc(hidden)]
+pub fn expect_value(value: &Value, expected: &Value) -> Result<(), MatchError> {
+	if value == expected {
+		Ok(())
+	} else {
+		Err(MatchError::Mismatch(expected.clone()))
+	}
+}
+
+#[doc(hidden)]
+pub fn check_len(array: &[Value], expected: usize, exact: bool) -> Result<(), MatchError> {
+	let found = array.len();
+	if exact && found != expected {
+		Err(MatchError::Length { expected, found })
+	} else if found < expected {
+		Err(MatchError::MinLength { expected, found })
+	} else {
+		Ok(())
+	}
+}
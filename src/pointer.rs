@@ -0,0 +1,910 @@
+//! JSON Pointer ([RFC 6901](https://www.rfc-editor.org/rfc/rfc6901)) support.
+use std::{borrow::Cow, cmp::Ordering, fmt};
+
+use crate::{code_map::Span, object::Entry, CodeMap, FragmentRef, Kind, Object, Traverse, Value};
+
+/// Escapes a reference token, replacing `~` with `~0` and `/` with `~1`.
+///
+/// ```
+/// use json_syntax::pointer::escape_token;
+///
+/// assert_eq!(escape_token("a/b~c"), "a~1b~0c");
+/// ```
+pub fn escape_token(token: &str) -> Cow<'_, str> {
+	if token.contains(['~', '/']) {
+		Cow::Owned(token.replace('~', "~0").replace('/', "~1"))
+	} else {
+		Cow::Borrowed(token)
+	}
+}
+
+/// Unescapes a reference token, replacing `~1` with `/` and `~0` with `~`.
+///
+/// Returns `None` if the token contains an invalid escape sequence.
+pub fn unescape_token(token: &str) -> Option<Cow<'_, str>> {
+	if !token.contains('~') {
+		return Some(Cow::Borrowed(token));
+	}
+
+	let mut result = String::with_capacity(token.len());
+	let mut chars = token.chars();
+	while let Some(c) = chars.next() {
+		match c {
+			'~' => match chars.next() {
+				Some('0') => result.push('~'),
+				Some('1') => result.push('/'),
+				_ => return None,
+			},
+			c => result.push(c),
+		}
+	}
+
+	Some(Cow::Owned(result))
+}
+
+/// Splits a JSON Pointer into unescaped reference tokens.
+///
+/// Returns `None` if the pointer is not empty and does not start with `/`,
+/// or contains an invalid escape sequence.
+///
+/// ```
+/// use json_syntax::pointer::tokens;
+///
+/// assert_eq!(tokens("/a~1b/0").unwrap(), ["a/b", "0"]);
+/// assert!(tokens("").unwrap().is_empty());
+/// assert!(tokens("a").is_none());
+/// ```
+pub fn tokens(pointer: &str) -> Option<Vec<Cow<'_, str>>> {
+	if pointer.is_empty() {
+		return Some(Vec::new());
+	}
+
+	pointer
+		.strip_prefix('/')?
+		.split('/')
+		.map(unescape_token)
+		.collect()
+}
+
+/// Parses an array index reference token.
+///
+/// Leading zeros are not allowed, following RFC 6901.
+fn array_index(token: &str) -> Option<usize> {
+	if token.len() > 1 && token.starts_with('0') || !token.bytes().all(|b| b.is_ascii_digit()) {
+		None
+	} else {
+		token.parse().ok()
+	}
+}
+
+impl Value {
+	/// Returns the value referenced by the given JSON Pointer
+	/// ([RFC 6901](https://www.rfc-editor.org/rfc/rfc6901)).
+	///
+	/// If an object contains duplicate keys, the first matching entry is
+	/// selected. Returns `None` if the pointer is invalid or does not
+	/// reference any value.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::json;
+	///
+	/// let value = json!({ "a": { "b/c": [0, { "~d": 1 }] } });
+	///
+	/// assert_eq!(value.pointer(""), Some(&value));
+	/// assert_eq!(value.pointer("/a/b~1c/1/~0d"), Some(&json!(1)));
+	/// assert_eq!(value.pointer("/a/b~1c/01"), None);
+	/// ```
+	pub fn pointer(&self, pointer: &str) -> Option<&Value> {
+		tokens(pointer)?
+			.into_iter()
+			.try_fold(self, |value, token| match value {
+				Value::Object(object) => object.get(token.as_ref()).next(),
+				Value::Array(items) => items.get(array_index(&token)?),
+				_ => None,
+			})
+	}
+
+	/// Returns a mutable reference to the value referenced by the given
+	/// JSON Pointer.
+	///
+	/// See [`Self::pointer`].
+	pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Value> {
+		resolve_mut(self, tokens(pointer)?)
+	}
+
+	/// Removes and returns the value referenced by the given JSON Pointer.
+	///
+	/// Depending on `mode`, the value is either replaced by `null`, or its
+	/// object entry or array item is removed (shifting the following items).
+	/// The root value (referenced by the empty pointer) cannot be removed,
+	/// and is always replaced by `null`.
+	///
+	/// If an object contains duplicate keys, the first matching entry is
+	/// selected. Returns `None` if the pointer is invalid or does not
+	/// reference any value.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, pointer::TakeMode};
+	///
+	/// let mut value = json!({ "a": { "b": [0, 1] }, "c": true });
+	///
+	/// assert_eq!(value.take_at("/a/b/0", TakeMode::Remove), Some(json!(0)));
+	/// assert_eq!(value.take_at("/c", TakeMode::LeaveNull), Some(json!(true)));
+	/// assert_eq!(value.take_at("/d", TakeMode::Remove), None);
+	/// assert_eq!(value, json!({ "a": { "b": [1] }, "c": null }));
+	/// ```
+	pub fn take_at(&mut self, pointer: &str, mode: TakeMode) -> Option<Value> {
+		let mut tokens = tokens(pointer)?;
+		let Some(last) = tokens.pop() else {
+			return Some(self.take());
+		};
+
+		let parent = resolve_mut(self, tokens)?;
+		match mode {
+			TakeMode::LeaveNull => {
+				let value = match parent {
+					Value::Object(object) => object.get_mut(last.as_ref()).next(),
+					Value::Array(items) => items.get_mut(array_index(&last)?),
+					_ => None,
+				}?;
+
+				Some(value.take())
+			}
+			TakeMode::Remove => match parent {
+				Value::Object(object) => {
+					let i = object.index_of(last.as_ref())?;
+					object.remove_at(i).map(|entry| entry.value)
+				}
+				Value::Array(items) => {
+					let i = array_index(&last)?;
+					(i < items.len()).then(|| items.remove(i))
+				}
+				_ => None,
+			},
+		}
+	}
+
+	/// Inserts a value at the location referenced by the given JSON Pointer,
+	/// returning the value previously at this location, if any.
+	///
+	/// The last reference token designates the location of the inserted
+	/// value. On an object, it sets the (first) entry with this key, or adds a
+	/// new entry. On an array, it replaces the item at this index, or appends
+	/// the value if the index is the array length or `-`.
+	///
+	/// With [`CreateIntermediates::Yes`], missing values along the path, and
+	/// `null` values, are replaced with empty objects, or empty arrays when
+	/// the next reference token is an array index or `-`. Missing array items
+	/// are only created at the end of their array (at the array length or
+	/// `-`): arrays are never padded, so that a pointer cannot trigger an
+	/// arbitrarily large allocation.
+	///
+	/// # Errors
+	///
+	/// Fails if a reference token cannot be applied, for instance because the
+	/// referenced value is missing (and cannot be created) or is not an
+	/// object or array. An array index past the end of its array (or past
+	/// the position of a new item) gives [`InsertError::NotFound`], whether
+	/// or not it is the last reference token. The path is checked before any
+	/// intermediate value is created, so that the value is left unchanged on
+	/// error.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, pointer::CreateIntermediates};
+	///
+	/// let mut value = json!({ "a": 1 });
+	///
+	/// value.insert_at("/b/c/0", json!(true), CreateIntermediates::Yes).unwrap();
+	/// assert_eq!(value, json!({ "a": 1, "b": { "c": [true] } }));
+	/// assert!(value.insert_at("/b/c/5", json!(false), CreateIntermediates::Yes).is_err());
+	///
+	/// let old = value.insert_at("/a", json!(2), CreateIntermediates::No).unwrap();
+	/// assert_eq!(old, Some(json!(1)));
+	///
+	/// assert!(value.insert_at("/d/e", json!(3), CreateIntermediates::No).is_err());
+	/// ```
+	pub fn insert_at(
+		&mut self,
+		pointer: &str,
+		value: Value,
+		create: CreateIntermediates,
+	) -> Result<Option<Value>, InsertError> {
+		let create = create == CreateIntermediates::Yes;
+		let mut tokens = tokens(pointer).ok_or(InsertError::InvalidPointer)?;
+		let Some(last) = tokens.pop() else {
+			return Ok(Some(std::mem::replace(self, value)));
+		};
+
+		if create {
+			check_insert_path(self, &tokens, &last)?
+		}
+
+		let mut current = self;
+		for (i, token) in tokens.iter().enumerate() {
+			if create && current.is_null() {
+				*current = container_for(token)
+			}
+
+			current = match current {
+				Value::Object(object) => {
+					if !create && !object.contains_key(token.as_ref()) {
+						return Err(InsertError::NotFound(i));
+					}
+
+					object.get_mut_or_insert_with(token.as_ref(), || Value::Null)
+				}
+				Value::Array(items) => {
+					let index = insert_index(items, token).ok_or(InsertError::InvalidIndex(i))?;
+					if index >= items.len() {
+						if !create || index > items.len() {
+							return Err(InsertError::NotFound(i));
+						}
+
+						items.push(Value::Null)
+					}
+
+					&mut items[index]
+				}
+				other => return Err(InsertError::NotAContainer(i, other.kind())),
+			}
+		}
+
+		let i = tokens.len();
+		if create && current.is_null() {
+			*current = container_for(&last)
+		}
+
+		match current {
+			Value::Object(object) => match object.get_mut(last.as_ref()).next() {
+				Some(old) => Ok(Some(std::mem::replace(old, value))),
+				None => {
+					object.push(last.as_ref().into(), value);
+					Ok(None)
+				}
+			},
+			Value::Array(items) => {
+				let index = insert_index(items, &last).ok_or(InsertError::InvalidIndex(i))?;
+				if index < items.len() {
+					Ok(Some(std::mem::replace(&mut items[index], value)))
+				} else if index == items.len() {
+					items.push(value);
+					Ok(None)
+				} else {
+					Err(InsertError::NotFound(i))
+				}
+			}
+			other => Err(InsertError::NotAContainer(i, other.kind())),
+		}
+	}
+
+	/// Flattens this value into a single-level object, mapping the JSON
+	/// Pointer of each leaf value to the leaf value.
+	///
+	/// Leaves are literals (`null`, booleans, numbers and strings) and empty
+	/// arrays and objects. Entries are in traversal order (pre-order). The
+	/// value can be restored with [`Object::unflatten`].
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::json;
+	///
+	/// let value = json!({ "a": { "b": [1, {}] }, "c/d": "e" });
+	/// let flat = value.flatten();
+	///
+	/// assert_eq!(json!(flat.clone()), json!({ "/a/b/0": 1, "/a/b/1": {}, "/c~1d": "e" }));
+	/// assert_eq!(flat.unflatten().unwrap(), value);
+	/// ```
+	pub fn flatten(&self) -> Object {
+		self.select_where(|_, value| {
+			!value.is_array() && !value.is_object() || value.is_empty_array_or_object()
+		})
+		.map(|(pointer, value)| Entry::new(pointer.as_str().into(), value.clone()))
+		.collect()
+	}
+
+	/// Returns an iterator over all the values (including `self`) for which
+	/// the given predicate returns `true`, with their JSON Pointer.
+	///
+	/// The predicate is called with the pointer to each value and the value
+	/// itself, in the order of [`Self::traverse`] (pre-order).
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::json;
+	///
+	/// let value = json!({ "a": [1, "b", 2], "c": { "d/e": 3 } });
+	///
+	/// let numbers: Vec<_> = value
+	///   .select_where(|_, value| value.is_number())
+	///   .map(|(pointer, _)| pointer)
+	///   .collect();
+	///
+	/// assert_eq!(numbers, ["/a/0", "/a/2", "/c/d~1e"]);
+	/// ```
+	pub fn select_where<F>(&self, predicate: F) -> SelectWhere<'_, F>
+	where
+		F: FnMut(&str, &Value) -> bool,
+	{
+		SelectWhere {
+			traverse: self.traverse(),
+			index: 0,
+			pointer: String::new(),
+			stack: Vec::new(),
+			predicate,
+		}
+	}
+}
+
+impl Object {
+	/// Builds a value from a flattened object, mapping JSON Pointers to
+	/// values, as returned by [`Value::flatten`].
+	///
+	/// Values are inserted in order with [`Value::insert_at`], creating
+	/// intermediate arrays and objects. An intermediate value is an array if
+	/// the next reference token is an array index (or `-`), and an object
+	/// otherwise. As a consequence, objects whose keys are all array indexes
+	/// are restored as arrays. Array items must be given in order, an index
+	/// past the end of its array being an error.
+	pub fn unflatten(&self) -> Result<Value, InsertError> {
+		let mut result = Value::Null;
+
+		for entry in self {
+			result.insert_at(&entry.key, entry.value.clone(), CreateIntermediates::Yes)?;
+		}
+
+		Ok(result)
+	}
+}
+
+impl CodeMap {
+	/// Returns the JSON Pointer to the given fragment of `value`, where
+	/// `self` is the code map of `value`.
+	///
+	/// The pointer to an object entry, or to its key, is the pointer to the
+	/// entry value. Returns `None` if there is no such fragment.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{Parse, Value};
+	///
+	/// let (value, code_map) = Value::parse_str(r#"{ "a": [true, { "b/c": null }] }"#).unwrap();
+	///
+	/// assert_eq!(code_map.pointer_of(&value, 0).unwrap(), "");
+	/// assert_eq!(code_map.pointer_of(&value, 4).unwrap(), "/a/0");
+	/// assert_eq!(code_map.pointer_of(&value, 7).unwrap(), "/a/1/b~1c"); // "b/c"
+	/// assert!(code_map.pointer_of(&value, 9).is_none());
+	/// ```
+	pub fn pointer_of(&self, value: &Value, fragment: usize) -> Option<String> {
+		let mut pointer = String::new();
+		let mut value = value;
+		let mut offset = 0;
+
+		while fragment != offset {
+			match value {
+				Value::Array(items) => {
+					let (i, child) = self.child_containing(offset, items.len(), fragment)?;
+					pointer.push('/');
+					pointer.push_str(&i.to_string());
+					value = &items[i];
+					offset = child
+				}
+				Value::Object(object) => {
+					let (i, child) = self.child_containing(offset, object.len(), fragment)?;
+					let entry = &object.entries()[i];
+					pointer.push('/');
+					pointer.push_str(&escape_token(&entry.key));
+
+					if fragment <= child + 1 {
+						break;
+					}
+
+					value = &entry.value;
+					offset = child + 2
+				}
+				_ => return None,
+			}
+		}
+
+		Some(pointer)
+	}
+
+	/// Returns the index of the fragment of `value` referenced by the given
+	/// JSON Pointer, where `self` is the code map of `value`.
+	///
+	/// If an object contains duplicate keys, the first matching entry is
+	/// selected, as in [`Value::pointer`]. Returns `None` if the pointer is
+	/// invalid or does not reference any value.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{Parse, Value};
+	///
+	/// let (value, code_map) = Value::parse_str(r#"{ "a": [true, { "b/c": null }] }"#).unwrap();
+	///
+	/// let i = code_map.fragment_at_pointer(&value, "/a/1/b~1c").unwrap();
+	/// assert_eq!(i, 8);
+	/// assert_eq!(code_map[i].span.range(), 23..27); // null
+	/// assert!(code_map.fragment_at_pointer(&value, "/a/2").is_none());
+	/// ```
+	pub fn fragment_at_pointer(&self, value: &Value, pointer: &str) -> Option<usize> {
+		let mut value = value;
+		let mut offset = 0;
+
+		for token in tokens(pointer)? {
+			match value {
+				Value::Array(items) => {
+					let i = array_index(&token)?;
+					value = items.get(i)?;
+					offset = self.child_offset(offset, i)?
+				}
+				Value::Object(object) => {
+					let i = object.index_of(token.as_ref())?;
+					value = &object.entries()[i].value;
+					offset = self.child_offset(offset, i)? + 2
+				}
+				_ => return None,
+			}
+		}
+
+		Some(offset)
+	}
+
+	/// Returns an iterator over the values of `value` (including `value`
+	/// itself), in document order, with their JSON Pointer, kind and span,
+	/// where `self` is the code map of `value`.
+	///
+	/// Object entries and keys are not visited.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{Kind, Parse, Value};
+	///
+	/// let source = r#"{ "a": [true, null] }"#;
+	/// let (value, code_map) = Value::parse_str(source).unwrap();
+	///
+	/// let outline: Vec<_> = code_map
+	///   .iter_with(&value)
+	///   .map(|(pointer, kind, span)| (pointer, kind, &source[span.range()]))
+	///   .collect();
+	///
+	/// assert_eq!(outline, [
+	///   ("".to_string(), Kind::Object, source),
+	///   ("/a".to_string(), Kind::Array, "[true, null]"),
+	///   ("/a/0".to_string(), Kind::Boolean, "true"),
+	///   ("/a/1".to_string(), Kind::Null, "null")
+	/// ]);
+	/// ```
+	pub fn iter_with<'a>(&'a self, value: &'a Value) -> IterWith<'a> {
+		IterWith {
+			values: value.select_where(|_, _| true),
+			code_map: self,
+		}
+	}
+
+	/// Returns the fragment index of the `i`-th child (array item or object
+	/// entry) of the container at `offset`.
+	fn child_offset(&self, offset: usize, i: usize) -> Option<usize> {
+		let mut child = offset + 1;
+		for _ in 0..i {
+			child += self.get(child)?.volume
+		}
+
+		Some(child)
+	}
+
+	/// Finds the child (array item or object entry) of the container at
+	/// `offset` containing the given fragment.
+	///
+	/// Returns the position of the child in the container and its fragment
+	/// index.
+	fn child_containing(
+		&self,
+		offset: usize,
+		len: usize,
+		fragment: usize,
+	) -> Option<(usize, usize)> {
+		let mut child = offset + 1;
+		for i in 0..len {
+			let volume = self.get(child)?.volume;
+			if fragment < child + volume {
+				return Some((i, child));
+			}
+
+			child += volume
+		}
+
+		None
+	}
+}
+
+/// Parses an array index reference token for insertion, where `-` designates
+/// the end of the array.
+fn insert_index(items: &[Value], token: &str) -> Option<usize> {
+	if token == "-" {
+		Some(items.len())
+	} else {
+		array_index(token)
+	}
+}
+
+/// Checks that [`Value::insert_at`] can create the missing values along the
+/// path made of `tokens` and `last`, without modifying `value`.
+fn check_insert_path(value: &Value, tokens: &[Cow<str>], last: &str) -> Result<(), InsertError> {
+	// Value referenced by the previous tokens, or `None` if it is missing and
+	// will be created.
+	let mut current = Some(value);
+	for (i, token) in tokens.iter().map(Cow::as_ref).chain([last]).enumerate() {
+		current = match current {
+			None | Some(Value::Null) => match container_for(token) {
+				Value::Array(_) if insert_index(&[], token) != Some(0) => {
+					return Err(InsertError::NotFound(i))
+				}
+				_ => None,
+			},
+			Some(Value::Object(object)) => object.get(token).next(),
+			Some(Value::Array(items)) => {
+				let index = insert_index(items, token).ok_or(InsertError::InvalidIndex(i))?;
+				match index.cmp(&items.len()) {
+					Ordering::Less => Some(&items[index]),
+					Ordering::Equal => None,
+					Ordering::Greater => return Err(InsertError::NotFound(i)),
+				}
+			}
+			Some(other) => return Err(InsertError::NotAContainer(i, other.kind())),
+		}
+	}
+
+	Ok(())
+}
+
+/// Returns an empty container that can be indexed by the given token.
+fn container_for(token: &str) -> Value {
+	if token == "-" || array_index(token).is_some() {
+		Value::Array(Vec::new())
+	} else {
+		Value::Object(crate::Object::new())
+	}
+}
+
+/// Whether [`Value::insert_at`] creates missing intermediate values.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CreateIntermediates {
+	/// Create missing intermediate objects and arrays.
+	Yes,
+
+	/// Fail if an intermediate value is missing.
+	#[default]
+	No,
+}
+
+/// Insertion error, returned by [`Value::insert_at`].
+///
+/// Each variant (but `InvalidPointer`) holds the position of the offending
+/// reference token in the pointer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InsertError {
+	/// The pointer is invalid.
+	InvalidPointer,
+
+	/// The reference token does not reference any value.
+	NotFound(usize),
+
+	/// The reference token is not a valid array index.
+	InvalidIndex(usize),
+
+	/// The reference token is applied to a value that is neither an object
+	/// nor an array.
+	NotAContainer(usize, Kind),
+}
+
+impl fmt::Display for InsertError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::InvalidPointer => write!(f, "invalid JSON pointer"),
+			Self::NotFound(i) => write!(f, "reference token {i} does not reference any value"),
+			Self::InvalidIndex(i) => write!(f, "reference token {i} is not a valid array index"),
+			Self::NotAContainer(i, kind) => {
+				write!(f, "reference token {i} cannot be applied to {kind} value")
+			}
+		}
+	}
+}
+
+impl std::error::Error for InsertError {}
+
+/// What to leave behind when taking a value out with [`Value::take_at`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TakeMode {
+	/// Remove the object entry or array item.
+	#[default]
+	Remove,
+
+	/// Replace the value with `null`.
+	LeaveNull,
+}
+
+/// Iterator over the values matching a predicate, with their JSON Pointer.
+///
+/// Returned by [`Value::select_where`].
+pub struct SelectWhere<'a, F> {
+	traverse: Traverse<'a>,
+
+	/// Fragment index of the last visited fragment.
+	index: usize,
+
+	/// Pointer to the last visited value or entry.
+	pointer: String,
+
+	/// Arrays, objects and entries being traversed.
+	stack: Vec<SelectFrame>,
+
+	predicate: F,
+}
+
+/// Array, object or entry being traversed by [`SelectWhere`].
+struct SelectFrame {
+	/// Length of the pointer to the container.
+	len: usize,
+
+	/// Number of fragments remaining to be visited in the container.
+	remaining: usize,
+
+	/// Next array index, or `None` if the container is not an array.
+	index: Option<usize>,
+}
+
+impl<'a, F> SelectWhere<'a, F> {
+	/// Updates the pointer for the next fragment.
+	fn enter(&mut self, fragment: FragmentRef<'a>) {
+		while self.stack.last().is_some_and(|frame| frame.remaining == 0) {
+			self.stack.pop();
+		}
+
+		match self.stack.last_mut() {
+			Some(frame) => {
+				frame.remaining -= 1;
+				self.pointer.truncate(frame.len);
+				match fragment {
+					FragmentRef::Value(_) => {
+						if let Some(index) = &mut frame.index {
+							self.pointer.push('/');
+							self.pointer.push_str(&index.to_string());
+							*index += 1
+						}
+					}
+					FragmentRef::Entry(entry) => {
+						self.pointer.push('/');
+						self.pointer.push_str(&escape_token(&entry.key))
+					}
+					FragmentRef::Key(_) => (),
+				}
+			}
+			None => self.pointer.clear(),
+		}
+
+		let (remaining, index) = match fragment {
+			FragmentRef::Value(Value::Array(items)) => (items.len(), Some(0)),
+			FragmentRef::Value(Value::Object(object)) => (object.len(), None),
+			FragmentRef::Entry(_) => (2, None),
+			_ => return,
+		};
+
+		self.stack.push(SelectFrame {
+			len: self.pointer.len(),
+			remaining,
+			index,
+		})
+	}
+}
+
+impl<'a, F> Iterator for SelectWhere<'a, F>
+where
+	F: FnMut(&str, &Value) -> bool,
+{
+	type Item = (String, &'a Value);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		while let Some((index, fragment)) = self.traverse.next() {
+			self.index = index;
+			self.enter(fragment);
+			if let FragmentRef::Value(value) = fragment {
+				if (self.predicate)(&self.pointer, value) {
+					return Some((self.pointer.clone(), value));
+				}
+			}
+		}
+
+		None
+	}
+}
+
+/// Iterator over the values of a value with their JSON Pointer, kind and
+/// span.
+///
+/// Returned by [`CodeMap::iter_with`].
+pub struct IterWith<'a> {
+	values: SelectWhere<'a, fn(&str, &Value) -> bool>,
+	code_map: &'a CodeMap,
+}
+
+impl<'a> Iterator for IterWith<'a> {
+	type Item = (String, Kind, Span);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let (pointer, value) = self.values.next()?;
+		let span = self.code_map.get(self.values.index)?.span;
+		Some((pointer, value.kind(), span))
+	}
+}
+
+/// Resolves the given (unescaped) reference tokens.
+fn resolve_mut<'a, 't>(
+	value: &'a mut Value,
+	tokens: impl IntoIterator<Item = Cow<'t, str>>,
+) -> Option<&'a mut Value> {
+	tokens
+		.into_iter()
+		.try_fold(value, |value, token| match value {
+			Value::Object(object) => object.get_mut(token.as_ref()).next(),
+			Value::Array(items) => items.get_mut(array_index(&token)?),
+			_ => None,
+		})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn invalid_escape() {
+		assert!(unescape_token("a~2").is_none());
+		assert!(unescape_token("a~").is_none());
+		assert_eq!(unescape_token("~01").unwrap(), "~1");
+	}
+
+	#[test]
+	fn array_indexes() {
+		assert_eq!(array_index("0"), Some(0));
+		assert_eq!(array_index("10"), Some(10));
+		assert_eq!(array_index("01"), None);
+		assert_eq!(array_index("-"), None);
+		assert_eq!(array_index("+1"), None);
+	}
+
+	#[test]
+	fn select_where() {
+		let value = crate::json!([{ "a": [[], null] }, { "": null, "~": { "b": null } }]);
+		let nulls: Vec<_> = value
+			.select_where(|_, value| value.is_null())
+			.map(|(pointer, _)| pointer)
+			.collect();
+		assert_eq!(nulls, ["/0/a/1", "/1/", "/1/~0/b"]);
+
+		let all: Vec<_> = value.select_where(|_, _| true).collect();
+		assert_eq!(all.len(), value.volume());
+		assert_eq!(all[0], (String::new(), &value))
+	}
+
+	#[test]
+	fn flatten() {
+		let value = crate::json!([{ "a": null, "": [[]] }, true]);
+		let flat = value.flatten();
+		assert_eq!(
+			crate::json!(flat.clone()),
+			crate::json!({ "/0/a": null, "/0//0": [], "/1": true })
+		);
+		assert_eq!(flat.unflatten().unwrap(), value);
+
+		let value = crate::json!({ "a": { "0": 1 } });
+		assert_eq!(
+			value.flatten().unflatten().unwrap(),
+			crate::json!({ "a": [1] })
+		);
+
+		assert_eq!(Value::Null.flatten().unflatten().unwrap(), Value::Null);
+	}
+
+	#[test]
+	fn insert_at() {
+		let mut value = Value::Null;
+		value
+			.insert_at("/a/-/b", Value::Boolean(true), CreateIntermediates::Yes)
+			.unwrap();
+		assert_eq!(value, crate::json!({ "a": [{ "b": true }] }));
+
+		assert_eq!(
+			value.insert_at("/a/2", Value::Null, CreateIntermediates::No),
+			Err(InsertError::NotFound(1))
+		);
+		assert_eq!(
+			value.insert_at("/a/0/b/c", Value::Null, CreateIntermediates::Yes),
+			Err(InsertError::NotAContainer(3, Kind::Boolean))
+		);
+
+		// Arrays are never padded.
+		assert_eq!(
+			value.insert_at("/a/2", Value::Null, CreateIntermediates::Yes),
+			Err(InsertError::NotFound(1))
+		);
+		assert_eq!(
+			value.insert_at("/a/2/b", Value::Null, CreateIntermediates::Yes),
+			Err(InsertError::NotFound(1))
+		);
+
+		// Intermediate values are not created if the insertion fails.
+		assert_eq!(
+			value.insert_at("/c/d/1", Value::Null, CreateIntermediates::Yes),
+			Err(InsertError::NotFound(2))
+		);
+		assert_eq!(
+			value.insert_at("/a/-/e/1", Value::Null, CreateIntermediates::Yes),
+			Err(InsertError::NotFound(3))
+		);
+		assert_eq!(value, crate::json!({ "a": [{ "b": true }] }));
+
+		let mut flat = Object::new();
+		flat.push("/99999999999".into(), Value::Null);
+		assert_eq!(flat.unflatten(), Err(InsertError::NotFound(0)));
+	}
+
+	#[test]
+	fn pointer_of() {
+		use crate::Parse;
+		let (value, code_map) =
+			crate::Value::parse_str(r#"[{ "a": [[], null], "": {} }, { "~": { "b": 1 } }]"#)
+				.unwrap();
+		let pointers: Vec<_> = (0..code_map.len())
+			.map(|i| code_map.pointer_of(&value, i).unwrap())
+			.collect();
+		assert_eq!(
+			pointers,
+			[
+				"", "/0", "/0/a", "/0/a", "/0/a", "/0/a/0", "/0/a/1", "/0/", "/0/", "/0/", "/1",
+				"/1/~0", "/1/~0", "/1/~0", "/1/~0/b", "/1/~0/b", "/1/~0/b"
+			]
+		);
+		assert!(code_map.pointer_of(&value, code_map.len()).is_none());
+
+		for (i, fragment) in value.traverse() {
+			if fragment.is_value() {
+				let pointer = code_map.pointer_of(&value, i).unwrap();
+				assert_eq!(code_map.fragment_at_pointer(&value, &pointer), Some(i))
+			}
+		}
+
+		assert!(code_map.fragment_at_pointer(&value, "/0/a/2").is_none());
+		assert!(code_map.fragment_at_pointer(&value, "/0/b").is_none());
+		assert!(code_map.fragment_at_pointer(&value, "/1/~0/b/c").is_none());
+		assert!(code_map.fragment_at_pointer(&value, "1").is_none());
+	}
+
+	#[test]
+	fn iter_with() {
+		use crate::Parse;
+		let (value, code_map) = crate::Value::parse_str(r#"[{ "a": {} }, "b"]"#).unwrap();
+		let items: Vec<_> = code_map
+			.iter_with(&value)
+			.map(|(pointer, kind, span)| (pointer, kind, span.range()))
+			.collect();
+		assert_eq!(
+			items,
+			[
+				("".to_owned(), Kind::Array, 0..18),
+				("/0".to_owned(), Kind::Object, 1..12),
+				("/0/a".to_owned(), Kind::Object, 8..10),
+				("/1".to_owned(), Kind::String, 14..17)
+			]
+		);
+	}
+}
@@ -0,0 +1,96 @@
+use std::fmt;
+
+use super::string_literal;
+use crate::{object::Entry, Value};
+
+impl Value {
+	/// Prints this value in canonical form according to
+	/// [RFC 8785](https://www.rfc-editor.org/rfc/rfc8785), without modifying
+	/// it.
+	///
+	/// The output is the same as calling [`Value::canonicalize`] on a copy of
+	/// the value and then printing it with
+	/// [`compact_print`](crate::Print::compact_print).
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::json;
+	///
+	/// let value = json!({ "b": 4.50, "a": [1E30, 2e-3] });
+	/// assert_eq!(
+	///   value.canonical_print().to_string(),
+	///   "{\"a\":[1e+30,0.002],\"b\":4.5}"
+	/// )
+	/// ```
+	pub fn canonical_print(&self) -> CanonicalPrinted<'_> {
+		CanonicalPrinted(self)
+	}
+}
+
+/// Value printed in canonical form.
+///
+/// See [`Value::canonical_print`].
+pub struct CanonicalPrinted<'a>(&'a Value);
+
+impl fmt::Display for CanonicalPrinted<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut buffer = ryu_js::Buffer::new();
+		fmt_canonical(self.0, f, &mut buffer)
+	}
+}
+
+fn fmt_canonical(
+	value: &Value,
+	f: &mut fmt::Formatter,
+	buffer: &mut ryu_js::Buffer,
+) -> fmt::Result {
+	match value {
+		Value::Null => f.write_str("null"),
+		Value::Boolean(true) => f.write_str("true"),
+		Value::Boolean(false) => f.write_str("false"),
+		Value::Number(n) => f.write_str(n.canonical_with(buffer).as_str()),
+		Value::String(s) => string_literal(s, f),
+		Value::Array(a) => {
+			f.write_str("[")?;
+
+			for (i, item) in a.iter().enumerate() {
+				if i > 0 {
+					f.write_str(",")?
+				}
+
+				fmt_canonical(item, f, buffer)?
+			}
+
+			f.write_str("]")
+		}
+		Value::Object(o) => {
+			let mut entries: Vec<&Entry> = o.iter().collect();
+			entries.sort_by(|a, b| {
+				a.key.cmp(&b.key).then_with(|| {
+					// Entries with the same key are sorted by canonical value,
+					// as done by `Object::canonicalize`.
+					let mut a = a.value.clone();
+					let mut b = b.value.clone();
+					a.canonicalize_with(buffer);
+					b.canonicalize_with(buffer);
+					a.cmp(&b)
+				})
+			});
+
+			f.write_str("{")?;
+
+			for (i, entry) in entries.into_iter().enumerate() {
+				if i > 0 {
+					f.write_str(",")?
+				}
+
+				string_literal(&entry.key, f)?;
+				f.write_str(":")?;
+				fmt_canonical(&entry.value, f, buffer)?
+			}
+
+			f.write_str("}")
+		}
+	}
+}
@@ -0,0 +1,89 @@
+use core::fmt;
+
+use crate::{FragmentRef, NumberBuf, Value};
+
+/// Invalid output error.
+///
+/// Returned by [`strict_check`] when a value would not serialize to valid
+/// JSON.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InvalidOutput {
+	/// Number that is not lexically valid (e.g. `NaN`), with its fragment
+	/// index.
+	InvalidNumber { offset: usize, number: NumberBuf },
+}
+
+impl InvalidOutput {
+	/// Returns the index of the invalid fragment.
+	pub fn offset(&self) -> usize {
+		match self {
+			Self::InvalidNumber { offset, .. } => *offset,
+		}
+	}
+}
+
+impl fmt::Display for InvalidOutput {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::InvalidNumber { number, .. } => write!(f, "invalid number `{number}`"),
+		}
+	}
+}
+
+impl std::error::Error for InvalidOutput {}
+
+/// Checks that the given value serializes to valid
+/// [RFC 8259](https://datatracker.ietf.org/doc/html/rfc8259) JSON.
+///
+/// Numbers are stored in lexical form and can be built without validation
+/// (using `NumberBuf::new_unchecked`). This function checks that every
+/// number is lexically valid, so that no `NaN`, `Infinity` or otherwise
+/// malformed number ends up in the output. Strings and keys are always
+/// valid Unicode (they cannot contain unpaired surrogates) and are escaped
+/// when printed, so they are always valid.
+///
+/// It is intended to run before emitting documents constructed
+/// programmatically from untrusted pieces. The first invalid fragment is
+/// reported.
+///
+/// # Example
+///
+/// ```
+/// use json_syntax::{json, print::strict_check};
+///
+/// let value = json!({ "a": [1, 2.5e-3, "b"] });
+/// assert!(strict_check(&value).is_ok());
+/// ```
+pub fn strict_check(value: &Value) -> Result<(), InvalidOutput> {
+	for (offset, fragment) in value.traverse() {
+		if let FragmentRef::Value(Value::Number(n)) = fragment {
+			if json_number::Number::new(n.as_str()).is_err() {
+				return Err(InvalidOutput::InvalidNumber {
+					offset,
+					number: n.clone(),
+				});
+			}
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::json;
+
+	#[test]
+	fn invalid_number() {
+		let nan = unsafe { NumberBuf::new_unchecked("NaN".as_bytes().into()) };
+		let value = json!({ "a": [1, nan.clone()] });
+		assert_eq!(
+			strict_check(&value),
+			Err(InvalidOutput::InvalidNumber {
+				offset: 5,
+				number: nan
+			})
+		)
+	}
+}
@@ -0,0 +1,76 @@
+use std::fmt;
+
+use super::{
+	layout::{Driver, Hooks},
+	Options,
+};
+use crate::Value;
+
+impl Value {
+	/// Prints the value with the given options, emitting comments.
+	///
+	/// The `comment` function is called with the index of every fragment of
+	/// the value (following the [`CodeMap`](crate::CodeMap) order). If it
+	/// returns a comment, it is printed just before the fragment: as a `//`
+	/// line comment if the fragment is on its own line, or as a `/* */`
+	/// block comment if it is part of an inlined array/object. Comments
+	/// attached to an object entry, its key and its value are all printed
+	/// before the entry.
+	///
+	/// Note that JSON does not support comments. The output is meant for
+	/// humans or JSON-with-comments readers.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, print::Options};
+	///
+	/// let value = json!({ "a": 0, "b": 1 });
+	/// let printed = value.print_with_comments(Options::pretty(), |i| {
+	///   (i == 4).then(|| "from defaults".to_string())
+	/// });
+	///
+	/// assert_eq!(
+	///   printed.to_string(),
+	///   "{\n  \"a\": 0,\n  // from defaults\n  \"b\": 1\n}"
+	/// )
+	/// ```
+	pub fn print_with_comments<F>(&self, options: Options, comment: F) -> PrintedWithComments<'_, F>
+	where
+		F: Fn(usize) -> Option<String>,
+	{
+		PrintedWithComments {
+			value: self,
+			options,
+			comment,
+		}
+	}
+}
+
+/// Value printed with comments.
+///
+/// See [`Value::print_with_comments`].
+pub struct PrintedWithComments<'a, F> {
+	value: &'a Value,
+	options: Options,
+	comment: F,
+}
+
+impl<'a, F: Fn(usize) -> Option<String>> fmt::Display for PrintedWithComments<'a, F> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let hooks = Comments(&self.comment);
+		Driver::new(&self.options, &hooks, self.value).print(f, self.value, 0)?;
+		self.options.fmt_trailing_newline(f)
+	}
+}
+
+/// Printing hooks emitting comments.
+struct Comments<'a, F>(&'a F);
+
+impl<'a, F: Fn(usize) -> Option<String>> Hooks for Comments<'a, F> {
+	const COMMENTS: bool = true;
+
+	fn comment(&self, fragment: usize) -> Option<String> {
+		(self.0)(fragment)
+	}
+}
@@ -79,6 +79,22 @@ pub trait PrecomputeSizeWithContext<C> {
 		options: &Options,
 		sizes: &mut Vec<Size>,
 	) -> Size;
+
+	/// Pre-computes the size of a value located at the given depth.
+	///
+	/// Contextual counterpart of
+	/// [`PrecomputeSize::pre_compute_size_at_depth`](super::PrecomputeSize::pre_compute_size_at_depth).
+	/// Collections must override it so that [`Options::depth_limits`]
+	/// applies to their items.
+	fn contextual_pre_compute_size_at_depth(
+		&self,
+		context: &C,
+		options: &Options,
+		_depth: usize,
+		sizes: &mut Vec<Size>,
+	) -> Size {
+		self.contextual_pre_compute_size(context, options, sizes)
+	}
 }
 
 impl<'a, T: PrecomputeSizeWithContext<C> + ?Sized, C> PrecomputeSizeWithContext<C> for &'a T {
@@ -90,12 +106,32 @@ impl<'a, T: PrecomputeSizeWithContext<C> + ?Sized, C> PrecomputeSizeWithContext<
 	) -> Size {
 		T::contextual_pre_compute_size(*self, context, options, sizes)
 	}
+
+	fn contextual_pre_compute_size_at_depth(
+		&self,
+		context: &C,
+		options: &Options,
+		depth: usize,
+		sizes: &mut Vec<Size>,
+	) -> Size {
+		T::contextual_pre_compute_size_at_depth(*self, context, options, depth, sizes)
+	}
 }
 
 impl<'c, T: PrecomputeSizeWithContext<C>, C> super::PrecomputeSize for Contextual<T, &'c C> {
 	fn pre_compute_size(&self, options: &Options, sizes: &mut Vec<Size>) -> Size {
 		self.0.contextual_pre_compute_size(self.1, options, sizes)
 	}
+
+	fn pre_compute_size_at_depth(
+		&self,
+		options: &Options,
+		depth: usize,
+		sizes: &mut Vec<Size>,
+	) -> Size {
+		self.0
+			.contextual_pre_compute_size_at_depth(self.1, options, depth, sizes)
+	}
 }
 
 impl<T: PrecomputeSizeWithContext<C>, M, C> PrecomputeSizeWithContext<C> for locspan::Meta<T, M> {
@@ -107,6 +143,17 @@ impl<T: PrecomputeSizeWithContext<C>, M, C> PrecomputeSizeWithContext<C> for loc
 	) -> Size {
 		self.0.contextual_pre_compute_size(context, options, sizes)
 	}
+
+	fn contextual_pre_compute_size_at_depth(
+		&self,
+		context: &C,
+		options: &Options,
+		depth: usize,
+		sizes: &mut Vec<Size>,
+	) -> Size {
+		self.0
+			.contextual_pre_compute_size_at_depth(context, options, depth, sizes)
+	}
 }
 
 impl<T: PrintWithSizeAndContext<C>, M, C> PrintWithSizeAndContext<C> for locspan::Meta<T, M> {
@@ -145,6 +192,17 @@ impl<T: PrecomputeSizeWithContext<C>, C> PrecomputeSizeWithContext<C> for locspa
 	) -> Size {
 		self.0.contextual_pre_compute_size(context, options, sizes)
 	}
+
+	fn contextual_pre_compute_size_at_depth(
+		&self,
+		context: &C,
+		options: &Options,
+		depth: usize,
+		sizes: &mut Vec<Size>,
+	) -> Size {
+		self.0
+			.contextual_pre_compute_size_at_depth(context, options, depth, sizes)
+	}
 }
 
 impl<T: PrintWithSizeAndContext<C>, C> PrintWithSizeAndContext<C> for locspan::Stripped<T> {
@@ -181,7 +239,22 @@ impl<T: PrecomputeSizeWithContext<C>, C> PrecomputeSizeWithContext<C> for [T] {
 		options: &Options,
 		sizes: &mut Vec<Size>,
 	) -> Size {
-		super::pre_compute_array_size(self.iter().map(|i| i.with(context)), options, sizes)
+		self.contextual_pre_compute_size_at_depth(context, options, 0, sizes)
+	}
+
+	fn contextual_pre_compute_size_at_depth(
+		&self,
+		context: &C,
+		options: &Options,
+		depth: usize,
+		sizes: &mut Vec<Size>,
+	) -> Size {
+		super::pre_compute_array_size_at_depth(
+			self.iter().map(|i| i.with(context)),
+			options,
+			depth,
+			sizes,
+		)
 	}
 }
 
@@ -213,7 +286,22 @@ impl<T: PrecomputeSizeWithContext<C>, C> PrecomputeSizeWithContext<C> for HashSe
 		options: &Options,
 		sizes: &mut Vec<Size>,
 	) -> Size {
-		super::pre_compute_array_size(self.iter().map(|i| i.with(context)), options, sizes)
+		self.contextual_pre_compute_size_at_depth(context, options, 0, sizes)
+	}
+
+	fn contextual_pre_compute_size_at_depth(
+		&self,
+		context: &C,
+		options: &Options,
+		depth: usize,
+		sizes: &mut Vec<Size>,
+	) -> Size {
+		super::pre_compute_array_size_at_depth(
+			self.iter().map(|i| i.with(context)),
+			options,
+			depth,
+			sizes,
+		)
 	}
 }
 
@@ -237,3 +325,34 @@ impl<T: PrintWithSizeAndContext<C>, C> PrintWithSizeAndContext<C> for HashSet<T>
 		)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::print::{Limit, PrecomputeSize};
+
+	struct Item;
+
+	impl PrecomputeSizeWithContext<()> for Item {
+		fn contextual_pre_compute_size(
+			&self,
+			_context: &(),
+			_options: &Options,
+			_sizes: &mut Vec<Size>,
+		) -> Size {
+			Size::Width(1)
+		}
+	}
+
+	#[test]
+	fn depth_limits() {
+		let inner = [Item];
+		let outer = [&inner[..]];
+		let options = Options::pretty().depth_limits(vec![Limit::Never, Limit::Always]);
+
+		let mut sizes = Vec::new();
+		let size = outer[..].with(&()).pre_compute_size(&options, &mut sizes);
+		assert!(matches!(size, Size::Expanded));
+		assert!(matches!(sizes[..], [Size::Expanded, Size::Expanded]))
+	}
+}
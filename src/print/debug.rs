@@ -0,0 +1,66 @@
+use std::fmt;
+
+use super::{Options, Print};
+use crate::Value;
+
+impl Value {
+	/// Returns a [`fmt::Debug`] wrapper printing the value as compact JSON.
+	///
+	/// The derived `Debug` output of large nested values is hard to read in
+	/// test failures and logs. This wrapper can be used instead, for
+	/// instance in assertion messages. Enabling the `compact-debug` feature
+	/// makes compact JSON the default `Debug` representation of [`Value`]
+	/// and [`Object`](crate::Object).
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::json;
+	///
+	/// let value = json!({ "a": [1, null, "b"] });
+	/// assert_eq!(format!("{:?}", value.debug()), "{\"a\":[1,null,\"b\"]}");
+	/// ```
+	pub fn debug(&self) -> CompactDebug<'_, Self> {
+		CompactDebug(self)
+	}
+}
+
+/// Compact JSON [`fmt::Debug`] wrapper.
+///
+/// Returned by [`Value::debug`].
+pub struct CompactDebug<'a, T: ?Sized>(pub &'a T);
+
+impl<'a, T: Print + ?Sized> fmt::Debug for CompactDebug<'a, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt_with(f, &Options::compact(), 0)
+	}
+}
+
+impl<'a, T: Print + ?Sized> fmt::Display for CompactDebug<'a, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Debug::fmt(self, f)
+	}
+}
+
+#[cfg(feature = "compact-debug")]
+impl fmt::Debug for Value {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.debug().fmt(f)
+	}
+}
+
+#[cfg(feature = "compact-debug")]
+impl fmt::Debug for crate::Object {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let options = Options::compact();
+		let mut sizes = Vec::new();
+		super::pre_compute_object_size(
+			options
+				.object_entries(self)
+				.map(|e| (e.key.as_str(), &e.value)),
+			&options,
+			&mut sizes,
+		);
+		super::PrintWithSize::fmt_with_size(self, f, &options, 0, &sizes, &mut 0)
+	}
+}
@@ -0,0 +1,59 @@
+use std::fmt;
+
+use super::{Options, Print};
+
+/// Object-safe version of [`Print`].
+///
+/// The [`Print`] trait provides methods returning `Printed<Self>`, which
+/// prevents using it as a trait object. This trait is implemented for every
+/// [`Print`] type, and `dyn DynPrint` itself implements [`Print`], so
+/// heterogeneous printable things can be stored as `Box<dyn DynPrint>` and
+/// printed as usual.
+///
+/// # Example
+///
+/// ```
+/// use json_syntax::{json, Print, print::DynPrint};
+///
+/// let items: Vec<Box<dyn DynPrint>> = vec![
+///   Box::new(json!({ "a": 1 })),
+///   Box::new(true),
+///   Box::new(json_syntax::String::from("b")),
+/// ];
+///
+/// let printed: Vec<_> = items.iter().map(|i| i.compact_print().to_string()).collect();
+/// assert_eq!(printed, ["{\"a\":1}", "true", "\"b\""]);
+/// ```
+pub trait DynPrint {
+	fn dyn_fmt_with(&self, f: &mut fmt::Formatter, options: &Options, indent: usize)
+		-> fmt::Result;
+}
+
+impl<T: Print + ?Sized> DynPrint for T {
+	fn dyn_fmt_with(
+		&self,
+		f: &mut fmt::Formatter,
+		options: &Options,
+		indent: usize,
+	) -> fmt::Result {
+		self.fmt_with(f, options, indent)
+	}
+}
+
+impl<'a> Print for dyn DynPrint + 'a {
+	fn fmt_with(&self, f: &mut fmt::Formatter, options: &Options, indent: usize) -> fmt::Result {
+		self.dyn_fmt_with(f, options, indent)
+	}
+}
+
+impl<'a> Print for dyn DynPrint + Send + Sync + 'a {
+	fn fmt_with(&self, f: &mut fmt::Formatter, options: &Options, indent: usize) -> fmt::Result {
+		self.dyn_fmt_with(f, options, indent)
+	}
+}
+
+impl<T: Print + ?Sized> Print for Box<T> {
+	fn fmt_with(&self, f: &mut fmt::Formatter, options: &Options, indent: usize) -> fmt::Result {
+		(**self).fmt_with(f, options, indent)
+	}
+}
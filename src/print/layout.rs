@@ -0,0 +1,888 @@
+//! Array and object layout, shared by all the value printers.
+//!
+//! [`Collection`] writes the delimiters, separators and indentation of an
+//! array or object, and is used by both [`print_array`](super::print_array)
+//! and [`print_object`](super::print_object) and the [`Driver`]. The driver
+//! prints [`Value`]s, and is customized with [`Hooks`] to emit comments or
+//! elide content, and with an [`Output`] to limit the printed length.
+//!
+//! Plain printing (without hooks) does not go through the driver, but
+//! directly through `print_array` and `print_object`.
+use std::fmt;
+
+use super::{printed_string_size, string_literal, Indent, Options, Size, Spaces};
+use crate::{Object, Value};
+
+/// Elision marker.
+pub(crate) const ELLIPSIS: &str = "…";
+
+/// Returns the length of `n` indentations.
+fn indent_len(indent: Indent, n: usize) -> usize {
+	match indent {
+		Indent::Spaces(i) | Indent::Tabs(i) => i as usize * n,
+	}
+}
+
+/// String literal.
+pub(crate) struct Literal<'s>(pub &'s str);
+
+impl<'s> fmt::Display for Literal<'s> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		string_literal(self.0, f)
+	}
+}
+
+/// Array or object layout.
+pub(crate) struct Collection<'a> {
+	options: &'a Options,
+	open: char,
+	close: char,
+	begin: usize,
+	end: usize,
+	before_comma: usize,
+	after_comma: usize,
+	indent: usize,
+	expanded: bool,
+
+	/// Number of spaces inside the collection if it is empty and inlined.
+	empty: Option<usize>,
+}
+
+impl<'a> Collection<'a> {
+	pub fn array(options: &'a Options, size: Size, indent: usize, empty: bool) -> Self {
+		Self {
+			options,
+			open: '[',
+			close: ']',
+			begin: options.array_begin,
+			end: options.array_end,
+			before_comma: options.array_before_comma,
+			after_comma: options.array_after_comma,
+			indent,
+			expanded: matches!(size, Size::Expanded),
+			empty: empty.then_some(options.array_empty),
+		}
+	}
+
+	pub fn object(options: &'a Options, size: Size, indent: usize, empty: bool) -> Self {
+		Self {
+			options,
+			open: '{',
+			close: '}',
+			begin: options.object_begin,
+			end: options.object_end,
+			before_comma: options.object_before_comma,
+			after_comma: options.object_after_comma,
+			indent,
+			expanded: matches!(size, Size::Expanded),
+			empty: empty.then_some(options.object_empty),
+		}
+	}
+
+	pub fn is_expanded(&self) -> bool {
+		self.expanded
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.empty.is_some()
+	}
+
+	/// Writes the opening delimiter.
+	///
+	/// If the collection is empty, this writes everything up to the closing
+	/// delimiter.
+	pub fn begin<W: fmt::Write + ?Sized>(&self, w: &mut W) -> fmt::Result {
+		w.write_char(self.open)?;
+		match self.empty {
+			Some(_) if self.expanded => {
+				write!(w, "\n{}", self.options.indent.by(self.indent))
+			}
+			Some(empty) => write!(w, "{}", Spaces(empty)),
+			None if self.expanded => w.write_char('\n'),
+			None => write!(w, "{}", Spaces(self.begin)),
+		}
+	}
+
+	/// Returns the length of what [`Self::begin`] writes.
+	pub fn begin_len(&self) -> usize {
+		1 + match self.empty {
+			Some(_) if self.expanded => 1 + indent_len(self.options.indent, self.indent),
+			Some(empty) => empty,
+			None if self.expanded => 1,
+			None => self.begin,
+		}
+	}
+
+	/// Writes what comes before the `i`-th item: the separator from the
+	/// previous item, and the indentation.
+	pub fn item<W: fmt::Write + ?Sized>(&self, w: &mut W, i: usize) -> fmt::Result {
+		if i > 0 {
+			write!(w, "{},", Spaces(self.before_comma))?;
+			if self.expanded {
+				w.write_char('\n')?
+			} else {
+				write!(w, "{}", Spaces(self.after_comma))?
+			}
+		}
+
+		if self.expanded {
+			write!(w, "{}", self.options.indent.by(self.indent + 1))?
+		}
+
+		Ok(())
+	}
+
+	/// Returns the length of what [`Self::item`] writes.
+	pub fn item_len(&self, i: usize) -> usize {
+		let mut len = 0;
+		if i > 0 {
+			len += self.before_comma + 1 + if self.expanded { 1 } else { self.after_comma }
+		}
+
+		if self.expanded {
+			len += indent_len(self.options.indent, self.indent + 1)
+		}
+
+		len
+	}
+
+	/// Writes the given object key, followed by the colon.
+	///
+	/// The key is padded up to `key_width` if the object is expanded.
+	pub fn key<W: fmt::Write + ?Sized>(
+		&self,
+		w: &mut W,
+		key: &str,
+		key_width: usize,
+	) -> fmt::Result {
+		self.options.fmt_key(key, w)?;
+		if self.expanded {
+			write!(
+				w,
+				"{}",
+				Spaces(key_width.saturating_sub(self.options.key_size(key)))
+			)?
+		}
+
+		write!(
+			w,
+			"{}:{}",
+			Spaces(self.options.object_before_colon),
+			Spaces(self.options.object_after_colon)
+		)
+	}
+
+	/// Returns the length of what [`Self::key`] writes.
+	pub fn key_len(&self, key: &str, key_width: usize) -> usize {
+		let key_size = self.options.key_size(key);
+		let padding = if self.expanded {
+			key_width.saturating_sub(key_size)
+		} else {
+			0
+		};
+
+		key_size + padding + self.options.object_before_colon + 1 + self.options.object_after_colon
+	}
+
+	/// Writes the closing delimiter.
+	pub fn end<W: fmt::Write + ?Sized>(&self, w: &mut W) -> fmt::Result {
+		if self.empty.is_none() {
+			if self.expanded {
+				write!(w, "\n{}", self.options.indent.by(self.indent))?
+			} else {
+				write!(w, "{}", Spaces(self.end))?
+			}
+		}
+
+		w.write_char(self.close)
+	}
+
+	/// Returns the length of what [`Self::end`] writes.
+	pub fn end_len(&self) -> usize {
+		let len = match self.empty {
+			Some(_) => 0,
+			None if self.expanded => 1 + indent_len(self.options.indent, self.indent),
+			None => self.end,
+		};
+
+		len + 1
+	}
+}
+
+/// Printing hooks of the [`Driver`].
+///
+/// By default, nothing is elided and no comment is printed.
+pub(crate) trait Hooks {
+	/// Whether or not comments are printed, requiring the driver to keep
+	/// track of fragment indexes.
+	const COMMENTS: bool = false;
+
+	/// Returns the comment attached to the given fragment.
+	fn comment(&self, _fragment: usize) -> Option<String> {
+		None
+	}
+
+	/// Checks if the content of non-empty arrays and objects at the given
+	/// depth is elided.
+	fn elides_content(&self, _depth: usize) -> bool {
+		false
+	}
+
+	/// Splits the given array items into the printed items and whether or
+	/// not the rest is elided.
+	fn array_items<'v>(&self, items: &'v [Value]) -> (&'v [Value], bool) {
+		(items, false)
+	}
+
+	/// Splits the given string value into its printed prefix and whether or
+	/// not the rest is elided.
+	fn truncate<'s>(&self, s: &'s str) -> (&'s str, bool) {
+		(s, false)
+	}
+}
+
+/// Output of the [`Driver`].
+///
+/// A bounded output makes the driver elide whatever does not fit. Lengths
+/// are counted in characters.
+pub(crate) trait Output: fmt::Write {
+	/// Whether or not the output is bounded. If not, [`Self::fits`] always
+	/// returns `true`.
+	const BOUNDED: bool = false;
+
+	/// Checks that `len` more characters fit in the output, keeping
+	/// `reserved` characters for what follows.
+	fn fits(&self, _len: usize, _reserved: usize) -> bool {
+		true
+	}
+
+	/// Checks that the given string fits in the output, keeping `reserved`
+	/// characters for what follows.
+	fn fits_str(&self, s: &str, reserved: usize) -> bool {
+		!Self::BOUNDED || self.fits(s.chars().count(), reserved)
+	}
+
+	/// Returns the number of written characters.
+	fn len(&self) -> usize {
+		0
+	}
+
+	/// Returns a mark to which the output can be rolled back.
+	fn mark(&self) -> Mark {
+		Mark::default()
+	}
+
+	/// Removes everything written since the given mark.
+	fn rollback(&mut self, _mark: Mark) {}
+}
+
+impl<'a> Output for fmt::Formatter<'a> {}
+
+/// Output position.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct Mark {
+	bytes: usize,
+	len: usize,
+}
+
+/// Output limited to a maximum number of characters.
+pub(crate) struct Bounded {
+	output: String,
+	len: usize,
+	max_len: usize,
+}
+
+impl Bounded {
+	pub fn new(max_len: usize) -> Self {
+		Self {
+			output: String::new(),
+			len: 0,
+			max_len,
+		}
+	}
+
+	pub fn as_str(&self) -> &str {
+		&self.output
+	}
+}
+
+impl fmt::Write for Bounded {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		self.output.push_str(s);
+		self.len += s.chars().count();
+		Ok(())
+	}
+}
+
+impl Output for Bounded {
+	const BOUNDED: bool = true;
+
+	fn fits(&self, len: usize, reserved: usize) -> bool {
+		self.len + len + reserved <= self.max_len
+	}
+
+	fn len(&self) -> usize {
+		self.len
+	}
+
+	fn mark(&self) -> Mark {
+		Mark {
+			bytes: self.output.len(),
+			len: self.len,
+		}
+	}
+
+	fn rollback(&mut self, mark: Mark) {
+		self.output.truncate(mark.bytes);
+		self.len = mark.len
+	}
+}
+
+/// Value printer.
+///
+/// Pre-computes the size of every array and object (following the
+/// [`PrecomputeSize`](super::PrecomputeSize) rules) before laying them out
+/// using [`Collection`].
+pub(crate) struct Driver<'a, H> {
+	options: &'a Options,
+	hooks: &'a H,
+	sizes: Vec<Size>,
+
+	/// Index in `sizes`.
+	index: usize,
+
+	/// Current fragment index, if [`Hooks::COMMENTS`] is set.
+	fragment: usize,
+
+	/// Number of fragments of each fragment subtree (including the fragment
+	/// itself), indexed by fragment. Only computed if [`Hooks::COMMENTS`] is
+	/// set.
+	volumes: Vec<usize>,
+}
+
+impl<'a, H: Hooks> Driver<'a, H> {
+	pub fn new(options: &'a Options, hooks: &'a H, value: &Value) -> Self {
+		let mut driver = Self {
+			options,
+			hooks,
+			sizes: Vec::with_capacity(value.count(|_, v| v.is_array() || v.is_object())),
+			index: 0,
+			fragment: 0,
+			volumes: Vec::new(),
+		};
+
+		driver.pre_compute_size(value, 0);
+
+		if H::COMMENTS {
+			driver.volumes.reserve(value.traverse().count());
+			fragment_volumes(value, &mut driver.volumes);
+		}
+
+		driver
+	}
+
+	fn string_size(&self, s: &str) -> usize {
+		let (prefix, elided) = self.hooks.truncate(s);
+		printed_string_size(prefix) + if elided { ELLIPSIS.chars().count() } else { 0 }
+	}
+
+	fn pre_compute_size(&mut self, value: &Value, depth: usize) -> Size {
+		match value {
+			Value::Null => Size::Width(4),
+			Value::Boolean(true) => Size::Width(4),
+			Value::Boolean(false) => Size::Width(5),
+			Value::Number(n) => Size::Width(self.options.number_size(n)),
+			Value::String(s) => Size::Width(self.string_size(s)),
+			Value::Array(a) if !a.is_empty() && self.hooks.elides_content(depth) => {
+				Size::Width(2 + ELLIPSIS.chars().count())
+			}
+			Value::Object(o) if !o.is_empty() && self.hooks.elides_content(depth) => {
+				Size::Width(2 + ELLIPSIS.chars().count())
+			}
+			Value::Array(a) => self.pre_compute_array_size(a, depth),
+			Value::Object(o) => self.pre_compute_object_size(o, depth),
+		}
+	}
+
+	fn pre_compute_array_size(&mut self, items: &[Value], depth: usize) -> Size {
+		let options = self.options;
+		let index = self.sizes.len();
+		self.sizes.push(Size::Width(0));
+
+		let mut size = Size::Width(2 + options.array_begin + options.array_end);
+		let comma = Size::Width(1 + options.array_before_comma + options.array_after_comma);
+
+		let (printed, elided) = self.hooks.array_items(items);
+		for (i, item) in printed.iter().enumerate() {
+			if i > 0 {
+				size.add(comma)
+			}
+
+			size.add(self.pre_compute_size(item, depth + 1))
+		}
+
+		if elided {
+			if !printed.is_empty() {
+				size.add(comma)
+			}
+
+			size.add(Size::Width(ELLIPSIS.chars().count()))
+		}
+
+		let len = printed.len() + usize::from(elided);
+		let size = super::apply_limit(options.array_limit_at(depth), len, size);
+		self.sizes[index] = size;
+		size
+	}
+
+	fn pre_compute_object_size(&mut self, object: &Object, depth: usize) -> Size {
+		let options = self.options;
+		let index = self.sizes.len();
+		self.sizes.push(Size::Width(0));
+
+		if options.object_align_colons {
+			self.sizes.push(Size::Width(0));
+		}
+
+		let mut size = Size::Width(2 + options.object_begin + options.object_end);
+
+		let mut key_width = 0;
+		for (i, entry) in options.object_entries(object).enumerate() {
+			if i > 0 {
+				size.add(Size::Width(
+					1 + options.object_before_comma + options.object_after_comma,
+				))
+			}
+
+			let key_size = options.key_size(&entry.key);
+			key_width = key_width.max(key_size);
+			size.add(Size::Width(
+				key_size + 1 + options.object_before_colon + options.object_after_colon,
+			));
+			size.add(self.pre_compute_size(&entry.value, depth + 1))
+		}
+
+		let size = super::apply_limit(options.object_limit_at(depth), object.len(), size);
+		self.sizes[index] = size;
+
+		if options.object_align_colons {
+			self.sizes[index + 1] = Size::Width(key_width)
+		}
+
+		size
+	}
+
+	/// Prints the given value, starting at the given indentation level.
+	///
+	/// If nothing fits in the output, an elision marker is printed instead
+	/// (if it fits).
+	pub fn print<O: Output>(&mut self, out: &mut O, value: &Value, indent: usize) -> fmt::Result {
+		self.comments(out, indent, true, 1)?;
+		if !self.value(out, value, indent, 0, 0)? && out.len() == 0 && out.fits(1, 0) {
+			out.write_str(ELLIPSIS)?
+		}
+
+		Ok(())
+	}
+
+	/// Prints the comments attached to the next `count` fragments.
+	fn comments<O: Output>(
+		&self,
+		out: &mut O,
+		indent: usize,
+		expanded: bool,
+		count: usize,
+	) -> fmt::Result {
+		if H::COMMENTS {
+			for i in self.fragment..(self.fragment + count) {
+				if let Some(comment) = self.hooks.comment(i) {
+					if expanded {
+						for line in comment.lines() {
+							writeln!(out, "// {line}")?;
+							write!(out, "{}", self.options.indent.by(indent))?
+						}
+					} else {
+						write!(out, "/* {} */ ", comment.replace("*/", "* /"))?
+					}
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Prints the given value, keeping `reserved` characters for what
+	/// follows.
+	///
+	/// Returns `true` if the value has been entirely printed. Otherwise,
+	/// either nothing or a truncated version of the value has been printed.
+	fn value<O: Output>(
+		&mut self,
+		out: &mut O,
+		value: &Value,
+		indent: usize,
+		depth: usize,
+		reserved: usize,
+	) -> Result<bool, fmt::Error> {
+		self.fragment += 1;
+		match value {
+			Value::Null => self.literal(out, "null", reserved),
+			Value::Boolean(true) => self.literal(out, "true", reserved),
+			Value::Boolean(false) => self.literal(out, "false", reserved),
+			Value::Number(n) => self.literal(out, &self.options.number_lexeme(n), reserved),
+			Value::String(s) => self.string(out, s, reserved),
+			Value::Array(a) if !a.is_empty() && self.hooks.elides_content(depth) => {
+				self.skip_fragments();
+				self.literal(out, &format!("[{ELLIPSIS}]"), reserved)
+			}
+			Value::Object(o) if !o.is_empty() && self.hooks.elides_content(depth) => {
+				self.skip_fragments();
+				self.literal(out, &format!("{{{ELLIPSIS}}}"), reserved)
+			}
+			Value::Array(a) => self.array(out, a, indent, depth, reserved),
+			Value::Object(o) => self.object(out, o, indent, depth, reserved),
+		}
+	}
+
+	/// Skips the sub-fragments of the value being printed.
+	fn skip_fragments(&mut self) {
+		if H::COMMENTS {
+			self.fragment += self.volumes[self.fragment - 1] - 1
+		}
+	}
+
+	fn literal<O: Output>(
+		&self,
+		out: &mut O,
+		s: &str,
+		reserved: usize,
+	) -> Result<bool, fmt::Error> {
+		let fits = out.fits_str(s, reserved);
+		if fits {
+			out.write_str(s)?
+		}
+
+		Ok(fits)
+	}
+
+	fn string<O: Output>(&self, out: &mut O, s: &str, reserved: usize) -> Result<bool, fmt::Error> {
+		let (prefix, elided) = self.hooks.truncate(s);
+		if out.fits(self.string_size(s), reserved) {
+			write_string(out, prefix, elided)?;
+			return Ok(true);
+		}
+
+		// Opening quote, ellipsis and closing quote.
+		let mut len = 2 + ELLIPSIS.chars().count();
+		let mut prefix_len = 0;
+		for c in prefix.chars() {
+			len += printed_string_size(c.encode_utf8(&mut [0; 4])) - 2;
+			if !out.fits(len, reserved) {
+				break;
+			}
+
+			prefix_len += c.len_utf8()
+		}
+
+		if prefix_len > 0 {
+			write_string(out, &prefix[..prefix_len], true)?
+		}
+
+		Ok(false)
+	}
+
+	/// Prints what follows an incomplete item: an elision marker in place
+	/// of the item if nothing has been printed since `start`, or after it
+	/// if `more` items were expected.
+	fn elide_rest<O: Output>(
+		&self,
+		out: &mut O,
+		collection: &Collection,
+		i: usize,
+		start: usize,
+		more: bool,
+	) -> Result<bool, fmt::Error> {
+		if out.len() == start {
+			out.write_str(ELLIPSIS)?
+		} else if more {
+			collection.item(out, i + 1)?;
+			out.write_str(ELLIPSIS)?
+		}
+
+		collection.end(out)?;
+		Ok(false)
+	}
+
+	fn array<O: Output>(
+		&mut self,
+		out: &mut O,
+		items: &[Value],
+		indent: usize,
+		depth: usize,
+		reserved: usize,
+	) -> Result<bool, fmt::Error> {
+		let size = self.sizes[self.index];
+		self.index += 1;
+
+		let (printed, elided) = self.hooks.array_items(items);
+		let collection =
+			Collection::array(self.options, size, indent, printed.is_empty() && !elided);
+
+		let begin_len = collection.begin_len();
+		let end_len = collection.end_len();
+		if collection.is_empty() {
+			return self.fits_then(out, begin_len + end_len, reserved, |out| {
+				collection.begin(out)?;
+				collection.end(out)
+			});
+		}
+
+		// Room for `[…]`.
+		if !out.fits(begin_len + ELLIPSIS.chars().count() + end_len, reserved) {
+			return Ok(false);
+		}
+
+		collection.begin(out)?;
+
+		for (i, item) in printed.iter().enumerate() {
+			let more = i + 1 < printed.len() || elided;
+			let more_len = if more {
+				collection.item_len(i + 1) + ELLIPSIS.chars().count()
+			} else {
+				0
+			};
+
+			collection.item(out, i)?;
+			self.comments(out, indent + 1, collection.is_expanded(), 1)?;
+			let start = out.len();
+
+			if !self.value(
+				out,
+				item,
+				indent + 1,
+				depth + 1,
+				reserved + end_len + more_len,
+			)? {
+				return self.elide_rest(out, &collection, i, start, more);
+			}
+		}
+
+		if elided {
+			collection.item(out, printed.len())?;
+			out.write_str(ELLIPSIS)?;
+			self.skip_items(items.len() - printed.len())
+		}
+
+		collection.end(out)?;
+		Ok(true)
+	}
+
+	/// Skips the fragments of the next `count` elided items.
+	fn skip_items(&mut self, count: usize) {
+		if H::COMMENTS {
+			for _ in 0..count {
+				self.fragment += self.volumes[self.fragment]
+			}
+		}
+	}
+
+	fn object<O: Output>(
+		&mut self,
+		out: &mut O,
+		object: &Object,
+		indent: usize,
+		depth: usize,
+		reserved: usize,
+	) -> Result<bool, fmt::Error> {
+		let size = self.sizes[self.index];
+		self.index += 1;
+
+		let key_width = self.options.object_key_width(&self.sizes, &mut self.index);
+
+		let collection = Collection::object(self.options, size, indent, object.is_empty());
+
+		let begin_len = collection.begin_len();
+		let end_len = collection.end_len();
+		if collection.is_empty() {
+			return self.fits_then(out, begin_len + end_len, reserved, |out| {
+				collection.begin(out)?;
+				collection.end(out)
+			});
+		}
+
+		// Room for `{…}`.
+		if !out.fits(begin_len + ELLIPSIS.chars().count() + end_len, reserved) {
+			return Ok(false);
+		}
+
+		collection.begin(out)?;
+
+		// Fragment index of each entry, in the original order.
+		let mut offsets = Vec::new();
+		let mut end = self.fragment;
+		if H::COMMENTS {
+			offsets.reserve(object.len());
+			for _ in object {
+				offsets.push(end);
+				end += self.volumes[end];
+			}
+		}
+
+		// Entry indexes in printing order, only allocated if keys are sorted.
+		let mut order = Vec::new();
+		if self.options.sort_keys {
+			order.extend(0..object.len());
+			order.sort_by(|&a, &b| object.entries()[a].key.cmp(&object.entries()[b].key));
+		}
+
+		for i in 0..object.len() {
+			let j = order.get(i).copied().unwrap_or(i);
+			let entry = &object.entries()[j];
+			let more = i + 1 < object.len();
+			let more_len = if more {
+				collection.item_len(i + 1) + ELLIPSIS.chars().count()
+			} else {
+				0
+			};
+			let reserved = reserved + end_len + more_len;
+
+			collection.item(out, i)?;
+
+			if H::COMMENTS {
+				self.fragment = offsets[j];
+				self.comments(out, indent + 1, collection.is_expanded(), 3)?;
+				self.fragment += 2;
+			}
+
+			let start = out.len();
+			let complete = out.fits(collection.key_len(&entry.key, key_width), reserved) && {
+				let mark = out.mark();
+				collection.key(out, &entry.key, key_width)?;
+				let value_start = out.len();
+				let complete = self.value(out, &entry.value, indent + 1, depth + 1, reserved)?;
+				if !complete && out.len() == value_start {
+					// Print the key with an elided value.
+					if out.fits(ELLIPSIS.chars().count(), reserved) {
+						out.write_str(ELLIPSIS)?
+					} else {
+						out.rollback(mark)
+					}
+				}
+
+				complete
+			};
+
+			if !complete {
+				return self.elide_rest(out, &collection, i, start, more);
+			}
+		}
+
+		if H::COMMENTS {
+			self.fragment = end;
+		}
+
+		collection.end(out)?;
+		Ok(true)
+	}
+
+	/// Calls `f` if `len` characters fit in the output, keeping `reserved`
+	/// characters for what follows.
+	fn fits_then<O: Output>(
+		&self,
+		out: &mut O,
+		len: usize,
+		reserved: usize,
+		f: impl FnOnce(&mut O) -> fmt::Result,
+	) -> Result<bool, fmt::Error> {
+		let fits = out.fits(len, reserved);
+		if fits {
+			f(out)?
+		}
+
+		Ok(fits)
+	}
+}
+
+/// Writes the given string literal, with an ellipsis before the closing
+/// quote if the rest of the string is elided.
+fn write_string<W: fmt::Write + ?Sized>(w: &mut W, prefix: &str, elided: bool) -> fmt::Result {
+	if elided {
+		let mut literal = Literal(prefix).to_string();
+		literal.insert_str(literal.len() - 1, ELLIPSIS);
+		w.write_str(&literal)
+	} else {
+		write!(w, "{}", Literal(prefix))
+	}
+}
+
+/// Pushes the volume of every fragment of the given value, in the
+/// [`CodeMap`](crate::CodeMap) order, returning the volume of the value.
+fn fragment_volumes(value: &Value, volumes: &mut Vec<usize>) -> usize {
+	let i = volumes.len();
+	volumes.push(1);
+
+	match value {
+		Value::Array(items) => {
+			for item in items {
+				volumes[i] += fragment_volumes(item, volumes)
+			}
+		}
+		Value::Object(object) => {
+			for entry in object {
+				// Entry and key fragments.
+				let j = volumes.len();
+				volumes.extend([2, 1]);
+				volumes[j] += fragment_volumes(&entry.value, volumes);
+				volumes[i] += volumes[j]
+			}
+		}
+		_ => (),
+	}
+
+	volumes[i]
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		json,
+		print::{Limit, Options, Print, Printer},
+		Parse, Value,
+	};
+
+	#[test]
+	fn generic_layout() {
+		let value = json!({ "b": [1, [2, 3], {}], "a": { "c": [] } });
+		for options in [
+			Options::pretty(),
+			Options::compact(),
+			Options::stable(),
+			Options::pretty()
+				.sort_keys(true)
+				.object_align_colons(true)
+				.array_begin(3)
+				.object_begin(0)
+				.array_limit(Some(Limit::Width(10))),
+		] {
+			let mut expected = String::new();
+			Printer::new(options.clone()).print_into(&mut expected, &value);
+
+			let printed = value.print_with(options.clone()).to_string();
+			assert_eq!(printed, expected);
+			assert_eq!(value.printed_len(&options), printed.len())
+		}
+	}
+
+	#[test]
+	fn fragment_volumes() {
+		let (value, code_map) =
+			Value::parse_str(r#"{ "a": [1, { "b": [] }], "c": {}, "d": [[2], 3] }"#).unwrap();
+		let mut volumes = Vec::new();
+		assert_eq!(
+			super::fragment_volumes(&value, &mut volumes),
+			code_map.len()
+		);
+		let expected: Vec<_> = code_map.iter().map(|(_, entry)| entry.volume).collect();
+		assert_eq!(volumes, expected)
+	}
+}
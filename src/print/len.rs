@@ -0,0 +1,96 @@
+//! Printed length computation.
+//!
+//! Walks a value along with its pre-computed sizes (see
+//! [`PrecomputeSize`](super::PrecomputeSize)), adding up the length of what
+//! [`print_array`](super::print_array) and [`print_object`](super::print_object)
+//! would write, without formatting anything.
+use super::{is_identifier, layout::Collection, printed_string_size, Options, Size};
+use crate::Value;
+
+/// Returns the length in bytes of the given string literal.
+///
+/// [`printed_string_size`] counts characters. Escaped characters are all
+/// ASCII, so only the extra bytes of the other characters are added.
+fn string_literal_len(s: &str) -> usize {
+	printed_string_size(s) + s.len() - s.chars().count()
+}
+
+/// Returns the length in bytes of the given object key.
+fn key_len(options: &Options, key: &str) -> usize {
+	if options.unquoted_keys && is_identifier(key) {
+		key.len()
+	} else {
+		string_literal_len(key)
+	}
+}
+
+/// Returns the length in bytes of the given value printed with the
+/// pre-computed `sizes`.
+pub(crate) fn value_len(
+	value: &Value,
+	options: &Options,
+	indent: usize,
+	sizes: &[Size],
+	index: &mut usize,
+) -> usize {
+	match value {
+		Value::Null => 4,
+		Value::Boolean(true) => 4,
+		Value::Boolean(false) => 5,
+		Value::Number(n) => options.number_size(n),
+		Value::String(s) => string_literal_len(s),
+		Value::Array(a) => array_len(a, options, indent, sizes, index),
+		Value::Object(o) => object_len(o, options, indent, sizes, index),
+	}
+}
+
+fn array_len(
+	items: &[Value],
+	options: &Options,
+	indent: usize,
+	sizes: &[Size],
+	index: &mut usize,
+) -> usize {
+	let size = sizes[*index];
+	*index += 1;
+
+	// Delimiters, separators and indentation are ASCII: their length in
+	// characters is their length in bytes.
+	let collection = Collection::array(options, size, indent, items.is_empty());
+	let mut len = collection.begin_len();
+
+	for (i, item) in items.iter().enumerate() {
+		len += collection.item_len(i);
+		len += value_len(item, options, indent + 1, sizes, index)
+	}
+
+	len + collection.end_len()
+}
+
+fn object_len(
+	object: &crate::Object,
+	options: &Options,
+	indent: usize,
+	sizes: &[Size],
+	index: &mut usize,
+) -> usize {
+	let size = sizes[*index];
+	*index += 1;
+
+	let key_width = options.object_key_width(sizes, index);
+
+	let collection = Collection::object(options, size, indent, object.is_empty());
+	let mut len = collection.begin_len();
+
+	for (i, entry) in options.object_entries(object).enumerate() {
+		len += collection.item_len(i);
+
+		// `Collection::key_len` counts the key in characters.
+		len += collection.key_len(&entry.key, key_width) - options.key_size(&entry.key);
+		len += key_len(options, &entry.key);
+
+		len += value_len(&entry.value, options, indent + 1, sizes, index)
+	}
+
+	len + collection.end_len()
+}
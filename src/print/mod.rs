@@ -1,4 +1,26 @@
-use std::fmt;
+use std::{borrow::Cow, fmt};
+
+mod check;
+mod comment;
+mod debug;
+mod dynamic;
+mod layout;
+mod len;
+mod preview;
+mod printer;
+
+pub use check::*;
+pub use comment::*;
+pub use debug::*;
+pub use dynamic::*;
+pub use preview::*;
+pub use printer::*;
+
+#[cfg(feature = "canonicalize")]
+mod canonical;
+
+#[cfg(feature = "canonicalize")]
+pub use canonical::*;
 
 #[cfg(feature = "contextual")]
 mod contextual;
@@ -98,6 +120,9 @@ pub enum Limit {
 	/// (first argument), or if its the representation is more than the
 	/// given number of characters long (second argument).
 	ItemOrWidth(usize, usize),
+
+	/// Never expanded, unless it contains an expanded value.
+	Never,
 }
 
 /// Print options.
@@ -148,6 +173,63 @@ pub struct Options {
 
 	/// Limit after which an array is expanded.
 	pub object_limit: Option<Limit>,
+
+	/// Per-depth expansion limits.
+	///
+	/// The limit at index `n` applies to both arrays and objects at depth `n`
+	/// (the root value being at depth `0`), replacing `array_limit` and
+	/// `object_limit`. Deeper values use `array_limit` and `object_limit`.
+	///
+	/// For instance, the following options keep the first two levels
+	/// expanded and inline everything deeper:
+	///
+	/// ```
+	/// use json_syntax::print::{Limit, Options};
+	///
+	/// let mut options = Options::pretty();
+	/// options.depth_limits = vec![Limit::Always, Limit::Always];
+	/// options.array_limit = Some(Limit::Never);
+	/// options.object_limit = Some(Limit::Never);
+	/// ```
+	pub depth_limits: Vec<Limit>,
+
+	/// Whether or not to align the colons of expanded objects, by padding
+	/// keys with spaces.
+	pub object_align_colons: bool,
+
+	/// Whether or not to print object entries sorted by key.
+	///
+	/// Entries with the same key keep their relative order.
+	pub sort_keys: bool,
+
+	/// Whether or not to omit the quotes around object keys that are
+	/// identifiers (matching `[A-Za-z_][A-Za-z0-9_]*`), JavaScript/JSON5
+	/// style.
+	///
+	/// The output is not valid JSON if any key is unquoted, so this should
+	/// only be used for human-facing output.
+	pub unquoted_keys: bool,
+
+	/// Whether or not to normalize number lexemes.
+	///
+	/// The exponent marker is lowercased, and the exponent is stripped of
+	/// its `+` sign and leading zeros (e.g. `1.5E+007` is printed as
+	/// `1.5e7`). The stored numbers are left untouched.
+	pub normalize_numbers: bool,
+
+	/// Whether or not to print numbers in their shortest form, as defined
+	/// by [RFC 8785](https://www.rfc-editor.org/rfc/rfc8785#name-serialization-of-numbers)
+	/// (e.g. `4.50` is printed as `4.5`).
+	///
+	/// Numbers are re-rendered as `f64` through `ryu-js`, like
+	/// canonicalization does, meaning precision may be lost. The stored
+	/// numbers are left untouched. This takes precedence over
+	/// [`Self::normalize_numbers`].
+	#[cfg(feature = "canonicalize")]
+	pub shortest_floats: bool,
+
+	/// Whether or not to add a newline at the end of the printed value.
+	pub trailing_newline: bool,
 }
 
 impl Options {
@@ -170,6 +252,14 @@ impl Options {
 			object_before_colon: 0,
 			object_after_colon: 1,
 			object_limit: Some(Limit::ItemOrWidth(1, 16)),
+			depth_limits: Vec::new(),
+			object_align_colons: false,
+			sort_keys: false,
+			unquoted_keys: false,
+			normalize_numbers: false,
+			#[cfg(feature = "canonicalize")]
+			shortest_floats: false,
+			trailing_newline: false,
 		}
 	}
 
@@ -194,6 +284,14 @@ impl Options {
 			object_before_colon: 0,
 			object_after_colon: 0,
 			object_limit: None,
+			depth_limits: Vec::new(),
+			object_align_colons: false,
+			sort_keys: false,
+			unquoted_keys: false,
+			normalize_numbers: false,
+			#[cfg(feature = "canonicalize")]
+			shortest_floats: false,
+			trailing_newline: false,
 		}
 	}
 
@@ -218,12 +316,314 @@ impl Options {
 			object_before_colon: 0,
 			object_after_colon: 1,
 			object_limit: None,
+			depth_limits: Vec::new(),
+			object_align_colons: false,
+			sort_keys: false,
+			unquoted_keys: false,
+			normalize_numbers: false,
+			#[cfg(feature = "canonicalize")]
+			shortest_floats: false,
+			trailing_newline: false,
+		}
+	}
+
+	/// Stable print options.
+	///
+	/// Designed for diff-friendly output: object entries are sorted by key,
+	/// non-empty arrays and objects are always expanded (one item per
+	/// line) with a fixed two-spaces indentation, number lexemes are
+	/// normalized and the output ends with a newline. Documents that only
+	/// differ by the order of their object entries (or the style of their
+	/// number exponents) produce byte-identical output.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, Print, print::Options};
+	///
+	/// let value = json!({ "b": [1], "a": {} });
+	/// assert_eq!(
+	///   value.print_with(Options::stable()).to_string(),
+	///   "{\n  \"a\": {},\n  \"b\": [\n    1\n  ]\n}\n"
+	/// )
+	/// ```
+	#[inline(always)]
+	pub fn stable() -> Self {
+		Self {
+			indent: Indent::Spaces(2),
+			array_begin: 0,
+			array_end: 0,
+			array_empty: 0,
+			array_before_comma: 0,
+			array_after_comma: 1,
+			array_limit: Some(Limit::Item(0)),
+			object_begin: 0,
+			object_end: 0,
+			object_empty: 0,
+			object_before_comma: 0,
+			object_after_comma: 1,
+			object_before_colon: 0,
+			object_after_colon: 1,
+			object_limit: Some(Limit::Item(0)),
+			depth_limits: Vec::new(),
+			object_align_colons: false,
+			sort_keys: true,
+			unquoted_keys: false,
+			normalize_numbers: true,
+			#[cfg(feature = "canonicalize")]
+			shortest_floats: false,
+			trailing_newline: true,
 		}
 	}
 }
 
+impl Options {
+	/// Returns the array expansion limit for the given depth.
+	pub(crate) fn array_limit_at(&self, depth: usize) -> Option<Limit> {
+		match self.depth_limits.get(depth) {
+			Some(limit) => Some(*limit),
+			None => self.array_limit,
+		}
+	}
+
+	/// Returns the entries of the given object, in printing order.
+	pub(crate) fn object_entries<'a>(&self, object: &'a crate::Object) -> ObjectEntries<'a> {
+		if self.sort_keys {
+			let mut entries: Vec<_> = object.iter().collect();
+			entries.sort_by(|a, b| a.key.cmp(&b.key));
+			ObjectEntries::Sorted(entries.into_iter())
+		} else {
+			ObjectEntries::Ordered(object.iter())
+		}
+	}
+
+	/// Reads the key width of an object from the pre-computed `sizes`, if
+	/// colons are aligned, or returns `0`.
+	///
+	/// This must be called right after reading the object size.
+	pub(crate) fn object_key_width(&self, sizes: &[Size], index: &mut usize) -> usize {
+		if self.object_align_colons {
+			let width = match sizes[*index] {
+				Size::Width(width) => width,
+				Size::Expanded => 0,
+			};
+			*index += 1;
+			width
+		} else {
+			0
+		}
+	}
+
+	/// Formats the given object key, without quotes if
+	/// [`Self::unquoted_keys`] is set and the key is an identifier.
+	pub(crate) fn fmt_key<W: fmt::Write + ?Sized>(&self, key: &str, f: &mut W) -> fmt::Result {
+		if self.unquoted_keys && is_identifier(key) {
+			f.write_str(key)
+		} else {
+			write!(f, "{}", layout::Literal(key))
+		}
+	}
+
+	/// Returns the printed size of the given object key.
+	///
+	/// See [`Self::fmt_key`].
+	pub(crate) fn key_size(&self, key: &str) -> usize {
+		if self.unquoted_keys && is_identifier(key) {
+			key.len()
+		} else {
+			printed_string_size(key)
+		}
+	}
+
+	/// Returns the printed lexeme of the given number.
+	///
+	/// See [`Self::normalize_numbers`].
+	pub(crate) fn number_lexeme<'a>(&self, number: &'a crate::Number) -> Cow<'a, str> {
+		#[cfg(feature = "canonicalize")]
+		if self.shortest_floats {
+			let mut buffer = ryu_js::Buffer::new();
+			return Cow::Owned(number.canonical_with(&mut buffer).as_str().to_owned());
+		}
+
+		if self.normalize_numbers {
+			normalize_number(number.as_str())
+		} else {
+			Cow::Borrowed(number.as_str())
+		}
+	}
+
+	/// Returns the printed size of the given number.
+	///
+	/// See [`Self::number_lexeme`].
+	pub(crate) fn number_size(&self, number: &crate::Number) -> usize {
+		self.number_lexeme(number).len()
+	}
+
+	/// Writes a newline if [`Self::trailing_newline`] is set.
+	pub(crate) fn fmt_trailing_newline(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.trailing_newline {
+			f.write_str("\n")
+		} else {
+			Ok(())
+		}
+	}
+
+	/// Checks if some array or object may be expanded with these options.
+	fn may_expand(&self) -> bool {
+		[self.array_limit, self.object_limit]
+			.into_iter()
+			.flatten()
+			.chain(self.depth_limits.iter().copied())
+			.any(|limit| limit != Limit::Never)
+	}
+
+	/// Returns the object expansion limit for the given depth.
+	pub(crate) fn object_limit_at(&self, depth: usize) -> Option<Limit> {
+		match self.depth_limits.get(depth) {
+			Some(limit) => Some(*limit),
+			None => self.object_limit,
+		}
+	}
+}
+
+macro_rules! options_builder {
+	($($(#[$meta:meta])* $field:ident: $ty:ty),*) => {
+		/// Builder methods.
+		///
+		/// Those methods allow changing the options without relying on the
+		/// structure fields, which is not possible outside of this crate since
+		/// `Options` is `#[non_exhaustive]`.
+		///
+		/// # Example
+		///
+		/// ```
+		/// use json_syntax::print::{Indent, Options};
+		///
+		/// let options = Options::pretty()
+		///   .indent(Indent::Tabs(1))
+		///   .sort_keys(true)
+		///   .build();
+		/// ```
+		impl Options {
+			$(
+				$(#[$meta])*
+				#[inline(always)]
+				pub fn $field(mut self, value: $ty) -> Self {
+					self.$field = value;
+					self
+				}
+			)*
+
+			/// Ends a builder methods chain.
+			///
+			/// This does nothing and is only provided for readability.
+			#[inline(always)]
+			pub fn build(self) -> Self {
+				self
+			}
+		}
+	};
+}
+
+options_builder! {
+	/// Sets the indentation string.
+	indent: Indent,
+
+	/// Sets the number of spaces added after `[`.
+	array_begin: usize,
+
+	/// Sets the number of spaces added before `]`.
+	array_end: usize,
+
+	/// Sets the number of spaces inside an inlined empty array.
+	array_empty: usize,
+
+	/// Sets the number of spaces before a comma in an array.
+	array_before_comma: usize,
+
+	/// Sets the number of spaces after a comma in an array.
+	array_after_comma: usize,
+
+	/// Sets the limit after which an array is expanded.
+	array_limit: Option<Limit>,
+
+	/// Sets the number of spaces added after `{`.
+	object_begin: usize,
+
+	/// Sets the number of spaces added before `}`.
+	object_end: usize,
+
+	/// Sets the number of spaces inside an inlined empty object.
+	object_empty: usize,
+
+	/// Sets the number of spaces before a comma in an object.
+	object_before_comma: usize,
+
+	/// Sets the number of spaces after a comma in an object.
+	object_after_comma: usize,
+
+	/// Sets the number of spaces before a colon in an object.
+	object_before_colon: usize,
+
+	/// Sets the number of spaces after a colon in an object.
+	object_after_colon: usize,
+
+	/// Sets the limit after which an object is expanded.
+	object_limit: Option<Limit>,
+
+	/// Sets the per-depth expansion limits.
+	depth_limits: Vec<Limit>,
+
+	/// Sets whether or not to align the colons of expanded objects.
+	object_align_colons: bool,
+
+	/// Sets whether or not object entries are sorted by key.
+	sort_keys: bool,
+
+	/// Sets whether or not to omit the quotes around identifier keys.
+	unquoted_keys: bool,
+
+	/// Sets whether or not to normalize number lexemes.
+	normalize_numbers: bool,
+
+	/// Sets whether or not to print numbers in their shortest form.
+	#[cfg(feature = "canonicalize")]
+	shortest_floats: bool,
+
+	/// Sets whether or not to add a newline at the end of the printed value.
+	trailing_newline: bool
+}
+
+/// Object entries, in printing order.
+///
+/// See [`Options::object_entries`].
+pub(crate) enum ObjectEntries<'a> {
+	Ordered(core::slice::Iter<'a, crate::object::Entry>),
+	Sorted(std::vec::IntoIter<&'a crate::object::Entry>),
+}
+
+impl<'a> Iterator for ObjectEntries<'a> {
+	type Item = &'a crate::object::Entry;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		match self {
+			Self::Ordered(e) => e.next(),
+			Self::Sorted(e) => e.next(),
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		match self {
+			Self::Ordered(e) => e.size_hint(),
+			Self::Sorted(e) => e.size_hint(),
+		}
+	}
+}
+
+impl<'a> ExactSizeIterator for ObjectEntries<'a> {}
+
 /// The size of a value.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub enum Size {
 	/// The value (array or object) is expanded on multiple lines.
 	Expanded,
@@ -267,6 +667,86 @@ pub trait Print {
 		Printed(self, options, 0)
 	}
 
+	/// Print the value with the given options, starting at the given
+	/// indentation level.
+	///
+	/// The first line is not indented, but the following ones are indented
+	/// by (at least) `level` indentations. This is useful to print JSON
+	/// inside an already indented host document.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, Print, print::Options};
+	///
+	/// let value = json!({ "a": [1, 2] });
+	/// assert_eq!(
+	///   format!("  - {}", value.print_with_indent(Options::pretty(), 1)),
+	///   "  - {\n    \"a\": [\n      1,\n      2\n    ]\n  }"
+	/// )
+	/// ```
+	#[inline(always)]
+	fn print_with_indent(&self, options: Options, level: usize) -> Printed<'_, Self> {
+		Printed(self, options, level)
+	}
+
+	/// Returns the length in bytes of the value printed with the given
+	/// options, without actually printing it.
+	///
+	/// This can be used to pre-allocate buffers, or enforce payload size
+	/// limits before serializing. For [`Value`](crate::Value), the length is
+	/// computed from the pre-computed layout sizes (see [`PrecomputeSize`]).
+	/// The default implementation counts the formatted bytes.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, Print, print::Options};
+	///
+	/// let value = json!({ "a": [1, 2, "ä"], "b": null });
+	/// let len = value.printed_len(&Options::pretty());
+	/// assert_eq!(len, value.pretty_print().to_string().len());
+	/// ```
+	fn printed_len(&self, options: &Options) -> usize {
+		let mut counter = Counter(0);
+		fmt::Write::write_fmt(
+			&mut counter,
+			format_args!("{}", PrintedRef(self, options, 0)),
+		)
+		.unwrap();
+		counter.0 + usize::from(options.trailing_newline)
+	}
+
+	/// Prints the value with the given options into a byte buffer.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, Print, print::Options};
+	///
+	/// let value = json!({ "a": [1, 2] });
+	/// assert_eq!(value.to_vec_with(&Options::compact()), b"{\"a\":[1,2]}");
+	/// ```
+	fn to_vec_with(&self, options: &Options) -> Vec<u8> {
+		let mut buffer = Vec::new();
+		self.write_to_vec(&mut buffer, options);
+		buffer
+	}
+
+	/// Appends the value printed with the given options to `buffer`,
+	/// without going through an intermediate `String`.
+	fn write_to_vec(&self, buffer: &mut Vec<u8>, options: &Options) {
+		fmt::Write::write_fmt(
+			&mut BytesWriter(buffer),
+			format_args!("{}", PrintedRef(self, options, 0)),
+		)
+		.expect("writing to a `Vec<u8>` cannot fail");
+
+		if options.trailing_newline {
+			buffer.push(b'\n')
+		}
+	}
+
 	fn fmt_with(&self, f: &mut fmt::Formatter, options: &Options, indent: usize) -> fmt::Result;
 }
 
@@ -282,7 +762,7 @@ impl<T: Print, M> Print for locspan::Meta<T, M> {
 	}
 }
 
-impl<'a, T: Print + ?Sized> Print for &'a T {
+impl<T: Print + ?Sized> Print for &T {
 	fn fmt_with(&self, f: &mut fmt::Formatter, options: &Options, indent: usize) -> fmt::Result {
 		(**self).fmt_with(f, options, indent)
 	}
@@ -325,7 +805,7 @@ impl<T: PrintWithSize, M> PrintWithSize for locspan::Meta<T, M> {
 	}
 }
 
-impl<'a, T: PrintWithSize + ?Sized> PrintWithSize for &'a T {
+impl<T: PrintWithSize + ?Sized> PrintWithSize for &T {
 	fn fmt_with_size(
 		&self,
 		f: &mut fmt::Formatter,
@@ -341,10 +821,11 @@ impl<'a, T: PrintWithSize + ?Sized> PrintWithSize for &'a T {
 /// Printed value.
 pub struct Printed<'t, T: ?Sized>(&'t T, Options, usize);
 
-impl<'t, T: Print> fmt::Display for Printed<'t, T> {
+impl<'t, T: Print + ?Sized> fmt::Display for Printed<'t, T> {
 	#[inline(always)]
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		self.0.fmt_with(f, &self.1, self.2)
+		self.0.fmt_with(f, &self.1, self.2)?;
+		self.1.fmt_trailing_newline(f)
 	}
 }
 
@@ -361,8 +842,45 @@ impl Print for bool {
 
 impl Print for crate::NumberBuf {
 	#[inline(always)]
-	fn fmt_with(&self, f: &mut fmt::Formatter, _options: &Options, _indent: usize) -> fmt::Result {
-		fmt::Display::fmt(self, f)
+	fn fmt_with(&self, f: &mut fmt::Formatter, options: &Options, _indent: usize) -> fmt::Result {
+		f.write_str(&options.number_lexeme(self))
+	}
+}
+
+/// Normalizes the given number lexeme.
+///
+/// The exponent marker is lowercased, and the exponent is stripped of its
+/// `+` sign and leading zeros. The mantissa is left untouched.
+///
+/// ```
+/// use json_syntax::print::normalize_number;
+///
+/// assert_eq!(normalize_number("1.5E+007"), "1.5e7");
+/// assert_eq!(normalize_number("-2e-010"), "-2e-10");
+/// assert_eq!(normalize_number("3E00"), "3e0");
+/// assert_eq!(normalize_number("0.10"), "0.10");
+/// ```
+pub fn normalize_number(number: &str) -> Cow<'_, str> {
+	match number.find(['e', 'E']) {
+		Some(i) => {
+			let (mantissa, exponent) = (&number[..i], &number[(i + 1)..]);
+			let (sign, digits) = match exponent.strip_prefix('-') {
+				Some(digits) => ("-", digits),
+				None => ("", exponent.strip_prefix('+').unwrap_or(exponent)),
+			};
+
+			let digits = match digits.trim_start_matches('0') {
+				"" => "0",
+				digits => digits,
+			};
+
+			if number.as_bytes()[i] == b'e' && sign.len() + digits.len() == exponent.len() {
+				Cow::Borrowed(number)
+			} else {
+				Cow::Owned(format!("{mantissa}e{sign}{digits}"))
+			}
+		}
+		None => Cow::Borrowed(number),
 	}
 }
 
@@ -438,6 +956,21 @@ pub fn printed_string_size(s: &str) -> usize {
 	width
 }
 
+/// Checks if the given string is an identifier, matching
+/// `[A-Za-z_][A-Za-z0-9_]*`.
+///
+/// Such object keys can be printed without quotes using
+/// [`Options::unquoted_keys`].
+pub fn is_identifier(s: &str) -> bool {
+	let mut bytes = s.bytes();
+	match bytes.next() {
+		Some(b) if b.is_ascii_alphabetic() || b == b'_' => {
+			bytes.all(|b| b.is_ascii_alphanumeric() || b == b'_')
+		}
+		_ => false,
+	}
+}
+
 impl Print for crate::String {
 	#[inline(always)]
 	fn fmt_with(&self, f: &mut fmt::Formatter, _options: &Options, _indent: usize) -> fmt::Result {
@@ -457,56 +990,19 @@ where
 	I::IntoIter: ExactSizeIterator,
 	I::Item: PrintWithSize,
 {
-	use fmt::Display;
 	let size = sizes[*index];
 	*index += 1;
 
-	f.write_str("[")?;
-
 	let items = items.into_iter();
-	if items.len() == 0 {
-		match size {
-			Size::Expanded => {
-				f.write_str("\n")?;
-				options.indent.by(indent).fmt(f)?;
-			}
-			Size::Width(_) => Spaces(options.array_empty).fmt(f)?,
-		}
-	} else {
-		match size {
-			Size::Expanded => {
-				f.write_str("\n")?;
-
-				for (i, item) in items.enumerate() {
-					if i > 0 {
-						Spaces(options.array_before_comma).fmt(f)?;
-						f.write_str(",\n")?
-					}
+	let collection = layout::Collection::array(options, size, indent, items.len() == 0);
+	collection.begin(f)?;
 
-					options.indent.by(indent + 1).fmt(f)?;
-					item.fmt_with_size(f, options, indent + 1, sizes, index)?
-				}
-
-				f.write_str("\n")?;
-				options.indent.by(indent).fmt(f)?;
-			}
-			Size::Width(_) => {
-				Spaces(options.array_begin).fmt(f)?;
-				for (i, item) in items.enumerate() {
-					if i > 0 {
-						Spaces(options.array_before_comma).fmt(f)?;
-						f.write_str(",")?;
-						Spaces(options.array_after_comma).fmt(f)?
-					}
-
-					item.fmt_with_size(f, options, indent + 1, sizes, index)?
-				}
-				Spaces(options.array_end).fmt(f)?
-			}
-		}
+	for (i, item) in items.enumerate() {
+		collection.item(f, i)?;
+		item.fmt_with_size(f, options, indent + 1, sizes, index)?
 	}
 
-	f.write_str("]")
+	collection.end(f)
 }
 
 impl<T: PrintWithSize> PrintWithSize for Vec<T> {
@@ -535,67 +1031,22 @@ where
 	I::IntoIter: ExactSizeIterator,
 	V: PrintWithSize,
 {
-	use fmt::Display;
 	let size = sizes[*index];
 	*index += 1;
 
-	f.write_str("{")?;
+	let key_width = options.object_key_width(sizes, index);
 
 	let entries = entries.into_iter();
-	if entries.len() == 0 {
-		match size {
-			Size::Expanded => {
-				f.write_str("\n")?;
-				options.indent.by(indent).fmt(f)?;
-			}
-			Size::Width(_) => Spaces(options.object_empty).fmt(f)?,
-		}
-	} else {
-		match size {
-			Size::Expanded => {
-				f.write_str("\n")?;
-
-				for (i, (key, value)) in entries.enumerate() {
-					if i > 0 {
-						Spaces(options.object_before_comma).fmt(f)?;
-						f.write_str(",\n")?
-					}
-
-					options.indent.by(indent + 1).fmt(f)?;
-
-					string_literal(key, f)?;
-					Spaces(options.object_before_colon).fmt(f)?;
-					f.write_str(":")?;
-					Spaces(options.object_after_colon).fmt(f)?;
-
-					value.fmt_with_size(f, options, indent + 1, sizes, index)?
-				}
+	let collection = layout::Collection::object(options, size, indent, entries.len() == 0);
+	collection.begin(f)?;
 
-				f.write_str("\n")?;
-				options.indent.by(indent).fmt(f)?;
-			}
-			Size::Width(_) => {
-				Spaces(options.object_begin).fmt(f)?;
-				for (i, (key, value)) in entries.enumerate() {
-					if i > 0 {
-						Spaces(options.object_before_comma).fmt(f)?;
-						f.write_str(",")?;
-						Spaces(options.object_after_comma).fmt(f)?
-					}
-
-					string_literal(key, f)?;
-					Spaces(options.object_before_colon).fmt(f)?;
-					f.write_str(":")?;
-					Spaces(options.object_after_colon).fmt(f)?;
-
-					value.fmt_with_size(f, options, indent + 1, sizes, index)?
-				}
-				Spaces(options.object_end).fmt(f)?
-			}
-		}
+	for (i, (key, value)) in entries.enumerate() {
+		collection.item(f, i)?;
+		collection.key(f, key, key_width)?;
+		value.fmt_with_size(f, options, indent + 1, sizes, index)?
 	}
 
-	f.write_str("}")
+	collection.end(f)
 }
 
 impl PrintWithSize for crate::Object {
@@ -609,7 +1060,9 @@ impl PrintWithSize for crate::Object {
 		index: &mut usize,
 	) -> fmt::Result {
 		print_object(
-			self.iter().map(|e| (e.key.as_str(), &e.value)),
+			options
+				.object_entries(self)
+				.map(|e| (e.key.as_str(), &e.value)),
 			f,
 			options,
 			indent,
@@ -621,6 +1074,23 @@ impl PrintWithSize for crate::Object {
 
 pub trait PrecomputeSize {
 	fn pre_compute_size(&self, options: &Options, sizes: &mut Vec<Size>) -> Size;
+
+	/// Pre-computes the size of a value located at the given depth.
+	///
+	/// The depth is used to select the expansion limit in
+	/// [`Options::depth_limits`]. By default the depth is ignored, which is
+	/// fine for scalars: collections must override this method and pass
+	/// `depth + 1` to their items (see [`pre_compute_array_size_at_depth`]
+	/// and [`pre_compute_object_size_at_depth`]), otherwise depth limits
+	/// restart at depth `0` below them.
+	fn pre_compute_size_at_depth(
+		&self,
+		options: &Options,
+		_depth: usize,
+		sizes: &mut Vec<Size>,
+	) -> Size {
+		self.pre_compute_size(options, sizes)
+	}
 }
 
 impl PrecomputeSize for bool {
@@ -636,37 +1106,119 @@ impl PrecomputeSize for bool {
 
 impl PrecomputeSize for crate::Value {
 	fn pre_compute_size(&self, options: &Options, sizes: &mut Vec<Size>) -> Size {
+		self.pre_compute_size_at_depth(options, 0, sizes)
+	}
+
+	fn pre_compute_size_at_depth(
+		&self,
+		options: &Options,
+		depth: usize,
+		sizes: &mut Vec<Size>,
+	) -> Size {
 		match self {
 			crate::Value::Null => Size::Width(4),
 			crate::Value::Boolean(b) => b.pre_compute_size(options, sizes),
-			crate::Value::Number(n) => Size::Width(n.as_str().len()),
+			crate::Value::Number(n) => Size::Width(options.number_size(n)),
 			crate::Value::String(s) => Size::Width(printed_string_size(s)),
-			crate::Value::Array(a) => pre_compute_array_size(a, options, sizes),
-			crate::Value::Object(o) => pre_compute_object_size(
-				o.iter().map(|e| (e.key.as_str(), &e.value)),
+			crate::Value::Array(a) => pre_compute_array_size_at_depth(a, options, depth, sizes),
+			crate::Value::Object(o) => pre_compute_object_size_at_depth(
+				options
+					.object_entries(o)
+					.map(|e| (e.key.as_str(), &e.value)),
 				options,
+				depth,
 				sizes,
 			),
 		}
 	}
 }
 
-impl<'a, T: PrecomputeSize + ?Sized> PrecomputeSize for &'a T {
+impl<T: PrecomputeSize + ?Sized> PrecomputeSize for &T {
 	fn pre_compute_size(&self, options: &Options, sizes: &mut Vec<Size>) -> Size {
 		(**self).pre_compute_size(options, sizes)
 	}
+
+	fn pre_compute_size_at_depth(
+		&self,
+		options: &Options,
+		depth: usize,
+		sizes: &mut Vec<Size>,
+	) -> Size {
+		(**self).pre_compute_size_at_depth(options, depth, sizes)
+	}
 }
 
 impl<T: PrecomputeSize> PrecomputeSize for locspan::Stripped<T> {
 	fn pre_compute_size(&self, options: &Options, sizes: &mut Vec<Size>) -> Size {
 		self.0.pre_compute_size(options, sizes)
 	}
+
+	fn pre_compute_size_at_depth(
+		&self,
+		options: &Options,
+		depth: usize,
+		sizes: &mut Vec<Size>,
+	) -> Size {
+		self.0.pre_compute_size_at_depth(options, depth, sizes)
+	}
 }
 
 impl<T: PrecomputeSize, M> PrecomputeSize for locspan::Meta<T, M> {
 	fn pre_compute_size(&self, options: &Options, sizes: &mut Vec<Size>) -> Size {
 		self.value().pre_compute_size(options, sizes)
 	}
+
+	fn pre_compute_size_at_depth(
+		&self,
+		options: &Options,
+		depth: usize,
+		sizes: &mut Vec<Size>,
+	) -> Size {
+		self.value()
+			.pre_compute_size_at_depth(options, depth, sizes)
+	}
+}
+
+impl Limit {
+	/// Computes the size of an array/object with the given number of items
+	/// and width according to this limit.
+	pub fn apply(&self, len: usize, width: usize) -> Size {
+		match self {
+			Self::Never => Size::Width(width),
+			Self::Always => Size::Expanded,
+			Self::Item(i) => {
+				if len > *i {
+					Size::Expanded
+				} else {
+					Size::Width(width)
+				}
+			}
+			Self::ItemOrWidth(i, w) => {
+				if len > *i || width > *w {
+					Size::Expanded
+				} else {
+					Size::Width(width)
+				}
+			}
+			Self::Width(w) => {
+				if width > *w {
+					Size::Expanded
+				} else {
+					Size::Width(width)
+				}
+			}
+		}
+	}
+}
+
+fn apply_limit(limit: Option<Limit>, len: usize, size: Size) -> Size {
+	match size {
+		Size::Expanded => Size::Expanded,
+		Size::Width(width) => match limit {
+			None => Size::Width(width),
+			Some(limit) => limit.apply(len, width),
+		},
+	}
 }
 
 pub fn pre_compute_array_size<I: IntoIterator>(
@@ -674,13 +1226,25 @@ pub fn pre_compute_array_size<I: IntoIterator>(
 	options: &Options,
 	sizes: &mut Vec<Size>,
 ) -> Size
+where
+	I::Item: PrecomputeSize,
+{
+	pre_compute_array_size_at_depth(items, options, 0, sizes)
+}
+
+pub fn pre_compute_array_size_at_depth<I: IntoIterator>(
+	items: I,
+	options: &Options,
+	depth: usize,
+	sizes: &mut Vec<Size>,
+) -> Size
 where
 	I::Item: PrecomputeSize,
 {
 	let index = sizes.len();
 	sizes.push(Size::Width(0));
 
-	let mut size = Size::Width(2 + options.object_begin + options.object_end);
+	let mut size = Size::Width(2 + options.array_begin + options.array_end);
 
 	let mut len = 0;
 	for (i, item) in items.into_iter().enumerate() {
@@ -690,39 +1254,11 @@ where
 			));
 		}
 
-		size.add(item.pre_compute_size(options, sizes));
+		size.add(item.pre_compute_size_at_depth(options, depth + 1, sizes));
 		len += 1
 	}
 
-	let size = match size {
-		Size::Expanded => Size::Expanded,
-		Size::Width(width) => match options.array_limit {
-			None => Size::Width(width),
-			Some(Limit::Always) => Size::Expanded,
-			Some(Limit::Item(i)) => {
-				if len > i {
-					Size::Expanded
-				} else {
-					Size::Width(width)
-				}
-			}
-			Some(Limit::ItemOrWidth(i, w)) => {
-				if len > i || width > w {
-					Size::Expanded
-				} else {
-					Size::Width(width)
-				}
-			}
-			Some(Limit::Width(w)) => {
-				if width > w {
-					Size::Expanded
-				} else {
-					Size::Width(width)
-				}
-			}
-		},
-	};
-
+	let size = apply_limit(options.array_limit_at(depth), len, size);
 	sizes[index] = size;
 	size
 }
@@ -732,15 +1268,32 @@ pub fn pre_compute_object_size<'a, V, I: IntoIterator<Item = (&'a str, V)>>(
 	options: &Options,
 	sizes: &mut Vec<Size>,
 ) -> Size
+where
+	V: PrecomputeSize,
+{
+	pre_compute_object_size_at_depth(entries, options, 0, sizes)
+}
+
+pub fn pre_compute_object_size_at_depth<'a, V, I: IntoIterator<Item = (&'a str, V)>>(
+	entries: I,
+	options: &Options,
+	depth: usize,
+	sizes: &mut Vec<Size>,
+) -> Size
 where
 	V: PrecomputeSize,
 {
 	let index = sizes.len();
 	sizes.push(Size::Width(0));
 
+	if options.object_align_colons {
+		sizes.push(Size::Width(0));
+	}
+
 	let mut size = Size::Width(2 + options.object_begin + options.object_end);
 
 	let mut len = 0;
+	let mut key_width = 0;
 	for (i, (key, value)) in entries.into_iter().enumerate() {
 		if i > 0 {
 			size.add(Size::Width(
@@ -748,47 +1301,33 @@ where
 			));
 		}
 
+		let key_size = options.key_size(key);
+		key_width = key_width.max(key_size);
 		size.add(Size::Width(
-			printed_string_size(key) + 1 + options.object_before_colon + options.object_after_colon,
+			key_size + 1 + options.object_before_colon + options.object_after_colon,
 		));
-		size.add(value.pre_compute_size(options, sizes));
+		size.add(value.pre_compute_size_at_depth(options, depth + 1, sizes));
 		len += 1;
 	}
 
-	let size = match size {
-		Size::Expanded => Size::Expanded,
-		Size::Width(width) => match options.object_limit {
-			None => Size::Width(width),
-			Some(Limit::Always) => Size::Expanded,
-			Some(Limit::Item(i)) => {
-				if len > i {
-					Size::Expanded
-				} else {
-					Size::Width(width)
-				}
-			}
-			Some(Limit::ItemOrWidth(i, w)) => {
-				if len > i || width > w {
-					Size::Expanded
-				} else {
-					Size::Width(width)
-				}
-			}
-			Some(Limit::Width(w)) => {
-				if width > w {
-					Size::Expanded
-				} else {
-					Size::Width(width)
-				}
-			}
-		},
-	};
-
+	let size = apply_limit(options.object_limit_at(depth), len, size);
 	sizes[index] = size;
+
+	if options.object_align_colons {
+		sizes[index + 1] = Size::Width(key_width);
+	}
+
 	size
 }
 
 impl Print for crate::Value {
+	fn printed_len(&self, options: &Options) -> usize {
+		let mut sizes = Vec::with_capacity(self.count(|_, v| v.is_array() || v.is_object()));
+		self.pre_compute_size(options, &mut sizes);
+		let mut index = 0;
+		len::value_len(self, options, 0, &sizes, &mut index) + usize::from(options.trailing_newline)
+	}
+
 	fn fmt_with(&self, f: &mut fmt::Formatter, options: &Options, indent: usize) -> fmt::Result {
 		match self {
 			Self::Null => f.write_str("null"),
@@ -832,3 +1371,149 @@ impl PrintWithSize for crate::Value {
 		}
 	}
 }
+
+/// Sequence printing format, used by [`print_seq`].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum SeqFormat {
+	/// Newline-delimited JSON (NDJSON).
+	///
+	/// Each value is followed by a newline. Values should be printed on a
+	/// single line for the output to be valid NDJSON, meaning the print
+	/// options should not expand arrays or objects (e.g.
+	/// [`Options::compact`] or [`Options::inline`]).
+	Lines,
+
+	/// JSON array.
+	///
+	/// The array is expanded if the options define an array expansion limit
+	/// for the root value (the first of [`Options::depth_limits`], or
+	/// [`Options::array_limit`]) other than [`Limit::Never`]. Any other
+	/// limit expands the array, since the number of items is not known in
+	/// advance. Otherwise, the array is expanded if any item is, in which
+	/// case the item references are collected first.
+	Array,
+}
+
+/// Writer counting the number of written bytes.
+struct Counter(usize);
+
+impl fmt::Write for Counter {
+	#[inline(always)]
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		self.0 += s.len();
+		Ok(())
+	}
+}
+
+/// Writer appending to a byte buffer.
+struct BytesWriter<'a>(&'a mut Vec<u8>);
+
+impl<'a> fmt::Write for BytesWriter<'a> {
+	#[inline(always)]
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		self.0.extend_from_slice(s.as_bytes());
+		Ok(())
+	}
+}
+
+/// Value printed with borrowed options.
+struct PrintedRef<'a, T: ?Sized>(&'a T, &'a Options, usize);
+
+impl<'a, T: Print + ?Sized> fmt::Display for PrintedRef<'a, T> {
+	#[inline(always)]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt_with(f, self.1, self.2)
+	}
+}
+
+/// Prints a sequence of values into `writer`, one by one, without collecting
+/// them into an [`Array`](crate::Array) first.
+///
+/// # Example
+///
+/// ```
+/// use json_syntax::{json, print::{self, Options, SeqFormat}};
+///
+/// let rows = [json!({ "id": 1 }), json!({ "id": 2 })];
+///
+/// let mut output = String::new();
+/// print::print_seq(&rows, &mut output, &Options::compact(), SeqFormat::Lines).unwrap();
+/// assert_eq!(output, "{\"id\":1}\n{\"id\":2}\n");
+///
+/// let mut output = String::new();
+/// print::print_seq(&rows, &mut output, &Options::compact(), SeqFormat::Array).unwrap();
+/// assert_eq!(output, "[{\"id\":1},{\"id\":2}]");
+/// ```
+pub fn print_seq<'a, W: fmt::Write>(
+	items: impl IntoIterator<Item = &'a crate::Value>,
+	writer: &mut W,
+	options: &Options,
+	format: SeqFormat,
+) -> fmt::Result {
+	match format {
+		SeqFormat::Lines => {
+			for item in items {
+				writeln!(writer, "{}", PrintedRef(item, options, 0))?
+			}
+
+			Ok(())
+		}
+		SeqFormat::Array => {
+			// Items are at depth 1.
+			let item_options = match options.depth_limits.split_first() {
+				Some((_, depth_limits)) => Cow::Owned(Options {
+					depth_limits: depth_limits.to_vec(),
+					..options.clone()
+				}),
+				None => Cow::Borrowed(options),
+			};
+
+			match options.array_limit_at(0) {
+				Some(limit) if limit != Limit::Never => {
+					print_seq_array(items, writer, options, &item_options, Size::Expanded)
+				}
+				_ if !item_options.may_expand() => {
+					print_seq_array(items, writer, options, &item_options, Size::Width(0))
+				}
+				_ => {
+					// The array is expanded if any item is, which is only
+					// known once all the items are seen.
+					let items: Vec<_> = items.into_iter().collect();
+					let expanded = items.iter().any(|item| {
+						matches!(
+							item.pre_compute_size(&item_options, &mut Vec::new()),
+							Size::Expanded
+						)
+					});
+					let size = if expanded {
+						Size::Expanded
+					} else {
+						Size::Width(0)
+					};
+
+					print_seq_array(items, writer, options, &item_options, size)
+				}
+			}
+		}
+	}
+}
+
+/// Prints the given items as an array of the given size.
+fn print_seq_array<'a, W: fmt::Write>(
+	items: impl IntoIterator<Item = &'a crate::Value>,
+	writer: &mut W,
+	options: &Options,
+	item_options: &Options,
+	size: Size,
+) -> fmt::Result {
+	let mut items = items.into_iter().peekable();
+	let collection = layout::Collection::array(options, size, 0, items.peek().is_none());
+	collection.begin(writer)?;
+
+	for (i, item) in items.enumerate() {
+		collection.item(writer, i)?;
+		write!(writer, "{}", PrintedRef(item, item_options, 1))?
+	}
+
+	collection.end(writer)
+}
@@ -0,0 +1,202 @@
+use std::fmt;
+
+use super::{
+	layout::{Bounded, Driver, Hooks},
+	Options,
+};
+use crate::Value;
+
+/// Preview limits.
+///
+/// Limits the amount of content printed by [`Value::preview_with`].
+/// Elided content is replaced by `…` markers. By default, nothing is elided.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PreviewLimits {
+	/// Maximum depth of printed arrays and objects.
+	///
+	/// The content of non-empty arrays and objects at this depth (the root
+	/// value being at depth `0`) is elided: they are printed as `[…]` and
+	/// `{…}`.
+	pub max_depth: Option<usize>,
+
+	/// Maximum number of printed items per array.
+	pub max_array_items: Option<usize>,
+
+	/// Maximum number of printed characters per string value.
+	///
+	/// Object keys are never truncated.
+	pub max_string_len: Option<usize>,
+
+	/// Maximum number of printed characters.
+	///
+	/// Content is printed in order until this limit is reached, the rest
+	/// being elided. Truncated strings and arrays/objects are closed, so
+	/// that the structure of the printed prefix remains readable.
+	pub max_len: Option<usize>,
+}
+
+impl PreviewLimits {
+	/// Sets the maximum depth of printed arrays and objects.
+	pub fn max_depth(mut self, value: usize) -> Self {
+		self.max_depth = Some(value);
+		self
+	}
+
+	/// Sets the maximum number of printed items per array.
+	pub fn max_array_items(mut self, value: usize) -> Self {
+		self.max_array_items = Some(value);
+		self
+	}
+
+	/// Sets the maximum number of printed characters per string value.
+	pub fn max_string_len(mut self, value: usize) -> Self {
+		self.max_string_len = Some(value);
+		self
+	}
+
+	/// Sets the maximum number of printed characters.
+	pub fn max_len(mut self, value: usize) -> Self {
+		self.max_len = Some(value);
+		self
+	}
+}
+
+impl Value {
+	/// Prints a preview of the value with the given options, eliding
+	/// content beyond the given limits.
+	///
+	/// This is useful to log large documents without flooding the logs.
+	/// Elided content is replaced with `…` markers, meaning the output is
+	/// generally not valid JSON.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, print::{Options, PreviewLimits}};
+	///
+	/// let value = json!({
+	///   "name": "a very long name",
+	///   "items": [1, 2, 3, 4, 5],
+	///   "nested": { "a": { "b": null } }
+	/// });
+	///
+	/// let limits = PreviewLimits::default()
+	///   .max_depth(2)
+	///   .max_array_items(2)
+	///   .max_string_len(6);
+	///
+	/// assert_eq!(
+	///   value.preview_with(Options::inline(), limits).to_string(),
+	///   r#"{ "name": "a very…", "items": [ 1, 2, … ], "nested": { "a": {…} } }"#
+	/// )
+	/// ```
+	pub fn preview_with(&self, options: Options, limits: PreviewLimits) -> Preview<'_> {
+		Preview {
+			value: self,
+			options,
+			limits,
+		}
+	}
+}
+
+/// Value preview.
+///
+/// See [`Value::preview_with`].
+pub struct Preview<'a> {
+	value: &'a Value,
+	options: Options,
+	limits: PreviewLimits,
+}
+
+impl<'a> fmt::Display for Preview<'a> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut driver = Driver::new(&self.options, &self.limits, self.value);
+		match self.limits.max_len {
+			Some(max_len) => {
+				let mut output = Bounded::new(max_len);
+				driver.print(&mut output, self.value, 0)?;
+				f.write_str(output.as_str())?
+			}
+			None => driver.print(f, self.value, 0)?,
+		}
+
+		self.options.fmt_trailing_newline(f)
+	}
+}
+
+impl Hooks for PreviewLimits {
+	fn elides_content(&self, depth: usize) -> bool {
+		self.max_depth.map(|max| depth >= max).unwrap_or(false)
+	}
+
+	fn array_items<'v>(&self, items: &'v [Value]) -> (&'v [Value], bool) {
+		match self.max_array_items {
+			Some(max) if items.len() > max => (&items[..max], true),
+			_ => (items, false),
+		}
+	}
+
+	fn truncate<'s>(&self, s: &'s str) -> (&'s str, bool) {
+		match self.max_string_len {
+			Some(max) => match s.char_indices().nth(max) {
+				Some((i, _)) => (&s[..i], true),
+				None => (s, false),
+			},
+			None => (s, false),
+		}
+	}
+}
+
+impl Value {
+	/// Prints a single-line preview of the value, at most `max_len`
+	/// characters long.
+	///
+	/// Content that does not fit is elided and replaced with `…` markers,
+	/// meaning the output is generally not valid JSON. This is intended for
+	/// log lines and error messages.
+	///
+	/// This is a shorthand for [`Value::preview_with`] with inline options
+	/// and the [`PreviewLimits::max_len`] limit.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::json;
+	///
+	/// let value = json!({ "a": [1, 2, 3, 4, 5], "b": "some text" });
+	///
+	/// assert_eq!(value.preview(20).to_string(), r#"{"a": [1, 2, …], …}"#);
+	/// assert_eq!(value.preview(30).to_string(), r#"{"a": [1, 2, 3, 4, 5], "b": …}"#);
+	/// assert_eq!(value.preview(40).to_string(), r#"{"a": [1, 2, 3, 4, 5], "b": "some text"}"#);
+	/// ```
+	pub fn preview(&self, max_len: usize) -> Preview<'_> {
+		let options = Options::inline()
+			.array_begin(0)
+			.array_end(0)
+			.object_begin(0)
+			.object_end(0);
+
+		self.preview_with(options, PreviewLimits::default().max_len(max_len))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::json;
+
+	#[test]
+	fn preview_fits() {
+		let value = json!({ "a\nb": [[1, 2], { "c": "dé\u{1}f" }], "d": null });
+		let full = value.preview(usize::MAX).to_string();
+		assert_eq!(
+			full,
+			"{\"a\\nb\": [[1, 2], {\"c\": \"dé\\u0001f\"}], \"d\": null}"
+		);
+
+		for max_len in 0..full.chars().count() {
+			let preview = value.preview(max_len).to_string();
+			assert!(preview.chars().count() <= max_len, "{preview}");
+			assert!(preview.contains('…') || preview.is_empty(), "{preview}")
+		}
+	}
+}
@@ -0,0 +1,85 @@
+use std::fmt;
+
+use super::{Options, PrecomputeSize, PrintWithSize, Size};
+use crate::Value;
+
+/// Reusable printer.
+///
+/// Printing a value requires pre-computing the size of its arrays and
+/// objects into a scratch buffer, which is allocated on every
+/// [`Print::fmt_with`](super::Print::fmt_with) call. A `Printer` keeps this
+/// buffer around between prints, eliminating per-print allocations in hot
+/// serialization loops.
+///
+/// # Example
+///
+/// ```
+/// use json_syntax::{json, print::{Options, Printer}};
+///
+/// let mut printer = Printer::new(Options::compact());
+/// let mut output = String::new();
+///
+/// for i in 0..3 {
+///   output.clear();
+///   printer.print_into(&mut output, &json!({ "id": i }));
+///   assert_eq!(output, format!("{{\"id\":{i}}}"));
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Printer {
+	options: Options,
+	sizes: Vec<Size>,
+}
+
+impl Printer {
+	/// Creates a new printer with the given options.
+	pub fn new(options: Options) -> Self {
+		Self {
+			options,
+			sizes: Vec::new(),
+		}
+	}
+
+	/// Returns the print options.
+	pub fn options(&self) -> &Options {
+		&self.options
+	}
+
+	/// Returns a mutable reference to the print options.
+	pub fn options_mut(&mut self) -> &mut Options {
+		&mut self.options
+	}
+
+	/// Appends the given value to `output`.
+	pub fn print_into(&mut self, output: &mut String, value: &Value) {
+		self.write(output, value)
+			.expect("writing to a `String` cannot fail")
+	}
+
+	/// Writes the given value into `writer`.
+	pub fn write<W: fmt::Write>(&mut self, writer: &mut W, value: &Value) -> fmt::Result {
+		self.sizes.clear();
+		value.pre_compute_size(&self.options, &mut self.sizes);
+		write!(
+			writer,
+			"{}",
+			PrintedWithSizes(value, &self.options, &self.sizes)
+		)?;
+
+		if self.options.trailing_newline {
+			writer.write_char('\n')?
+		}
+
+		Ok(())
+	}
+}
+
+/// Value printed with pre-computed sizes.
+struct PrintedWithSizes<'a>(&'a Value, &'a Options, &'a [Size]);
+
+impl<'a> fmt::Display for PrintedWithSizes<'a> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut index = 0;
+		self.0.fmt_with_size(f, self.1, 0, self.2, &mut index)
+	}
+}
@@ -0,0 +1,294 @@
+//! JSON Schema inference.
+//!
+//! This module can infer a [JSON Schema](https://json-schema.org/) (draft
+//! 2020-12) skeleton from sample documents. The inferred schema is a starting
+//! point to write an actual schema, for instance to validate the output of
+//! undocumented APIs.
+use std::collections::BTreeMap;
+
+use crate::{object::Key, Object, Value};
+
+/// JSON Schema draft 2020-12 meta-schema URI.
+pub const DRAFT_2020_12: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Schema inference options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InferOptions {
+	/// Maximum number of distinct string values for which an `enum` is
+	/// generated.
+	///
+	/// Set to `0` to never generate enums.
+	pub max_enum_len: usize,
+}
+
+impl Default for InferOptions {
+	fn default() -> Self {
+		Self { max_enum_len: 8 }
+	}
+}
+
+/// Inferred JSON Schema.
+///
+/// # Example
+///
+/// ```
+/// use json_syntax::{json, schema::Schema};
+///
+/// let a = json!({ "id": 1, "status": "active", "tags": ["x"] });
+/// let b = json!({ "id": 2, "status": "active", "score": 1.5 });
+///
+/// let schema = Schema::infer(&[&a, &b]);
+///
+/// assert_eq!(schema.to_value(), json!({
+///   "$schema": "https://json-schema.org/draft/2020-12/schema",
+///   "type": "object",
+///   "properties": {
+///     "id": { "type": "integer" },
+///     "status": { "type": "string", "enum": ["active"] },
+///     "tags": { "type": "array", "items": { "type": "string", "enum": ["x"] } },
+///     "score": { "type": "number" }
+///   },
+///   "required": ["id", "status"]
+/// }));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Schema {
+	/// Number of observed values.
+	count: usize,
+
+	null: bool,
+	boolean: bool,
+	integer: bool,
+	number: bool,
+
+	/// Distinct observed strings, or `None` if no string was observed.
+	///
+	/// Once the number of distinct strings exceeds the maximum enum length,
+	/// the list is cleared and `enum_overflow` is set.
+	strings: Option<Vec<crate::String>>,
+	enum_overflow: bool,
+
+	/// Schema of array items, if an array was observed.
+	items: Option<Box<Schema>>,
+
+	/// Number of observed objects.
+	objects: usize,
+
+	/// Object properties, in order of first occurrence.
+	properties: Vec<Property>,
+
+	/// Index of each property in `properties`.
+	property_indexes: BTreeMap<Key, usize>,
+}
+
+/// Inferred object property.
+#[derive(Debug, Clone)]
+struct Property {
+	key: Key,
+
+	/// Number of observed objects containing the property.
+	///
+	/// Objects with duplicate keys are only counted once.
+	objects: usize,
+
+	/// Schema of the property values.
+	schema: Schema,
+}
+
+impl Schema {
+	/// Infers a schema from the given samples, with the default options.
+	pub fn infer(samples: &[&Value]) -> Self {
+		Self::infer_with(samples, InferOptions::default())
+	}
+
+	/// Infers a schema from the given samples.
+	pub fn infer_with(samples: &[&Value], options: InferOptions) -> Self {
+		let mut schema = Self::default();
+
+		for sample in samples {
+			schema.add(sample, &options)
+		}
+
+		schema
+	}
+
+	/// Refines the schema with a new sample.
+	pub fn add(&mut self, value: &Value, options: &InferOptions) {
+		self.count += 1;
+
+		match value {
+			Value::Null => self.null = true,
+			Value::Boolean(_) => self.boolean = true,
+			Value::Number(n) => {
+				if n.as_str().contains(['.', 'e', 'E']) {
+					self.number = true
+				} else {
+					self.integer = true
+				}
+			}
+			Value::String(s) => {
+				let strings = self.strings.get_or_insert_with(Vec::new);
+				if !self.enum_overflow && !strings.contains(s) {
+					if strings.len() < options.max_enum_len {
+						strings.push(s.clone())
+					} else {
+						strings.clear();
+						self.enum_overflow = true
+					}
+				}
+			}
+			Value::Array(items) => {
+				let schema = self.items.get_or_insert_with(Default::default);
+				for item in items {
+					schema.add(item, options)
+				}
+			}
+			Value::Object(object) => {
+				self.objects += 1;
+
+				for entry in object {
+					let i = match self.property_indexes.get(&entry.key) {
+						Some(&i) => i,
+						None => {
+							let i = self.properties.len();
+							self.properties.push(Property {
+								key: entry.key.clone(),
+								objects: 0,
+								schema: Self::default(),
+							});
+							self.property_indexes.insert(entry.key.clone(), i);
+							i
+						}
+					};
+
+					let property = &mut self.properties[i];
+					if property.objects < self.objects {
+						property.objects += 1
+					}
+
+					property.schema.add(&entry.value, options)
+				}
+			}
+		}
+	}
+
+	/// Returns the schema as a JSON Schema document, with the draft 2020-12
+	/// `$schema` keyword.
+	pub fn to_value(&self) -> Value {
+		let mut object = self.to_object();
+		object.push_front("$schema".into(), DRAFT_2020_12.into());
+		Value::Object(object)
+	}
+
+	/// Returns the schema as a JSON object, without the `$schema` keyword.
+	fn to_object(&self) -> Object {
+		let mut result = Object::new();
+
+		let mut types: Vec<Value> = Vec::new();
+		if self.null {
+			types.push("null".into())
+		}
+		if self.boolean {
+			types.push("boolean".into())
+		}
+		if self.number {
+			types.push("number".into())
+		} else if self.integer {
+			types.push("integer".into())
+		}
+		if self.strings.is_some() {
+			types.push("string".into())
+		}
+		if self.items.is_some() {
+			types.push("array".into())
+		}
+		if self.objects > 0 {
+			types.push("object".into())
+		}
+
+		let string_only = types.len() == 1 && self.strings.is_some();
+		match types.len() {
+			0 => (),
+			1 => {
+				result.push("type".into(), types.pop().unwrap());
+			}
+			_ => {
+				result.push("type".into(), Value::Array(types));
+			}
+		}
+
+		// Enums are only generated for string-only values.
+		if let Some(strings) = self.strings.as_ref().filter(|_| string_only) {
+			if !self.enum_overflow && !strings.is_empty() {
+				result.push(
+					"enum".into(),
+					Value::Array(strings.iter().cloned().map(Value::String).collect()),
+				);
+			}
+		}
+
+		if let Some(items) = &self.items {
+			result.push("items".into(), Value::Object(items.to_object()));
+		}
+
+		if self.objects > 0 {
+			let properties: Object = self
+				.properties
+				.iter()
+				.map(|p| (p.key.clone(), Value::Object(p.schema.to_object())))
+				.collect();
+			result.push("properties".into(), Value::Object(properties));
+
+			let required: Vec<Value> = self
+				.properties
+				.iter()
+				.filter(|p| p.objects == self.objects)
+				.map(|p| Value::String((*p.key).into()))
+				.collect();
+			if !required.is_empty() {
+				result.push("required".into(), Value::Array(required));
+			}
+		}
+
+		result
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::json;
+
+	#[test]
+	fn infer_mixed() {
+		let samples = [json!(null), json!(1), json!("a"), json!([true, 2.5])];
+		let schema = Schema::infer_with(
+			&samples.iter().collect::<Vec<_>>(),
+			InferOptions { max_enum_len: 0 },
+		);
+
+		assert_eq!(
+			schema.to_object(),
+			json!({
+				"type": ["null", "integer", "string", "array"],
+				"items": { "type": ["boolean", "number"] }
+			})
+			.into_object()
+			.unwrap()
+		)
+	}
+
+	#[test]
+	fn infer_duplicate_keys() {
+		let samples = [json!({ "a": 1, "a": 2 }), json!({})];
+		let schema = Schema::infer(&samples.iter().collect::<Vec<_>>());
+		assert_eq!(schema.to_object().get("required").next(), None);
+
+		let samples = [json!({ "a": 1, "a": 2 }), json!({ "a": 3 })];
+		let schema = Schema::infer(&samples.iter().collect::<Vec<_>>());
+		assert_eq!(
+			schema.to_object().get("required").next(),
+			Some(&json!(["a"]))
+		)
+	}
+}
@@ -24,7 +24,7 @@ impl Value {
 	}
 
 	#[cold]
-	fn unexpected(&self) -> Unexpected {
+	fn unexpected(&self) -> Unexpected<'_> {
 		match self {
 			Self::Null => Unexpected::Unit,
 			Self::Boolean(b) => Unexpected::Bool(*b),
@@ -0,0 +1,265 @@
+//! Structurally shared JSON values.
+use std::sync::Arc;
+
+use crate::{
+	object::{Entry, Key},
+	Kind, NumberBuf, Object, Value,
+};
+
+/// Structurally shared object entry.
+pub type SharedEntry = Entry<Key, SharedValue>;
+
+/// JSON value with structurally shared subtrees.
+///
+/// Arrays and objects are stored behind an [`Arc`], so cloning a
+/// `SharedValue` is cheap (it never copies nested collections), and the
+/// clones can be sent to other threads. Mutation is copy-on-write: only the
+/// collections on the path to the modified value are copied, and only if
+/// they are shared.
+///
+/// # Example
+///
+/// ```
+/// use json_syntax::{json, SharedValue, Value};
+///
+/// let shared = SharedValue::from(json!({ "a": [1, 2], "b": { "c": true } }));
+///
+/// let mut copy = shared.clone();
+/// *copy.get_mut("b").unwrap().get_mut("c").unwrap() = SharedValue::Boolean(false);
+///
+/// // The unmodified subtree is still shared.
+/// assert!(copy.get("a").unwrap().ptr_eq(shared.get("a").unwrap()));
+///
+/// assert_eq!(Value::from(shared), json!({ "a": [1, 2], "b": { "c": true } }));
+/// assert_eq!(Value::from(copy), json!({ "a": [1, 2], "b": { "c": false } }));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SharedValue {
+	/// `null`.
+	Null,
+
+	/// Boolean `true` or `false`.
+	Boolean(bool),
+
+	/// Number.
+	Number(NumberBuf),
+
+	/// String.
+	String(crate::String),
+
+	/// Shared array.
+	Array(Arc<Vec<SharedValue>>),
+
+	/// Shared object.
+	///
+	/// Entries are stored in order, and may contain duplicate keys, like
+	/// in [`Object`].
+	Object(Arc<Vec<SharedEntry>>),
+}
+
+impl SharedValue {
+	#[inline]
+	pub fn kind(&self) -> Kind {
+		match self {
+			Self::Null => Kind::Null,
+			Self::Boolean(_) => Kind::Boolean,
+			Self::Number(_) => Kind::Number,
+			Self::String(_) => Kind::String,
+			Self::Array(_) => Kind::Array,
+			Self::Object(_) => Kind::Object,
+		}
+	}
+
+	#[inline]
+	pub fn is_null(&self) -> bool {
+		matches!(self, Self::Null)
+	}
+
+	#[inline]
+	pub fn as_array(&self) -> Option<&[SharedValue]> {
+		match self {
+			Self::Array(items) => Some(items),
+			_ => None,
+		}
+	}
+
+	#[inline]
+	pub fn as_object(&self) -> Option<&[SharedEntry]> {
+		match self {
+			Self::Object(entries) => Some(entries),
+			_ => None,
+		}
+	}
+
+	/// Returns a mutable reference to the array items, if this is an array.
+	///
+	/// The array is copied first if it is shared.
+	pub fn as_array_mut(&mut self) -> Option<&mut Vec<SharedValue>> {
+		match self {
+			Self::Array(items) => Some(Arc::make_mut(items)),
+			_ => None,
+		}
+	}
+
+	/// Returns a mutable reference to the object entries, if this is an
+	/// object.
+	///
+	/// The object is copied first if it is shared.
+	pub fn as_object_mut(&mut self) -> Option<&mut Vec<SharedEntry>> {
+		match self {
+			Self::Object(entries) => Some(Arc::make_mut(entries)),
+			_ => None,
+		}
+	}
+
+	/// Returns the value of the first entry with the given key, if this is an
+	/// object.
+	pub fn get(&self, key: &str) -> Option<&SharedValue> {
+		self.as_object()?
+			.iter()
+			.find(|entry| *entry.key == *key)
+			.map(|entry| &entry.value)
+	}
+
+	/// Returns a mutable reference to the value of the first entry with the
+	/// given key, if this is an object.
+	///
+	/// The object is copied first if it is shared.
+	pub fn get_mut(&mut self, key: &str) -> Option<&mut SharedValue> {
+		// Do not copy the object if the key is missing.
+		self.get(key)?;
+
+		self.as_object_mut()?
+			.iter_mut()
+			.find(|entry| *entry.key == *key)
+			.map(|entry| &mut entry.value)
+	}
+
+	/// Returns the array item at the given index, if this is an array.
+	pub fn get_index(&self, index: usize) -> Option<&SharedValue> {
+		self.as_array()?.get(index)
+	}
+
+	/// Returns a mutable reference to the array item at the given index, if
+	/// this is an array.
+	///
+	/// The array is copied first if it is shared.
+	pub fn get_index_mut(&mut self, index: usize) -> Option<&mut SharedValue> {
+		// Do not copy the array if the index is out of bounds.
+		self.get_index(index)?;
+
+		self.as_array_mut()?.get_mut(index)
+	}
+
+	/// Checks if both values share the same array or object.
+	///
+	/// Always returns `false` if the values are not arrays or objects.
+	pub fn ptr_eq(&self, other: &Self) -> bool {
+		match (self, other) {
+			(Self::Array(a), Self::Array(b)) => Arc::ptr_eq(a, b),
+			(Self::Object(a), Self::Object(b)) => Arc::ptr_eq(a, b),
+			_ => false,
+		}
+	}
+}
+
+impl From<Value> for SharedValue {
+	fn from(value: Value) -> Self {
+		match value {
+			Value::Null => Self::Null,
+			Value::Boolean(b) => Self::Boolean(b),
+			Value::Number(n) => Self::Number(n),
+			Value::String(s) => Self::String(s),
+			Value::Array(items) => {
+				Self::Array(Arc::new(items.into_iter().map(Into::into).collect()))
+			}
+			Value::Object(object) => Self::Object(Arc::new(
+				object
+					.into_iter()
+					.map(|entry| Entry::new(entry.key, entry.value.into()))
+					.collect(),
+			)),
+		}
+	}
+}
+
+impl<'a> From<&'a Value> for SharedValue {
+	fn from(value: &'a Value) -> Self {
+		value.clone().into()
+	}
+}
+
+impl From<SharedValue> for Value {
+	/// Converts a shared value into a [`Value`].
+	///
+	/// Arrays and objects that are not shared are moved, the others are
+	/// cloned.
+	fn from(value: SharedValue) -> Self {
+		match value {
+			SharedValue::Null => Self::Null,
+			SharedValue::Boolean(b) => Self::Boolean(b),
+			SharedValue::Number(n) => Self::Number(n),
+			SharedValue::String(s) => Self::String(s),
+			SharedValue::Array(items) => Self::Array(
+				Arc::try_unwrap(items)
+					.unwrap_or_else(|items| (*items).clone())
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+			),
+			SharedValue::Object(entries) => Self::Object(Object::from_vec(
+				Arc::try_unwrap(entries)
+					.unwrap_or_else(|entries| (*entries).clone())
+					.into_iter()
+					.map(|entry| Entry::new(entry.key, entry.value.into()))
+					.collect(),
+			)),
+		}
+	}
+}
+
+impl<'a> From<&'a SharedValue> for Value {
+	fn from(value: &'a SharedValue) -> Self {
+		match value {
+			SharedValue::Null => Self::Null,
+			SharedValue::Boolean(b) => Self::Boolean(*b),
+			SharedValue::Number(n) => Self::Number(n.clone()),
+			SharedValue::String(s) => Self::String(s.clone()),
+			SharedValue::Array(items) => Self::Array(items.iter().map(Into::into).collect()),
+			SharedValue::Object(entries) => Self::Object(Object::from_vec(
+				entries
+					.iter()
+					.map(|entry| Entry::new(entry.key.clone(), (&entry.value).into()))
+					.collect(),
+			)),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::json;
+
+	#[test]
+	fn copy_on_write() {
+		let value = json!({ "a": [1, { "b": null }], "a": "dup", "c": [] });
+		let shared = SharedValue::from(value.clone());
+
+		let mut copy = shared.clone();
+		assert!(copy.ptr_eq(&shared));
+
+		*copy.get_mut("a").unwrap().get_index_mut(0).unwrap() = SharedValue::Null;
+		assert!(!copy.ptr_eq(&shared));
+		assert!(!copy.get("a").unwrap().ptr_eq(shared.get("a").unwrap()));
+		assert!(copy.get("c").unwrap().ptr_eq(shared.get("c").unwrap()));
+		assert!(copy.get_mut("d").is_none());
+
+		assert_eq!(Value::from(&shared), value);
+		assert_eq!(Value::from(shared), value);
+		assert_eq!(
+			Value::from(copy),
+			json!({ "a": [null, { "b": null }], "a": "dup", "c": [] })
+		)
+	}
+}
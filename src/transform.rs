@@ -0,0 +1,319 @@
+use std::{cmp::Ordering, ops::ControlFlow};
+
+use crate::{
+	object::{Entry, Key},
+	Number, NumberBuf, Object, Value,
+};
+
+impl Value {
+	/// Recursively replaces every string value with the result of `f`.
+	///
+	/// Object keys are left untouched, see [`Self::map_keys`].
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::json;
+	///
+	/// let mut value = json!({ "name": "  Alice ", "tags": [" a", "b "] });
+	/// value.map_strings(|s| s.trim().to_owned());
+	/// assert_eq!(value, json!({ "name": "Alice", "tags": ["a", "b"] }));
+	/// ```
+	pub fn map_strings<S: Into<crate::String>>(&mut self, mut f: impl FnMut(&str) -> S) {
+		self.traverse_mut(|_, value| {
+			if let Self::String(s) = value {
+				*s = f(s).into()
+			}
+
+			ControlFlow::Continue(())
+		})
+	}
+
+	/// Recursively replaces every object key with the result of `f`.
+	///
+	/// Entries order is preserved.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::json;
+	///
+	/// let mut value = json!({ "Name": "Alice", "Friends": [{ "Name": "Bob" }] });
+	/// value.map_keys(str::to_lowercase);
+	/// assert_eq!(value, json!({ "name": "Alice", "friends": [{ "name": "Bob" }] }));
+	/// ```
+	pub fn map_keys<K: Into<Key>>(&mut self, mut f: impl FnMut(&str) -> K) {
+		self.traverse_mut(|_, value| {
+			if let Self::Object(object) = value {
+				*object = std::mem::take(object)
+					.into_iter()
+					.map(|Entry { key, value }| Entry::new(f(&key).into(), value))
+					.collect::<Object>()
+			}
+
+			ControlFlow::Continue(())
+		})
+	}
+
+	/// Recursively replaces every number value with the result of `f`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, NumberBuf};
+	///
+	/// let mut value = json!({ "a": 1, "b": [2, 3.5] });
+	/// value.map_numbers(|n| match n.as_i64() {
+	///   Some(i) => (i * 10).into(),
+	///   None => NumberBuf::from_number(n)
+	/// });
+	/// assert_eq!(value, json!({ "a": 10, "b": [20, 3.5] }));
+	/// ```
+	pub fn map_numbers(&mut self, mut f: impl FnMut(&Number) -> NumberBuf) {
+		self.traverse_mut(|_, value| {
+			if let Self::Number(n) = value {
+				*n = f(n)
+			}
+
+			ControlFlow::Continue(())
+		})
+	}
+}
+
+impl Value {
+	/// Recursively sorts every object entries by key.
+	///
+	/// The sort is stable: entries with the same key keep their relative
+	/// order. Contrarily to [RFC 8785](https://www.rfc-editor.org/rfc/rfc8785)
+	/// canonicalization, numbers and strings are left untouched, and arrays
+	/// are not reordered.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{Value, Parse, Print};
+	///
+	/// let (mut value, _) = Value::parse_str(r#"{"b":1.0,"a":[{"d":null,"c":null}]}"#).unwrap();
+	/// value.sort_all();
+	/// assert_eq!(
+	///   value.compact_print().to_string(),
+	///   r#"{"a":[{"c":null,"d":null}],"b":1.0}"#
+	/// );
+	/// ```
+	pub fn sort_all(&mut self) {
+		self.sort_all_inner(None::<fn(&Value, &Value) -> Ordering>)
+	}
+
+	/// Recursively sorts every object entries by key, and every array
+	/// items with the given comparator function.
+	///
+	/// Nested values are sorted before their parent, so `compare` always
+	/// sees already sorted values.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::json;
+	///
+	/// let mut value = json!({ "b": [3, 1, 2], "a": [[2, 1], [0]] });
+	/// value.sort_all_with(Ord::cmp);
+	/// assert_eq!(value, json!({ "a": [[0], [1, 2]], "b": [1, 2, 3] }));
+	/// ```
+	pub fn sort_all_with(&mut self, compare: impl FnMut(&Value, &Value) -> Ordering) {
+		self.sort_all_inner(Some(compare))
+	}
+
+	fn sort_all_inner(&mut self, mut compare: Option<impl FnMut(&Value, &Value) -> Ordering>) {
+		self.traverse_post_mut(|value| match value {
+			Self::Array(items) => {
+				if let Some(compare) = &mut compare {
+					items.sort_by(|a, b| compare(a, b))
+				}
+			}
+			Self::Object(object) => object.sort_by(|a, b| a.key.cmp(&b.key)),
+			_ => (),
+		})
+	}
+
+	/// Recursively strips `null` values from objects.
+	///
+	/// Arrays items are left untouched since removing them would shift
+	/// positions. See [`Self::strip_nulls_with`] to also strip empty arrays and
+	/// objects.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::json;
+	///
+	/// let mut value = json!({ "a": null, "b": { "c": null }, "d": [null] });
+	/// value.strip_nulls();
+	/// assert_eq!(value, json!({ "b": {}, "d": [null] }));
+	/// ```
+	pub fn strip_nulls(&mut self) {
+		self.strip_nulls_with(StripOptions::default())
+	}
+
+	/// Recursively strips `null` values from objects, with the given options.
+	///
+	/// Nested values are stripped before their parent, so an object only
+	/// containing `null` values is itself considered empty.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::{json, StripOptions};
+	///
+	/// let mut value = json!({ "a": null, "b": { "c": null }, "d": [] });
+	/// value.strip_nulls_with(StripOptions::all());
+	/// assert_eq!(value, json!({}));
+	/// ```
+	pub fn strip_nulls_with(&mut self, options: StripOptions) {
+		self.traverse_post_mut(|value| {
+			if let Self::Object(object) = value {
+				object.retain(|_, value| !options.strips(value))
+			}
+		})
+	}
+
+	/// Recursively removes duplicate items from every array, keeping the first
+	/// occurrence of each item.
+	///
+	/// Items are compared ignoring the order of object entries, see
+	/// [`array::dedup_unordered`](crate::array::dedup_unordered). Nested
+	/// arrays are deduplicated before their parent.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use json_syntax::json;
+	///
+	/// let mut value = json!({
+	///   "records": [
+	///     { "id": 0, "tags": ["a", "a"] },
+	///     { "tags": ["a"], "id": 0 }
+	///   ]
+	/// });
+	///
+	/// value.dedup_arrays();
+	/// assert_eq!(value, json!({ "records": [{ "id": 0, "tags": ["a"] }] }));
+	/// ```
+	pub fn dedup_arrays(&mut self) {
+		self.traverse_post_mut(|value| {
+			if let Self::Array(items) = value {
+				crate::array::dedup_unordered(items)
+			}
+		})
+	}
+
+	/// Recursively visits this value and all its nested values in post-order,
+	/// allowing them to be modified in place.
+	///
+	/// Nested values are visited before their parent. Like
+	/// [`Self::traverse_mut`], this does not use recursion.
+	pub(crate) fn traverse_post_mut(&mut self, mut f: impl FnMut(&mut Value)) {
+		// Values being visited, with the index of their next child. A child is
+		// moved out of its parent while it is visited, then moved back in
+		// place: arrays and objects are never rebuilt, and since keys are left
+		// untouched, object indexes stay valid.
+		let mut stack = vec![(self.take(), 0)];
+
+		loop {
+			let (value, i) = stack.last_mut().unwrap();
+			if let Some(child) = value.child_mut(*i) {
+				let child = child.take();
+				*i += 1;
+				stack.push((child, 0));
+				continue;
+			}
+
+			let (mut value, _) = stack.pop().unwrap();
+			f(&mut value);
+
+			match stack.last_mut() {
+				Some((parent, i)) => *parent.child_mut(*i - 1).unwrap() = value,
+				None => break *self = value,
+			}
+		}
+	}
+
+	/// Returns the `i`-th array item or object entry value.
+	fn child_mut(&mut self, i: usize) -> Option<&mut Value> {
+		match self {
+			Self::Array(items) => items.get_mut(i),
+			Self::Object(object) => object.as_entries_mut().get_mut(i).map(|e| &mut e.value),
+			_ => None,
+		}
+	}
+}
+
+/// Options of [`Value::strip_nulls_with`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StripOptions {
+	/// Also strip empty arrays.
+	pub empty_arrays: bool,
+
+	/// Also strip empty objects.
+	pub empty_objects: bool,
+}
+
+impl StripOptions {
+	/// Strip `null` values, empty arrays and empty objects.
+	pub fn all() -> Self {
+		Self {
+			empty_arrays: true,
+			empty_objects: true,
+		}
+	}
+
+	fn strips(&self, value: &Value) -> bool {
+		match value {
+			Value::Null => true,
+			Value::Array(items) => self.empty_arrays && items.is_empty(),
+			Value::Object(object) => self.empty_objects && object.is_empty(),
+			_ => false,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{json, Print, Value};
+
+	#[test]
+	fn sort_all_stable() {
+		let mut value = json!([{ "b": 1, "a": 2, "b": 0 }, [{ "z": null, "y": null }]]);
+		value.sort_all();
+		assert_eq!(
+			value.compact_print().to_string(),
+			r#"[{"a":2,"b":1,"b":0},[{"y":null,"z":null}]]"#
+		)
+	}
+
+	#[test]
+	fn traverse_post_mut_in_place() {
+		let mut items = Vec::with_capacity(8);
+		items.push(json!({ "b": [null, 1], "a": null }));
+		let mut value = Value::Array(items);
+
+		let mut visited = Vec::new();
+		value.traverse_post_mut(|v| visited.push(v.clone()));
+		assert_eq!(
+			visited,
+			[
+				json!(null),
+				json!(1),
+				json!([null, 1]),
+				json!(null),
+				json!({ "b": [null, 1], "a": null }),
+				json!([{ "b": [null, 1], "a": null }])
+			]
+		);
+
+		value.strip_nulls();
+		value.sort_all();
+		assert_eq!(value, json!([{ "b": [null, 1] }]));
+		assert_eq!(value.as_array_mut().unwrap().capacity(), 8);
+		assert_eq!(value[0].as_object().unwrap().index_of("b"), Some(0))
+	}
+}
@@ -107,7 +107,7 @@ impl<T: TryFromJsonObject> TryFromJsonObject for Box<T> {
 ///
 /// This error may be returned by [`TryFromJson`] and [`TryFromJsonObject`]
 /// when trying to convert a value of the wrong [`Kind`].
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Unexpected {
 	/// Expected kind(s).
 	pub expected: KindSet,
@@ -233,7 +233,7 @@ macro_rules! number_from_json {
 	};
 }
 
-number_from_json!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+number_from_json!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
 
 impl TryFromJson for String {
 	type Error = Mapped<Unexpected>;
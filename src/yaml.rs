@@ -0,0 +1,1392 @@
+//! YAML interoperability.
+//!
+//! This module provides a YAML parser producing a [`Value`] and its
+//! [`CodeMap`], and a YAML printer.
+//!
+//! The parser supports the subset of YAML 1.2 commonly found in
+//! configuration files:
+//! - block mappings and sequences (including compact nested sequences),
+//! - flow mappings and sequences (hence any JSON document),
+//! - plain, single-quoted and double-quoted scalars,
+//! - literal (`|`) and folded (`>`) block scalars, with chomping indicators,
+//! - comments, and a single document optionally delimited by `---` and
+//!   `...`.
+//!
+//! Plain scalars are resolved using the YAML 1.2 core schema (`null`,
+//! booleans, integers and floats, other scalars being strings). Mapping keys
+//! are never resolved. Anchors, aliases, tags, complex mapping keys,
+//! multi-line plain scalars and multiple documents are not supported.
+//! Collections can be nested at most [`MAX_DEPTH`] levels deep.
+//!
+//! # Example
+//!
+//! ```
+//! use json_syntax::{json, Value};
+//!
+//! let input = "\
+//! name: json-syntax # comment
+//! tags: [json, yaml]
+//! authors:
+//!   - name: Alice
+//!     year: 2023
+//! description: |
+//!   Multi-line
+//!   text.
+//! ";
+//!
+//! let (value, code_map) = Value::from_yaml_str(input).unwrap();
+//! assert_eq!(value, json!({
+//!   "name": "json-syntax",
+//!   "tags": ["json", "yaml"],
+//!   "authors": [{ "name": "Alice", "year": 2023 }],
+//!   "description": "Multi-line\ntext.\n"
+//! }));
+//!
+//! // The code map gives the position of each fragment in the YAML source.
+//! let span = code_map[3].span; // the `"name"` entry value.
+//! assert_eq!(&input[span.range()], "json-syntax");
+//!
+//! assert_eq!(Value::from_yaml_str(&value.to_yaml_string()).unwrap().0, value);
+//! ```
+use core::fmt;
+
+use locspan::Span;
+
+use crate::{object::Key, CodeMap, NumberBuf, Object, Value};
+
+/// Maximum nesting depth of collections accepted by the parser.
+pub const MAX_DEPTH: usize = 128;
+
+/// YAML parsing error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Error {
+	/// Unexpected character or end of input.
+	///
+	/// The first parameter is the byte index at which the error occurred.
+	Unexpected(usize, Option<char>),
+
+	/// Invalid indentation.
+	///
+	/// The parameter is the byte index of the badly indented line content.
+	InvalidIndentation(usize),
+
+	/// Invalid escape sequence in a double-quoted scalar.
+	InvalidEscape(Span),
+
+	/// Infinite or NaN number, that cannot be represented in JSON.
+	NonFiniteNumber(Span),
+
+	/// YAML feature not supported by this parser.
+	///
+	/// The first parameter is the byte index at which the feature is used.
+	Unsupported(usize, &'static str),
+
+	/// Collections nested more than [`MAX_DEPTH`] levels deep.
+	///
+	/// The parameter is the byte index of the collection exceeding the limit.
+	TooDeep(usize),
+}
+
+impl Error {
+	/// Returns the byte index at which the error occurred.
+	pub fn position(&self) -> usize {
+		match self {
+			Self::Unexpected(i, _)
+			| Self::InvalidIndentation(i)
+			| Self::Unsupported(i, _)
+			| Self::TooDeep(i) => *i,
+			Self::InvalidEscape(span) | Self::NonFiniteNumber(span) => span.start(),
+		}
+	}
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Unexpected(_, Some(c)) => write!(f, "unexpected character `{c}`"),
+			Self::Unexpected(_, None) => write!(f, "unexpected end of file"),
+			Self::InvalidIndentation(_) => write!(f, "invalid indentation"),
+			Self::InvalidEscape(_) => write!(f, "invalid escape sequence"),
+			Self::NonFiniteNumber(_) => write!(f, "non-finite number"),
+			Self::Unsupported(_, feature) => write!(f, "unsupported YAML feature: {feature}"),
+			Self::TooDeep(_) => write!(f, "collections nested too deep"),
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+impl Value {
+	/// Parses a YAML document.
+	///
+	/// See the [`yaml`](crate::yaml) module documentation for the supported
+	/// YAML subset.
+	pub fn from_yaml_str(input: &str) -> Result<(Self, CodeMap), Error> {
+		Parser::new(input)?.parse()
+	}
+
+	/// Returns a YAML printer for this value.
+	///
+	/// Collections are printed in block style. Strings are printed as plain
+	/// scalars when possible, and double-quoted otherwise.
+	pub fn yaml_print(&self) -> YamlPrint<'_> {
+		YamlPrint(self)
+	}
+
+	/// Prints this value as a YAML document.
+	pub fn to_yaml_string(&self) -> String {
+		self.yaml_print().to_string()
+	}
+}
+
+/// Source line.
+#[derive(Debug, Clone, Copy)]
+struct Line {
+	/// Byte index of the line beginning.
+	begin: usize,
+
+	/// Byte index of the line end, excluding the line break.
+	end: usize,
+
+	/// Indentation (column of the line content).
+	indent: usize,
+
+	/// Byte index of the line content.
+	///
+	/// Lines starting with a sequence item or a mapping entry are split, the
+	/// content then starting after the `-` indicator or the key.
+	start: usize,
+}
+
+/// Removes the comment at the end of a line content, if any, and trailing
+/// whitespaces.
+///
+/// Quotes only open a quoted scalar at the start of a token: inside a plain
+/// scalar they are regular characters.
+fn strip_comment(text: &str) -> &str {
+	let mut quote = None;
+	let mut escaped = false;
+	let mut prev = None;
+	let mut token_start = true;
+	let mut flow_depth = 0usize;
+	let mut chars = text.char_indices().peekable();
+
+	while let Some((i, c)) = chars.next() {
+		let followed_by_space = chars.peek().map_or(true, |(_, n)| n.is_whitespace());
+		match quote {
+			Some('"') if escaped => escaped = false,
+			Some('"') if c == '\\' => escaped = true,
+			Some(q) if c == q => quote = None,
+			Some(_) => (),
+			None => match c {
+				'#' if prev.map_or(true, char::is_whitespace) => return text[..i].trim_end(),
+				c if c.is_whitespace() => (),
+				'"' | '\'' if token_start => {
+					quote = Some(c);
+					token_start = false
+				}
+				'[' | '{' if token_start => flow_depth += 1,
+				']' | '}' if flow_depth > 0 => {
+					flow_depth -= 1;
+					token_start = false
+				}
+				',' if flow_depth > 0 => token_start = true,
+				':' => {
+					token_start = followed_by_space
+						|| (flow_depth > 0 && prev.is_some_and(|p| "\"']}".contains(p)))
+				}
+				'-' | '?' if token_start && followed_by_space => (),
+				_ => token_start = false,
+			},
+		}
+
+		prev = Some(c)
+	}
+
+	text.trim_end()
+}
+
+fn is_sequence_item(text: &str) -> bool {
+	text == "-" || text.starts_with("- ") || text.starts_with("-\t")
+}
+
+fn is_document_marker(text: &str) -> bool {
+	text == "---" || text == "..." || text.starts_with("--- ") || text.starts_with("... ")
+}
+
+/// Mapping entry header.
+struct EntryHeader {
+	key: Key,
+	key_span: Span,
+
+	/// Byte index of the value on the same line, if any.
+	value: Option<usize>,
+}
+
+struct Parser<'a> {
+	input: &'a str,
+	lines: Vec<Line>,
+	pos: usize,
+	code_map: CodeMap,
+
+	/// End of the last parsed fragment.
+	end: usize,
+
+	/// Number of collections being parsed.
+	depth: usize,
+}
+
+impl<'a> Parser<'a> {
+	fn new(input: &'a str) -> Result<Self, Error> {
+		let mut lines = Vec::new();
+		let mut begin = 0;
+
+		for raw in input.split('\n') {
+			let end = begin + raw.strip_suffix('\r').unwrap_or(raw).len();
+			let text = &input[begin..end];
+			let indent = text.len() - text.trim_start_matches(' ').len();
+
+			if text[indent..].starts_with('\t') && !strip_comment(text.trim_start()).is_empty() {
+				return Err(Error::InvalidIndentation(begin + indent));
+			}
+
+			lines.push(Line {
+				begin,
+				end,
+				indent,
+				start: begin + indent,
+			});
+
+			begin += raw.len() + 1
+		}
+
+		Ok(Self {
+			input,
+			lines,
+			pos: 0,
+			code_map: CodeMap::default(),
+			end: 0,
+			depth: 0,
+		})
+	}
+
+	fn parse(mut self) -> Result<(Value, CodeMap), Error> {
+		self.skip_blank();
+
+		// Directives.
+		while let Some(line) = self.current() {
+			if self.content(&line).starts_with('%') {
+				self.pos += 1;
+				self.skip_blank()
+			} else {
+				break;
+			}
+		}
+
+		// Document start marker.
+		if let Some(line) = self.current() {
+			let text = self.content(&line);
+			if line.indent == 0 && (text == "---" || text.starts_with("--- ")) {
+				let rest = text[3..].trim_start();
+				if rest.is_empty() {
+					self.pos += 1
+				} else {
+					self.split(line.start + text.len() - rest.len())
+				}
+			}
+		}
+
+		let value = self.parse_node(0)?;
+
+		// Document end marker.
+		self.skip_blank();
+		if let Some(line) = self.current() {
+			let text = self.content(&line);
+			if line.indent == 0 && text == "..." {
+				self.pos += 1;
+				self.skip_blank()
+			}
+		}
+
+		match self.current() {
+			Some(line) => {
+				let text = self.content(&line);
+				if line.indent == 0 && is_document_marker(text) {
+					Err(Error::Unsupported(line.start, "multiple documents"))
+				} else if line.indent > 0 {
+					Err(Error::InvalidIndentation(line.start))
+				} else {
+					Err(Error::Unexpected(line.start, text.chars().next()))
+				}
+			}
+			None => Ok((value, self.code_map)),
+		}
+	}
+
+	fn current(&self) -> Option<Line> {
+		self.lines.get(self.pos).copied()
+	}
+
+	fn content(&self, line: &Line) -> &'a str {
+		strip_comment(&self.input[line.start..line.end])
+	}
+
+	fn is_marker(&self, line: &Line) -> bool {
+		line.indent == 0 && is_document_marker(self.content(line))
+	}
+
+	fn skip_blank(&mut self) {
+		while let Some(line) = self.current() {
+			if self.content(&line).is_empty() {
+				self.pos += 1
+			} else {
+				break;
+			}
+		}
+	}
+
+	/// Splits the current line so that its content starts at `start`.
+	fn split(&mut self, start: usize) {
+		let line = &mut self.lines[self.pos];
+		line.indent += start - line.start;
+		line.start = start;
+	}
+
+	fn begin_fragment(&mut self, start: usize) -> usize {
+		self.code_map.reserve(start)
+	}
+
+	/// Begins a collection fragment, checking the nesting depth.
+	fn begin_collection(&mut self, start: usize) -> Result<usize, Error> {
+		if self.depth == MAX_DEPTH {
+			return Err(Error::TooDeep(start));
+		}
+
+		self.depth += 1;
+		Ok(self.begin_fragment(start))
+	}
+
+	fn end_collection(&mut self, i: usize, end: usize) {
+		self.depth -= 1;
+		self.end_fragment(i, end)
+	}
+
+	fn end_fragment(&mut self, i: usize, end: usize) {
+		let len = self.code_map.len();
+		let entry = self.code_map.get_mut(i).unwrap();
+		entry.span.set_end(end);
+		entry.volume = len - i;
+		self.end = end
+	}
+
+	fn null_at(&mut self, position: usize) -> Value {
+		let i = self.begin_fragment(position);
+		self.end_fragment(i, position);
+		Value::Null
+	}
+
+	/// Parses a block node whose lines are indented by at least
+	/// `min_indent`.
+	fn parse_node(&mut self, min_indent: usize) -> Result<Value, Error> {
+		self.skip_blank();
+		let line = match self.current() {
+			Some(line) if line.indent >= min_indent && !self.is_marker(&line) => line,
+			_ => return Ok(self.null_at(self.end)),
+		};
+
+		if is_sequence_item(self.content(&line)) {
+			self.parse_sequence(line)
+		} else if self.entry_header(&line)?.is_some() {
+			self.parse_mapping(line)
+		} else {
+			self.parse_inline(min_indent)
+		}
+	}
+
+	/// Parses a block sequence whose first item is on the given line.
+	fn parse_sequence(&mut self, first: Line) -> Result<Value, Error> {
+		let indent = first.indent;
+		let i = self.begin_collection(first.start)?;
+		let mut items = Vec::new();
+
+		loop {
+			self.skip_blank();
+			let Some(line) = self.current() else { break };
+			if line.indent < indent || self.is_marker(&line) {
+				break;
+			}
+
+			if line.indent > indent {
+				return Err(Error::InvalidIndentation(line.start));
+			}
+
+			let text = self.content(&line);
+			if !is_sequence_item(text) {
+				break;
+			}
+
+			let rest = text[1..].trim_start();
+			self.end = line.start + 1;
+			if rest.is_empty() {
+				self.pos += 1
+			} else {
+				self.split(line.start + text.len() - rest.len())
+			}
+
+			items.push(self.parse_node(indent + 1)?)
+		}
+
+		self.end_collection(i, self.end);
+		Ok(Value::Array(items))
+	}
+
+	/// Parses a block mapping whose first entry is on the given line.
+	fn parse_mapping(&mut self, first: Line) -> Result<Value, Error> {
+		let indent = first.indent;
+		let i = self.begin_collection(first.start)?;
+		let mut object = Object::new();
+
+		loop {
+			self.skip_blank();
+			let Some(line) = self.current() else { break };
+			if line.indent < indent || self.is_marker(&line) {
+				break;
+			}
+
+			if line.indent > indent {
+				return Err(Error::InvalidIndentation(line.start));
+			}
+
+			let header = self
+				.entry_header(&line)?
+				.ok_or_else(|| Error::Unexpected(line.start, self.content(&line).chars().next()))?;
+
+			let entry_i = self.begin_fragment(header.key_span.start());
+			let key_i = self.begin_fragment(header.key_span.start());
+			self.end_fragment(key_i, header.key_span.end());
+
+			let value = match header.value {
+				Some(start) => {
+					self.split(start);
+					self.parse_inline(indent + 1)?
+				}
+				None => {
+					self.pos += 1;
+					self.skip_blank();
+					match self.current() {
+						Some(next)
+							if next.indent == indent
+								&& is_sequence_item(self.content(&next))
+								&& !self.is_marker(&next) =>
+						{
+							self.parse_sequence(next)?
+						}
+						_ => self.parse_node(indent + 1)?,
+					}
+				}
+			};
+
+			self.end_fragment(entry_i, self.end);
+			object.push(header.key, value);
+		}
+
+		self.end_collection(i, self.end);
+		Ok(Value::Object(object))
+	}
+
+	/// Checks if the given line starts with a mapping entry, and returns its
+	/// header.
+	fn entry_header(&self, line: &Line) -> Result<Option<EntryHeader>, Error> {
+		let text = self.content(line);
+
+		let (key, key_len) = match text.chars().next() {
+			None | Some('[' | '{') => return Ok(None),
+			Some('?') if text.len() == 1 || text[1..].starts_with([' ', '\t']) => {
+				return Err(Error::Unsupported(line.start, "complex mapping keys"))
+			}
+			Some('"' | '\'') => {
+				let (key, end) = self.parse_quoted(line.start)?;
+				if end > line.start + text.len() {
+					return Ok(None);
+				}
+
+				(key, end - line.start)
+			}
+			Some(_) => {
+				let Some(len) = find_mapping_indicator(text) else {
+					return Ok(None);
+				};
+
+				let key = text[..len].trim_end();
+				if key.is_empty() {
+					return Ok(None);
+				}
+
+				check_plain_start(key, line.start)?;
+				(key.to_owned(), key.len())
+			}
+		};
+
+		let after = text[key_len..].trim_start();
+		if !after.starts_with(':') || !(after.len() == 1 || after[1..].starts_with([' ', '\t'])) {
+			return Ok(None);
+		}
+
+		let value = after[1..].trim_start();
+		Ok(Some(EntryHeader {
+			key: key.as_str().into(),
+			key_span: Span::new(line.start, line.start + key_len),
+			value: if value.is_empty() {
+				None
+			} else {
+				Some(line.start + text.len() - value.len())
+			},
+		}))
+	}
+
+	/// Parses a node starting on the current line that is not a block
+	/// collection.
+	///
+	/// Block scalar lines must be indented by at least `min_indent`.
+	fn parse_inline(&mut self, min_indent: usize) -> Result<Value, Error> {
+		let Some(line) = self.current() else {
+			return Ok(self.null_at(self.end));
+		};
+
+		let text = self.content(&line);
+		match text.chars().next() {
+			None => {
+				let value = self.null_at(line.start);
+				self.pos += 1;
+				Ok(value)
+			}
+			Some('|' | '>') => self.parse_block_scalar(line, min_indent),
+			Some('[' | '{') => {
+				let mut position = line.start;
+				let value = self.parse_flow(&mut position)?;
+				self.end_line(position)?;
+				Ok(value)
+			}
+			Some('"' | '\'') => {
+				let i = self.begin_fragment(line.start);
+				let (s, end) = self.parse_quoted(line.start)?;
+				self.end_fragment(i, end);
+				self.end_line(end)?;
+				Ok(Value::String(s.into()))
+			}
+			_ => {
+				check_plain_start(text, line.start)?;
+				if let Some(len) = find_mapping_indicator(text) {
+					return Err(Error::Unexpected(line.start + len, Some(':')));
+				}
+
+				let span = Span::new(line.start, line.start + text.len());
+				let value = resolve_plain(text, span)?;
+				let i = self.begin_fragment(span.start());
+				self.end_fragment(i, span.end());
+				self.pos += 1;
+				Ok(value)
+			}
+		}
+	}
+
+	/// Checks that the rest of the line containing `position` is blank, and
+	/// moves to the next line.
+	fn end_line(&mut self, position: usize) -> Result<(), Error> {
+		while self.lines[self.pos].end < position {
+			self.pos += 1
+		}
+
+		let rest = &self.input[position..self.lines[self.pos].end];
+		let trimmed = rest.trim_start();
+		if !trimmed.is_empty() && (!trimmed.starts_with('#') || trimmed.len() == rest.len()) {
+			return Err(Error::Unexpected(
+				position + rest.len() - trimmed.len(),
+				trimmed.chars().next(),
+			));
+		}
+
+		self.pos += 1;
+		Ok(())
+	}
+
+	/// Parses a block scalar whose header is on the given line.
+	fn parse_block_scalar(&mut self, line: Line, min_indent: usize) -> Result<Value, Error> {
+		let header = self.content(&line);
+		let folded = header.starts_with('>');
+
+		let mut keep = None;
+		for (i, c) in header[1..].char_indices() {
+			match c {
+				'-' => keep = Some(false),
+				'+' => keep = Some(true),
+				'1'..='9' => {
+					return Err(Error::Unsupported(
+						line.start + 1 + i,
+						"block scalar indentation indicators",
+					))
+				}
+				c => return Err(Error::Unexpected(line.start + 1 + i, Some(c))),
+			}
+		}
+
+		let i = self.begin_fragment(line.start);
+		self.end = line.start + header.len();
+		self.pos += 1;
+
+		let mut content_indent = None;
+		let mut lines: Vec<&str> = Vec::new();
+		while let Some(line) = self.current() {
+			let raw = &self.input[line.begin..line.end];
+			if raw.trim().is_empty() {
+				lines.push("");
+				self.pos += 1;
+				continue;
+			}
+
+			let indent = *content_indent.get_or_insert(line.indent);
+			if line.indent < min_indent || line.indent < indent {
+				break;
+			}
+
+			lines.push(&raw[indent..]);
+			self.end = line.end;
+			self.pos += 1
+		}
+
+		let trailing = lines.iter().rev().take_while(|l| l.is_empty()).count();
+		let body = &lines[..lines.len() - trailing];
+
+		let mut result = String::new();
+		if folded {
+			let mut prev: Option<&str> = None;
+			for line in body {
+				if line.is_empty() {
+					result.push('\n')
+				} else {
+					if let Some(prev) = prev.filter(|p| !p.is_empty()) {
+						if prev.starts_with([' ', '\t']) || line.starts_with([' ', '\t']) {
+							result.push('\n')
+						} else {
+							result.push(' ')
+						}
+					}
+
+					result.push_str(line)
+				}
+
+				prev = Some(line)
+			}
+		} else {
+			result = body.join("\n")
+		}
+
+		match keep {
+			Some(false) => (),
+			None => {
+				if !body.is_empty() {
+					result.push('\n')
+				}
+			}
+			Some(true) => {
+				if !body.is_empty() {
+					result.push('\n')
+				}
+
+				for _ in 0..trailing {
+					result.push('\n')
+				}
+			}
+		}
+
+		self.end_fragment(i, self.end);
+		Ok(Value::String(result.into()))
+	}
+
+	/// Parses a quoted scalar starting at the given position, returning its
+	/// value and end position.
+	fn parse_quoted(&self, start: usize) -> Result<(String, usize), Error> {
+		let quote = match self.input[start..].chars().next() {
+			Some(q @ ('"' | '\'')) => q,
+			c => return Err(Error::Unexpected(start, c)),
+		};
+		let mut result = String::new();
+		let mut chars = self.input[start + 1..].char_indices().peekable();
+
+		while let Some((i, c)) = chars.next() {
+			let position = start + 1 + i;
+			match c {
+				'\'' if quote == '\'' => {
+					if chars.peek().map(|(_, c)| *c) == Some('\'') {
+						chars.next();
+						result.push('\'')
+					} else {
+						return Ok((result, position + 1));
+					}
+				}
+				'"' if quote == '"' => return Ok((result, position + 1)),
+				'\\' if quote == '"' => {
+					let Some((_, e)) = chars.next() else { break };
+					let simple = match e {
+						'0' => Some('\0'),
+						'a' => Some('\x07'),
+						'b' => Some('\x08'),
+						't' | '\t' => Some('\t'),
+						'n' => Some('\n'),
+						'v' => Some('\x0b'),
+						'f' => Some('\x0c'),
+						'r' => Some('\r'),
+						'e' => Some('\x1b'),
+						' ' => Some(' '),
+						'"' => Some('"'),
+						'/' => Some('/'),
+						'\\' => Some('\\'),
+						'N' => Some('\u{85}'),
+						'_' => Some('\u{a0}'),
+						'L' => Some('\u{2028}'),
+						'P' => Some('\u{2029}'),
+						_ => None,
+					};
+
+					match simple {
+						Some(c) => result.push(c),
+						None => {
+							let len = match e {
+								'x' => 2,
+								'u' => 4,
+								'U' => 8,
+								'\n' | '\r' => {
+									// Escaped line break.
+									while let Some((_, ' ' | '\t' | '\n' | '\r')) = chars.peek() {
+										chars.next();
+									}
+									continue;
+								}
+								_ => {
+									return Err(Error::InvalidEscape(Span::new(
+										position,
+										position + 1 + e.len_utf8(),
+									)))
+								}
+							};
+
+							let hex_start = position + 2;
+							let hex = self.input.get(hex_start..hex_start + len);
+							let c = hex
+								.filter(|h| h.bytes().all(|b| b.is_ascii_hexdigit()))
+								.and_then(|h| u32::from_str_radix(h, 16).ok())
+								.and_then(char::from_u32)
+								.ok_or(Error::InvalidEscape(Span::new(
+									position,
+									hex_start + len,
+								)))?;
+
+							for _ in 0..len {
+								chars.next();
+							}
+
+							result.push(c)
+						}
+					}
+				}
+				'\n' | '\r' => {
+					// Line folding.
+					let trimmed = result.trim_end_matches([' ', '\t']).len();
+					result.truncate(trimmed);
+
+					let mut breaks = if c == '\n' { 1 } else { 0 };
+					while let Some((_, c @ (' ' | '\t' | '\n' | '\r'))) = chars.peek().copied() {
+						if c == '\n' {
+							breaks += 1
+						}
+						chars.next();
+					}
+
+					if breaks > 1 {
+						for _ in 1..breaks {
+							result.push('\n')
+						}
+					} else {
+						result.push(' ')
+					}
+				}
+				c => result.push(c),
+			}
+		}
+
+		Err(Error::Unexpected(self.input.len(), None))
+	}
+
+	fn skip_flow_whitespaces(&self, position: &mut usize) {
+		let mut after_whitespace = true;
+		while let Some(c) = self.input[*position..].chars().next() {
+			match c {
+				' ' | '\t' | '\r' | '\n' => {
+					after_whitespace = true;
+					*position += 1
+				}
+				'#' if after_whitespace => {
+					*position += self.input[*position..]
+						.find('\n')
+						.unwrap_or(self.input.len() - *position)
+				}
+				_ => break,
+			}
+		}
+	}
+
+	fn parse_flow(&mut self, position: &mut usize) -> Result<Value, Error> {
+		self.skip_flow_whitespaces(position);
+		let start = *position;
+
+		match self.input[start..].chars().next() {
+			Some('[') => {
+				let i = self.begin_collection(start)?;
+				*position += 1;
+				let mut items = Vec::new();
+
+				loop {
+					self.skip_flow_whitespaces(position);
+					if self.input[*position..].starts_with(']') {
+						*position += 1;
+						break;
+					}
+
+					items.push(self.parse_flow(position)?);
+					self.flow_separator(position, ']')?
+				}
+
+				self.end_collection(i, *position);
+				Ok(Value::Array(items))
+			}
+			Some('{') => {
+				let i = self.begin_collection(start)?;
+				*position += 1;
+				let mut object = Object::new();
+
+				loop {
+					self.skip_flow_whitespaces(position);
+					if self.input[*position..].starts_with('}') {
+						*position += 1;
+						break;
+					}
+
+					let key_start = *position;
+					let key = match self.input[key_start..].chars().next() {
+						Some('"' | '\'') => {
+							let (key, end) = self.parse_quoted(key_start)?;
+							*position = end;
+							key
+						}
+						_ => {
+							let text = self.flow_plain(key_start);
+							if text.is_empty() {
+								return Err(Error::Unexpected(
+									key_start,
+									self.input[key_start..].chars().next(),
+								));
+							}
+
+							check_plain_start(text, key_start)?;
+							*position += text.len();
+							text.to_owned()
+						}
+					};
+
+					let entry_i = self.begin_fragment(key_start);
+					let key_i = self.begin_fragment(key_start);
+					self.end_fragment(key_i, *position);
+
+					self.skip_flow_whitespaces(position);
+					let value = if self.input[*position..].starts_with(':') {
+						*position += 1;
+						self.parse_flow(position)?
+					} else {
+						self.null_at(*position)
+					};
+
+					self.end_fragment(entry_i, self.end);
+					object.push(key.as_str().into(), value);
+					self.flow_separator(position, '}')?
+				}
+
+				self.end_collection(i, *position);
+				Ok(Value::Object(object))
+			}
+			Some('"' | '\'') => {
+				let i = self.begin_fragment(start);
+				let (s, end) = self.parse_quoted(start)?;
+				*position = end;
+				self.end_fragment(i, end);
+				Ok(Value::String(s.into()))
+			}
+			Some(c @ (']' | '}' | ',')) => Err(Error::Unexpected(start, Some(c))),
+			None => Err(Error::Unexpected(start, None)),
+			Some(_) => {
+				let text = self.flow_plain(start);
+				check_plain_start(text, start)?;
+				let span = Span::new(start, start + text.len());
+				let value = resolve_plain(text, span)?;
+				*position = span.end();
+				let i = self.begin_fragment(start);
+				self.end_fragment(i, span.end());
+				Ok(value)
+			}
+		}
+	}
+
+	/// Parses the separator after a flow collection item.
+	fn flow_separator(&self, position: &mut usize, close: char) -> Result<(), Error> {
+		self.skip_flow_whitespaces(position);
+		match self.input[*position..].chars().next() {
+			Some(',') => {
+				*position += 1;
+				Ok(())
+			}
+			Some(c) if c == close => Ok(()),
+			c => Err(Error::Unexpected(*position, c)),
+		}
+	}
+
+	/// Returns the plain scalar starting at the given position in a flow
+	/// collection.
+	fn flow_plain(&self, start: usize) -> &'a str {
+		let text = &self.input[start..];
+		let mut end = text.len();
+		let mut prev = None;
+
+		for (i, c) in text.char_indices() {
+			let stop = match c {
+				',' | '[' | ']' | '{' | '}' | '\n' | '\r' => true,
+				'#' => prev.map_or(true, char::is_whitespace),
+				':' => text[i + 1..]
+					.chars()
+					.next()
+					.map_or(true, |n| n.is_whitespace() || ",[]{}".contains(n)),
+				_ => false,
+			};
+
+			if stop {
+				end = i;
+				break;
+			}
+
+			prev = Some(c)
+		}
+
+		text[..end].trim_end()
+	}
+}
+
+/// Finds the `:` mapping value indicator in a plain key.
+fn find_mapping_indicator(text: &str) -> Option<usize> {
+	text.char_indices()
+		.find(|(i, c)| {
+			*c == ':'
+				&& text[i + 1..]
+					.chars()
+					.next()
+					.map_or(true, |n| n == ' ' || n == '\t')
+		})
+		.map(|(i, _)| i)
+}
+
+fn check_plain_start(text: &str, position: usize) -> Result<(), Error> {
+	match text.chars().next() {
+		Some('&') => Err(Error::Unsupported(position, "anchors")),
+		Some('*') => Err(Error::Unsupported(position, "aliases")),
+		Some('!') => Err(Error::Unsupported(position, "tags")),
+		Some(c @ ('%' | '@' | '`' | '|' | '>')) => Err(Error::Unexpected(position, Some(c))),
+		_ => Ok(()),
+	}
+}
+
+/// Resolves a plain scalar using the YAML 1.2 core schema.
+fn resolve_plain(text: &str, span: Span) -> Result<Value, Error> {
+	match text {
+		"" | "~" | "null" | "Null" | "NULL" => Ok(Value::Null),
+		"true" | "True" | "TRUE" => Ok(Value::Boolean(true)),
+		"false" | "False" | "FALSE" => Ok(Value::Boolean(false)),
+		_ => {
+			let unsigned = text.strip_prefix(['-', '+']).unwrap_or(text);
+			if matches!(
+				unsigned,
+				".inf" | ".Inf" | ".INF" | ".nan" | ".NaN" | ".NAN"
+			) {
+				return Err(Error::NonFiniteNumber(span));
+			}
+
+			Ok(match yaml_number(text) {
+				Some(n) => Value::Number(n),
+				None => Value::String(text.into()),
+			})
+		}
+	}
+}
+
+/// Parses a YAML 1.2 core schema number into a JSON number.
+fn yaml_number(text: &str) -> Option<NumberBuf> {
+	let radix = |digits: &str, radix: u32| {
+		u128::from_str_radix(digits, radix)
+			.ok()
+			.filter(|_| !digits.starts_with('+'))
+			.map(|n| NumberBuf::new(n.to_string().as_bytes().into()).unwrap())
+	};
+
+	if let Some(hex) = text.strip_prefix("0x") {
+		return radix(hex, 16);
+	}
+
+	if let Some(octal) = text.strip_prefix("0o") {
+		return radix(octal, 8);
+	}
+
+	let (sign, body) = match text.strip_prefix('-') {
+		Some(body) => ("-", body),
+		None => ("", text.strip_prefix('+').unwrap_or(text)),
+	};
+
+	let (mantissa, exponent) = match body.split_once(['e', 'E']) {
+		Some((mantissa, exponent)) => {
+			let digits = exponent.strip_prefix(['-', '+']).unwrap_or(exponent);
+			if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+				return None;
+			}
+
+			(mantissa, Some(exponent))
+		}
+		None => (body, None),
+	};
+
+	let (integer, fraction) = match mantissa.split_once('.') {
+		Some((integer, fraction)) => (integer, Some(fraction)),
+		None => (mantissa, None),
+	};
+
+	let all_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+	if !all_digits(integer) || !fraction.map_or(true, all_digits) {
+		return None;
+	}
+
+	if integer.is_empty() && fraction.map_or(true, str::is_empty) {
+		return None;
+	}
+
+	let mut result = sign.to_owned();
+	match integer.trim_start_matches('0') {
+		"" => result.push('0'),
+		integer => result.push_str(integer),
+	}
+
+	if let Some(fraction) = fraction.filter(|f| !f.is_empty()) {
+		result.push('.');
+		result.push_str(fraction)
+	}
+
+	if let Some(exponent) = exponent {
+		result.push('e');
+		result.push_str(exponent)
+	}
+
+	NumberBuf::new(result.as_bytes().into()).ok()
+}
+
+/// YAML printer.
+///
+/// Returned by [`Value::yaml_print`].
+pub struct YamlPrint<'a>(&'a Value);
+
+impl<'a> fmt::Display for YamlPrint<'a> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write_node(f, self.0, 0, false)
+	}
+}
+
+fn is_block_collection(value: &Value) -> bool {
+	match value {
+		Value::Array(items) => !items.is_empty(),
+		Value::Object(object) => !object.is_empty(),
+		_ => false,
+	}
+}
+
+/// Writes a block node.
+///
+/// If `inline` is true, the indentation of the first line has already been
+/// written.
+fn write_node(f: &mut fmt::Formatter, value: &Value, indent: usize, inline: bool) -> fmt::Result {
+	match value {
+		Value::Array(items) if !items.is_empty() => {
+			for (i, item) in items.iter().enumerate() {
+				if i > 0 || !inline {
+					write_indent(f, indent)?
+				}
+
+				f.write_str("- ")?;
+				write_item(f, item, indent + 2)?
+			}
+
+			Ok(())
+		}
+		Value::Object(object) if !object.is_empty() => {
+			for (i, entry) in object.iter().enumerate() {
+				if i > 0 || !inline {
+					write_indent(f, indent)?
+				}
+
+				write_string(f, &entry.key)?;
+				f.write_str(":")?;
+				if is_block_collection(&entry.value) {
+					f.write_str("\n")?;
+					write_node(f, &entry.value, indent + 2, false)?
+				} else {
+					f.write_str(" ")?;
+					write_item(f, &entry.value, indent + 2)?
+				}
+			}
+
+			Ok(())
+		}
+		_ => {
+			if !inline {
+				write_indent(f, indent)?
+			}
+
+			write_item(f, value, indent)
+		}
+	}
+}
+
+/// Writes a node after a sequence indicator or mapping key.
+fn write_item(f: &mut fmt::Formatter, value: &Value, indent: usize) -> fmt::Result {
+	match value {
+		Value::Null => f.write_str("null\n"),
+		Value::Boolean(b) => writeln!(f, "{b}"),
+		Value::Number(n) => writeln!(f, "{n}"),
+		Value::String(s) => {
+			write_string(f, s)?;
+			f.write_str("\n")
+		}
+		Value::Array(items) if items.is_empty() => f.write_str("[]\n"),
+		Value::Object(object) if object.is_empty() => f.write_str("{}\n"),
+		value => write_node(f, value, indent, true),
+	}
+}
+
+fn write_indent(f: &mut fmt::Formatter, indent: usize) -> fmt::Result {
+	for _ in 0..indent {
+		f.write_str(" ")?
+	}
+
+	Ok(())
+}
+
+fn write_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+	if is_plain_safe(s) {
+		f.write_str(s)
+	} else {
+		crate::print::string_literal(s, f)
+	}
+}
+
+/// Checks if the given string can be printed as a plain scalar, being read
+/// back as the same string.
+fn is_plain_safe(s: &str) -> bool {
+	!s.is_empty()
+		&& s.trim() == s
+		&& !s.starts_with([
+			'-', '?', ':', ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '\'', '"', '%',
+			'@', '`',
+		]) && !s.starts_with("...") // document end marker (`---` is covered by `-`)
+		&& !s.contains(": ")
+		&& !s.contains(" #")
+		&& !s.ends_with(':')
+		&& !s.chars().any(char::is_control)
+		&& matches!(
+			resolve_plain(s, Span::new(0, s.len())),
+			Ok(Value::String(_))
+		)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::json;
+
+	fn parse(input: &str) -> Value {
+		Value::from_yaml_str(input).unwrap().0
+	}
+
+	#[test]
+	fn scalars() {
+		assert_eq!(
+			parse("[~, null, true, False, 012, -0x1F, 0o17, +1.5, .5, 1., 'it''s', \"\\u00e9\\n\", a b]"),
+			json!([null, null, true, false, 12, "-0x1F", 15, 1.5, 0.5, 1, "it's", "é\n", "a b"])
+		);
+
+		assert_eq!(parse("1E-3").as_number().unwrap().as_str(), "1e-3");
+		assert_eq!(parse(""), Value::Null);
+		assert_eq!(parse("--- 12\n..."), json!(12));
+	}
+
+	#[test]
+	fn block_collections() {
+		let input = "\
+a:
+- 1
+- - 2
+  - 3
+b:
+  c: {x: 1, \"y\": [2]}
+  d: []
+e:
+  - f: 1
+    g:
+      - null
+";
+		assert_eq!(
+			parse(input),
+			json!({
+				"a": [1, [2, 3]],
+				"b": { "c": { "x": 1, "y": [2] }, "d": [] },
+				"e": [{ "f": 1, "g": [null] }]
+			})
+		)
+	}
+
+	#[test]
+	fn block_scalars() {
+		let input = "\
+a: |
+  x
+   y
+
+b: >-
+  x
+  y
+
+  z
+c: |+
+  x
+
+d: end
+";
+		assert_eq!(
+			parse(input),
+			json!({ "a": "x\n y\n", "b": "x y\nz", "c": "x\n\n", "d": "end" })
+		)
+	}
+
+	#[test]
+	fn quotes_in_plain_scalars() {
+		assert_eq!(parse("\\ \": #"), json!({ "\\ \"": null }));
+		assert_eq!(parse(",': #"), json!({ ",'": null }));
+		assert_eq!(parse("a: it's # comment"), json!({ "a": "it's" }));
+		assert_eq!(parse("a: 'b' # c"), json!({ "a": "b" }));
+		assert_eq!(parse("[a, 'b # c', \"d\"] # e"), json!(["a", "b # c", "d"]));
+	}
+
+	#[test]
+	fn code_map() {
+		use crate::code_map::Entry;
+		let input = "a: [1, x]\nb:\n  - true\n";
+		let (_, code_map) = Value::from_yaml_str(input).unwrap();
+		assert_eq!(
+			code_map.as_slice(),
+			[
+				Entry::new(Span::new(0, 21), 10),
+				Entry::new(Span::new(0, 9), 5),
+				Entry::new(Span::new(0, 1), 1),
+				Entry::new(Span::new(3, 9), 3),
+				Entry::new(Span::new(4, 5), 1),
+				Entry::new(Span::new(7, 8), 1),
+				Entry::new(Span::new(10, 21), 4),
+				Entry::new(Span::new(10, 11), 1),
+				Entry::new(Span::new(15, 21), 2),
+				Entry::new(Span::new(17, 21), 1),
+			]
+		)
+	}
+
+	#[test]
+	fn errors() {
+		assert_eq!(
+			Value::from_yaml_str("a: &x 1").unwrap_err(),
+			(Error::Unsupported(3, "anchors"))
+		);
+		assert_eq!(
+			Value::from_yaml_str("a: 1\n  b: 2").unwrap_err(),
+			(Error::InvalidIndentation(7))
+		);
+		assert_eq!(
+			Value::from_yaml_str("a: .inf").unwrap_err(),
+			(Error::NonFiniteNumber(Span::new(3, 7)))
+		);
+		assert_eq!(
+			Value::from_yaml_str("a: 1\n---\nb: 2").unwrap_err(),
+			(Error::Unsupported(5, "multiple documents"))
+		);
+		assert_eq!(
+			Value::from_yaml_str("a: b: c").unwrap_err(),
+			(Error::Unexpected(4, Some(':')))
+		);
+		assert_eq!(
+			Value::from_yaml_str(&"[".repeat(200_000)).unwrap_err(),
+			(Error::TooDeep(MAX_DEPTH))
+		);
+		assert_eq!(
+			Value::from_yaml_str(&"- ".repeat(200_000)).unwrap_err(),
+			(Error::TooDeep(2 * MAX_DEPTH))
+		);
+		assert!(Value::from_yaml_str(&("[".repeat(MAX_DEPTH) + &"]".repeat(MAX_DEPTH))).is_ok());
+	}
+
+	#[test]
+	fn print() {
+		let value = json!({
+			"a": [1, [2, 3], { "b": null, "c": {} }],
+			"true": "true",
+			"d": { "e": "multi\nline", "f": " padded", "g": "plain text" },
+			"h": []
+		});
+
+		let yaml = value.to_yaml_string();
+		assert_eq!(
+			yaml,
+			"\
+a:
+  - 1
+  - - 2
+    - 3
+  - b: null
+    c: {}
+\"true\": \"true\"
+d:
+  e: \"multi\\nline\"
+  f: \" padded\"
+  g: plain text
+h: []
+"
+		);
+
+		assert_eq!(parse(&yaml), value)
+	}
+
+	#[test]
+	fn print_document_markers() {
+		let value = json!({ "... a": "... a", "--- b": ["--- b", "...", "a ... b"] });
+		let yaml = value.to_yaml_string();
+		assert_eq!(
+			yaml,
+			"\
+\"... a\": \"... a\"
+\"--- b\":
+  - \"--- b\"
+  - \"...\"
+  - a ... b
+"
+		);
+
+		assert_eq!(parse(&yaml), value)
+	}
+}
@@ -181,3 +181,92 @@ fn macro_14() {
 		]))
 	)
 }
+
+#[test]
+fn macro_15() {
+	let key = "a";
+	let some = Some(true);
+	let none: Option<Value> = None;
+	let value = json! {
+		{ key: null, "b"?: some, "c"?: none, "d"?: Some(json!([1])) }
+	};
+
+	assert_eq!(
+		value,
+		Value::Object(Object::from_vec(vec![
+			Entry::new("a".into(), Value::Null),
+			Entry::new("b".into(), Value::Boolean(true)),
+			Entry::new("d".into(), Value::Array(vec![json!(1)]))
+		]))
+	)
+}
+
+#[test]
+fn json_match_01() {
+	use json_syntax::{json_match, pattern::MatchError, Kind, KindSet, Unexpected};
+	let value = json! {
+		{ "id": 1, "name": "a", "tags": [ "x", "y" ], "extra": null }
+	};
+
+	let result = json_match!(&value, {
+		"id": id as u8,
+		"name": name as String,
+		"tags": [_, second],
+		"extra": extra as Option<bool>
+	} => (id, name, second.clone(), extra));
+	assert_eq!(result, Ok((1, "a".to_string(), json!("y"), None)));
+
+	assert_eq!(
+		json_match!(value, { "tags": [_] } => ()),
+		Err(MatchError::Length {
+			expected: 1,
+			found: 2
+		})
+	);
+	assert_eq!(
+		json_match!(value, { "tags": [_, _, _, ..] } => ()),
+		Err(MatchError::MinLength {
+			expected: 3,
+			found: 2
+		})
+	);
+	assert_eq!(
+		json_match!(value, { "missing": _ } => ()),
+		Err(MatchError::MissingKey("missing"))
+	);
+	assert_eq!(
+		json_match!(value, { "name": n as u64 } => n),
+		Err(MatchError::Unexpected(Unexpected {
+			expected: KindSet::NUMBER,
+			found: Kind::String
+		}))
+	);
+	assert_eq!(json_match!(value, v as &Object => v.len()), Ok(4));
+	assert_eq!(
+		json_match!(json!(300), n as u8 => n),
+		Err(MatchError::OutOfBounds("u8"))
+	)
+}
+
+#[test]
+fn match_json_01() {
+	use json_syntax::{json_match, match_json, pattern::MatchError};
+	let value = json!({ "kind": "point", "x": -1, "y": 2, "hidden": null });
+
+	assert_eq!(
+		json_match!(value, { "kind": "line" } => ()),
+		Err(MatchError::Mismatch(json!("line")))
+	);
+	assert_eq!(
+		json_match!(value, { "hidden": null, "x": -1 } => ()),
+		Ok(())
+	);
+
+	let result = match_json!(value,
+		{ "kind": "line" } => 0,
+		{ "kind": "point", "x": x as i32, "y": y as i32 } => x + y,
+		_ => -100,
+	);
+	assert_eq!(result, Some(1));
+	assert_eq!(match_json!(json!([]), [_, ..] => ()), None)
+}
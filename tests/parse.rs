@@ -14,6 +14,28 @@ fn test<P: Clone + AsRef<Path> + Debug>(filename: P, options: Options) {
 	Value::parse_str_with(&input, options).expect("parse error");
 }
 
+#[test]
+fn empty_object_code_map() {
+	let (_, code_map) = Value::parse_str(r#"{ "a": {}, "b": [{}] }"#).unwrap();
+	let entries: Vec<_> = code_map
+		.iter()
+		.map(|(_, entry)| (entry.span.range(), entry.volume))
+		.collect();
+	assert_eq!(
+		entries,
+		[
+			(0..22, 8),
+			(2..9, 3),
+			(2..5, 1),
+			(7..9, 1),
+			(11..20, 4),
+			(11..14, 1),
+			(16..20, 2),
+			(17..19, 1),
+		]
+	)
+}
+
 #[test]
 fn flexible_i_object_key_lone_2nd_surrogate() {
 	test(
@@ -2593,3 +2615,19 @@ fn y_structure_whitespace_array() {
 fn y_issue_1() {
 	test("tests/inputs/y_issue_1.json", Options::strict())
 }
+
+#[test]
+fn parse_str_in_reuse() {
+	let mut code_map = json_syntax::CodeMap::default();
+
+	for i in 0..3 {
+		let input = format!("[{i}, ");
+		assert!(Value::parse_str_in(&input, Options::strict(), &mut code_map).is_err());
+
+		let input = format!(" {{ \"a{i}\": true }}");
+		let value = Value::parse_str_in(&input, Options::strict(), &mut code_map).unwrap();
+		assert_eq!(value, json_syntax::json!({ format!("a{i}"): true }));
+		assert_eq!(code_map.len(), 4);
+		assert_eq!(code_map.first().unwrap().span.start(), 1)
+	}
+}
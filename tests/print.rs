@@ -92,3 +92,318 @@ fn print_14() {
 		"{\n  \"a\": [\n    null,\n    []\n  ],\n  \"b\": [ 14 ]\n}"
 	)
 }
+
+#[test]
+fn print_seq_01() {
+	use json_syntax::print::{print_seq, Options, SeqFormat};
+	let rows = [json! { [ null, 1 ] }, json! { { "a": true } }];
+
+	let mut output = String::new();
+	print_seq(&rows, &mut output, &Options::inline(), SeqFormat::Lines).unwrap();
+	assert_eq!(output, "[ null, 1 ]\n{ \"a\": true }\n");
+
+	let mut output = String::new();
+	print_seq(&rows, &mut output, &Options::inline(), SeqFormat::Array).unwrap();
+	assert_eq!(output, "[ [ null, 1 ], { \"a\": true } ]");
+
+	let mut output = String::new();
+	print_seq(&rows, &mut output, &Options::pretty(), SeqFormat::Array).unwrap();
+	assert_eq!(
+		output,
+		json! { [ [ null, 1 ], { "a": true } ] }
+			.pretty_print()
+			.to_string()
+	);
+}
+
+#[test]
+fn print_seq_02() {
+	use json_syntax::print::{print_seq, Options, SeqFormat};
+
+	let mut output = String::new();
+	print_seq([], &mut output, &Options::compact(), SeqFormat::Array).unwrap();
+	assert_eq!(output, "[]");
+
+	let mut output = String::new();
+	print_seq([], &mut output, &Options::compact(), SeqFormat::Lines).unwrap();
+	assert_eq!(output, "");
+}
+
+#[test]
+fn print_comments_01() {
+	use json_syntax::print::Options;
+	let value = json! { { "a": [ null, true, "comment me" ], "b": [ 1 ] } };
+	let printed = value.print_with_comments(Options::pretty(), |i| match i {
+		0 => Some("root".to_string()),
+		1 => Some("entry\non two lines".to_string()),
+		6 => Some("item".to_string()),
+		10 => Some("inline".to_string()),
+		_ => None,
+	});
+
+	assert_eq!(
+		printed.to_string(),
+		"// root\n{\n  // entry\n  // on two lines\n  \"a\": [\n    null,\n    true,\n    // item\n    \"comment me\"\n  ],\n  \"b\": [ /* inline */ 1 ]\n}"
+	)
+}
+
+#[test]
+fn print_depth_limits() {
+	use json_syntax::print::{Limit, Options};
+	let value = json! { { "a": [ 1, [ 2, 3 ] ], "b": { "c": { "d": 4 } } } };
+
+	let mut options = Options::pretty();
+	options.depth_limits = vec![Limit::Always, Limit::Always];
+	options.array_limit = Some(Limit::Never);
+	options.object_limit = Some(Limit::Never);
+
+	assert_eq!(
+		value.print_with(options).to_string(),
+		"{\n  \"a\": [\n    1,\n    [ 2, 3 ]\n  ],\n  \"b\": {\n    \"c\": { \"d\": 4 }\n  }\n}"
+	)
+}
+
+#[test]
+fn print_sort_keys() {
+	use json_syntax::print::{Indent, Options};
+	let value = json! { { "b": { "d": 0, "c": 1 }, "a": null } };
+	let options = Options::pretty()
+		.indent(Indent::Tabs(1))
+		.sort_keys(true)
+		.build();
+
+	assert_eq!(
+		value.print_with(options.clone()).to_string(),
+		"{\n\t\"a\": null,\n\t\"b\": {\n\t\t\"c\": 1,\n\t\t\"d\": 0\n\t}\n}"
+	);
+
+	let printed = value.print_with_comments(options, |i| match i {
+		7 => Some("c".to_string()),
+		10 => Some("a".to_string()),
+		_ => None,
+	});
+
+	assert_eq!(
+		printed.to_string(),
+		"{\n\t// a\n\t\"a\": null,\n\t\"b\": {\n\t\t// c\n\t\t\"c\": 1,\n\t\t\"d\": 0\n\t}\n}"
+	)
+}
+
+#[test]
+fn print_align_colons() {
+	use json_syntax::print::Options;
+	let value = json! { { "a": 1, "bbb": { "cc": 2, "d": 3 }, "e": { "f": 4 } } };
+	let options = Options::pretty().object_align_colons(true);
+
+	assert_eq!(
+		value.print_with(options.clone()).to_string(),
+		"{\n  \"a\"  : 1,\n  \"bbb\": {\n    \"cc\": 2,\n    \"d\" : 3\n  },\n  \"e\"  : { \"f\": 4 }\n}"
+	);
+
+	assert_eq!(
+		value.print_with_comments(options, |_| None).to_string(),
+		value
+			.print_with(Options::pretty().object_align_colons(true))
+			.to_string()
+	)
+}
+
+#[test]
+fn print_preview() {
+	use json_syntax::print::{Options, PreviewLimits};
+	let value = json! { { "a": [ "abcdefghijklmnop", [ 1, 2 ], {} ], "b": { "c": [] } } };
+	let limits = PreviewLimits::default()
+		.max_depth(2)
+		.max_array_items(2)
+		.max_string_len(8);
+
+	assert_eq!(
+		value.preview_with(Options::pretty(), limits).to_string(),
+		"{\n  \"a\": [\n    \"abcdefgh…\",\n    […],\n    …\n  ],\n  \"b\": { \"c\": [] }\n}"
+	);
+
+	assert_eq!(
+		value
+			.preview_with(Options::pretty(), PreviewLimits::default())
+			.to_string(),
+		value.pretty_print().to_string()
+	)
+}
+
+#[test]
+fn print_printed_len() {
+	use json_syntax::print::{Indent, Options};
+	let value = json! {
+		{
+			"a": [ null, true, false, 1.5e3, [], {} ],
+			"ünïcödé": "\u{0} \"quoted\" ∞",
+			"b": { "c": [ [ 1, 2, 3 ], { "d": "e" } ] }
+		}
+	};
+
+	for options in [
+		Options::pretty(),
+		Options::compact(),
+		Options::inline(),
+		Options::pretty()
+			.indent(Indent::Tabs(1))
+			.sort_keys(true)
+			.object_align_colons(true),
+		Options::inline().unquoted_keys(true),
+	] {
+		assert_eq!(
+			value.printed_len(&options),
+			value.print_with(options).to_string().len()
+		)
+	}
+
+	assert_eq!(json! { "∞" }.printed_len(&Options::pretty()), 5)
+}
+
+#[test]
+fn printer_reuse() {
+	use json_syntax::print::{Options, Printer};
+	let values = [
+		json! { { "a": [ null, [ 1, 2 ] ], "b": {} } },
+		json! { "foo" },
+		json! { [ { "c": true } ] },
+	];
+
+	let mut printer = Printer::new(Options::pretty());
+	let mut output = String::new();
+	for value in &values {
+		output.clear();
+		printer.print_into(&mut output, value);
+		assert_eq!(output, value.pretty_print().to_string())
+	}
+}
+
+#[test]
+fn print_unquoted_keys() {
+	use json_syntax::print::Options;
+	let value = json! { { "a": 1, "_b2": { "c-d": 2, "3e": 3 }, "": null } };
+	let options = Options::inline().unquoted_keys(true);
+
+	assert_eq!(
+		value.print_with(options.clone()).to_string(),
+		"{ a: 1, _b2: { \"c-d\": 2, \"3e\": 3 }, \"\": null }"
+	);
+	assert_eq!(
+		value.printed_len(&options),
+		value.print_with(options).to_string().len()
+	)
+}
+
+#[test]
+fn print_normalize_numbers() {
+	use json_syntax::{print::Options, Parse, Value};
+	let (value, _) = Value::parse_str("[1E+05, 2.5e-007, 3E0, 4e10]").unwrap();
+	let options = Options::inline().normalize_numbers(true);
+
+	assert_eq!(
+		value.print_with(options.clone()).to_string(),
+		"[ 1e5, 2.5e-7, 3e0, 4e10 ]"
+	);
+	assert_eq!(value.printed_len(&options), 26);
+	assert_eq!(
+		value.inline_print().to_string(),
+		"[ 1E+05, 2.5e-007, 3E0, 4e10 ]"
+	)
+}
+
+#[cfg(feature = "canonicalize")]
+#[test]
+fn print_shortest_floats() {
+	use json_syntax::{print::Options, Parse, Value};
+	let (value, _) = Value::parse_str("[4.50, 1E30, 0.0020, 7]").unwrap();
+	let options = Options::compact().shortest_floats(true);
+
+	assert_eq!(
+		value.print_with(options.clone()).to_string(),
+		"[4.5,1e+30,0.002,7]"
+	);
+	assert_eq!(value.printed_len(&options), 19);
+	assert_eq!(value.compact_print().to_string(), "[4.50,1E30,0.0020,7]")
+}
+
+#[test]
+fn print_to_vec() {
+	use json_syntax::print::Options;
+	let value = json! { { "a": [ "ü", null ], "b": {} } };
+
+	assert_eq!(
+		value.to_vec_with(&Options::pretty()),
+		value.pretty_print().to_string().into_bytes()
+	);
+
+	let mut buffer = b"data: ".to_vec();
+	value.write_to_vec(&mut buffer, &Options::compact());
+	assert_eq!(buffer, b"data: {\"a\":[\"\xc3\xbc\",null],\"b\":{}}")
+}
+
+#[test]
+fn print_stable() {
+	use json_syntax::{print::Options, Parse, Value};
+	let (a, _) =
+		Value::parse_str(r#"{ "b": [ 1E+2, [] ], "a": { "d": null, "c": "x" } }"#).unwrap();
+	let (b, _) = Value::parse_str(r#"{"a":{"c":"x","d":null},"b":[1e2,[]]}"#).unwrap();
+
+	let expected = "{\n  \"a\": {\n    \"c\": \"x\",\n    \"d\": null\n  },\n  \"b\": [\n    1e2,\n    []\n  ]\n}\n";
+	assert_eq!(a.print_with(Options::stable()).to_string(), expected);
+	assert_eq!(b.print_with(Options::stable()).to_string(), expected);
+	assert_eq!(a.printed_len(&Options::stable()), expected.len());
+	assert_eq!(a.to_vec_with(&Options::stable()), expected.as_bytes())
+}
+
+#[cfg(feature = "compact-debug")]
+#[test]
+fn print_compact_debug() {
+	let value = json!({ "a": [1, { "b": null }] });
+	assert_eq!(format!("{value:?}"), "{\"a\":[1,{\"b\":null}]}");
+	assert_eq!(
+		format!("{:?}", value.as_object().unwrap()),
+		"{\"a\":[1,{\"b\":null}]}"
+	)
+}
+
+#[test]
+fn print_seq_limits() {
+	use json_syntax::print::{print_seq, Limit, Options, SeqFormat};
+	let rows = [json! { [ 1 ] }, json! { { "a": [] } }];
+
+	let options = Options::pretty().array_limit(Some(Limit::Never));
+	let mut output = String::new();
+	print_seq(&rows, &mut output, &options, SeqFormat::Array).unwrap();
+	assert_eq!(output, "[ [ 1 ], { \"a\": [] } ]");
+
+	let options = Options::inline().depth_limits(vec![Limit::Always, Limit::Always]);
+	let mut output = String::new();
+	print_seq(&rows, &mut output, &options, SeqFormat::Array).unwrap();
+	assert_eq!(
+		output,
+		json! { [ [ 1 ], { "a": [] } ] }
+			.print_with(options)
+			.to_string()
+	);
+
+	let options = Options::pretty().depth_limits(vec![Limit::Never]);
+	let mut output = String::new();
+	print_seq(&rows, &mut output, &options, SeqFormat::Array).unwrap();
+	assert_eq!(output, "[ [ 1 ], { \"a\": [] } ]");
+
+	// Expanded items expand the array.
+	for options in [
+		Options::pretty().depth_limits(vec![Limit::Never, Limit::Always]),
+		Options::inline().object_limit(Some(Limit::Item(0))),
+	] {
+		let mut output = String::new();
+		print_seq(&rows, &mut output, &options, SeqFormat::Array).unwrap();
+		assert_eq!(
+			output,
+			json! { [ [ 1 ], { "a": [] } ] }
+				.print_with(options)
+				.to_string()
+		);
+		assert!(output.starts_with("[\n"))
+	}
+}
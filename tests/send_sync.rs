@@ -0,0 +1,198 @@
+use json_syntax::{
+	analysis, array, code_map, kind, object, parse, patch, pointer, print, CodeMap, Kind, KindSet,
+	NumberBuf, Object, Unexpected, Value,
+};
+use std::sync::Arc;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn send_sync() {
+	assert_send_sync::<Value>();
+	assert_send_sync::<Object>();
+	assert_send_sync::<NumberBuf>();
+	assert_send_sync::<json_syntax::String>();
+	assert_send_sync::<json_syntax::FragmentRef>();
+	assert_send_sync::<json_syntax::SubFragments>();
+	assert_send_sync::<json_syntax::Traverse>();
+	assert_send_sync::<json_syntax::Accessor>();
+	assert_send_sync::<json_syntax::MappedValue>();
+	assert_send_sync::<json_syntax::MappedRef>();
+	assert_send_sync::<json_syntax::NumericOrd>();
+	assert_send_sync::<json_syntax::SharedValue>();
+	assert_send_sync::<json_syntax::ValueBuilder>();
+	assert_send_sync::<json_syntax::BuildError>();
+	assert_send_sync::<json_syntax::StripOptions>();
+	assert_send_sync::<json_syntax::Unordered<Value>>();
+	assert_send_sync::<json_syntax::TryIntoNumberError<u8>>();
+	assert_send_sync::<json_syntax::NumberType<u8>>();
+	assert_send_sync::<Unexpected>();
+	assert_send_sync::<Kind>();
+	assert_send_sync::<KindSet>();
+	assert_send_sync::<kind::KindSetIter>();
+	assert_send_sync::<kind::KindSetConjunction>();
+	assert_send_sync::<kind::KindSetDisjunction>();
+}
+
+#[test]
+fn send_sync_object() {
+	assert_send_sync::<object::Key>();
+	assert_send_sync::<object::Entry>();
+	assert_send_sync::<object::EntrySpans>();
+	assert_send_sync::<object::Duplicate<object::Entry>>();
+	assert_send_sync::<object::DedupPolicy>();
+	assert_send_sync::<object::Indexes>();
+	assert_send_sync::<object::Iter>();
+	assert_send_sync::<object::IterMut>();
+	assert_send_sync::<object::IterMapped>();
+	assert_send_sync::<object::Keys>();
+	assert_send_sync::<object::IntoKeys>();
+	assert_send_sync::<object::IntoValues>();
+	assert_send_sync::<object::Values>();
+	assert_send_sync::<object::ValuesMut>();
+	assert_send_sync::<object::ValuesIter>();
+	assert_send_sync::<object::ValuesIterMut>();
+	assert_send_sync::<object::ValuesWithIndex>();
+	assert_send_sync::<object::ValuesMutWithIndex>();
+	assert_send_sync::<object::Entries>();
+	assert_send_sync::<object::EntriesWithIndex>();
+	assert_send_sync::<object::MappedEntries>();
+	assert_send_sync::<object::MappedEntriesWithIndex>();
+	assert_send_sync::<object::MappedValues>();
+	assert_send_sync::<object::MappedValuesWithIndex>();
+	assert_send_sync::<object::RemovedByInsertion>();
+	assert_send_sync::<object::RemovedByInsertFront>();
+	assert_send_sync::<object::RemovedEntries<str>>();
+	assert_send_sync::<object::ObjectEntry>();
+	assert_send_sync::<object::OccupiedEntry>();
+	assert_send_sync::<object::VacantEntry>();
+	assert_send_sync::<object::ObjectBuilder>();
+	assert_send_sync::<object::SortedObject>();
+	assert_send_sync::<object::MergeOptions>();
+	assert_send_sync::<object::ArrayMergeStrategy>();
+	assert_send_sync::<object::PathSyntax>();
+	assert_send_sync::<object::GetNumberError>();
+	assert_send_sync::<array::IterMapped>();
+	assert_send_sync::<array::GroupError>();
+	assert_send_sync::<array::Missing>();
+	assert_send_sync::<array::ItemKindMismatch>();
+}
+
+#[test]
+fn send_sync_code_map() {
+	assert_send_sync::<CodeMap>();
+	assert_send_sync::<code_map::Entry>();
+	assert_send_sync::<code_map::Mapped<Value>>();
+	assert_send_sync::<code_map::LineIndex>();
+	assert_send_sync::<code_map::Iter>();
+	assert_send_sync::<code_map::IntoIter>();
+}
+
+#[test]
+fn send_sync_parse_print() {
+	assert_send_sync::<parse::Options>();
+	assert_send_sync::<parse::Error>();
+	assert_send_sync::<parse::Context>();
+	assert_send_sync::<parse::Parser<parse::StrChars>>();
+	assert_send_sync::<parse::Fragment>();
+	assert_send_sync::<print::Options>();
+	assert_send_sync::<print::Indent>();
+	assert_send_sync::<print::IndentBy>();
+	assert_send_sync::<print::Limit>();
+	assert_send_sync::<print::Padding>();
+	assert_send_sync::<print::SeqFormat>();
+	assert_send_sync::<print::Size>();
+	assert_send_sync::<print::Spaces>();
+	assert_send_sync::<print::Printed<Value>>();
+	assert_send_sync::<print::Printer>();
+	assert_send_sync::<print::Preview>();
+	assert_send_sync::<print::PreviewLimits>();
+	assert_send_sync::<print::InvalidOutput>();
+	assert_send_sync::<print::CompactDebug<Value>>();
+	assert_send_sync::<print::PrintedWithComments<fn(&str) -> Option<String>>>();
+}
+
+#[test]
+fn send_sync_pointer_patch_analysis() {
+	assert_send_sync::<pointer::CreateIntermediates>();
+	assert_send_sync::<pointer::InsertError>();
+	assert_send_sync::<pointer::TakeMode>();
+	assert_send_sync::<pointer::IterWith>();
+	assert_send_sync::<pointer::SelectWhere<fn(&str, &Value) -> bool>>();
+	assert_send_sync::<patch::Patch>();
+	assert_send_sync::<patch::Operation>();
+	assert_send_sync::<patch::DiffOptions>();
+	assert_send_sync::<patch::Difference>();
+	assert_send_sync::<patch::DifferenceKind>();
+	assert_send_sync::<json_syntax::pattern::MatchError>();
+	assert_send_sync::<json_syntax::schema::Schema>();
+	assert_send_sync::<json_syntax::schema::InferOptions>();
+	assert_send_sync::<analysis::KeyFold>();
+	assert_send_sync::<analysis::ConfusableKeys>();
+	assert_send_sync::<analysis::KeyIssue>();
+	assert_send_sync::<analysis::KeyPolicy>();
+	assert_send_sync::<analysis::NumberIssue>();
+	assert_send_sync::<analysis::UnsafeNumber>();
+}
+
+#[test]
+fn send_sync_features() {
+	#[cfg(feature = "canonicalize")]
+	assert_send_sync::<print::CanonicalPrinted>();
+	#[cfg(feature = "serde")]
+	{
+		assert_send_sync::<json_syntax::Serializer>();
+		assert_send_sync::<json_syntax::KeySerializer>();
+		assert_send_sync::<json_syntax::StringNumberSerializer>();
+		assert_send_sync::<json_syntax::SerializeArray>();
+		assert_send_sync::<json_syntax::SerializeMap>();
+		assert_send_sync::<json_syntax::SerializeTupleVariant>();
+		assert_send_sync::<json_syntax::SerializeStructVariant>();
+		assert_send_sync::<json_syntax::SerializeError>();
+		assert_send_sync::<json_syntax::DeserializeError>();
+		assert_send_sync::<json_syntax::compat::serde_json::Error>();
+	}
+	#[cfg(feature = "rayon")]
+	{
+		assert_send_sync::<object::ParIter>();
+		assert_send_sync::<object::ParIterMut>();
+	}
+	#[cfg(feature = "arbitrary")]
+	{
+		assert_send_sync::<json_syntax::arbitrary::Options>();
+		assert_send_sync::<json_syntax::arbitrary::KindWeights>();
+	}
+	#[cfg(feature = "cbor")]
+	assert_send_sync::<json_syntax::cbor::Error>();
+	#[cfg(feature = "msgpack")]
+	{
+		assert_send_sync::<json_syntax::msgpack::Options>();
+		assert_send_sync::<json_syntax::msgpack::Error>();
+	}
+	#[cfg(feature = "yaml")]
+	{
+		assert_send_sync::<json_syntax::yaml::Error>();
+		assert_send_sync::<json_syntax::yaml::YamlPrint>();
+	}
+	#[cfg(any(feature = "ariadne", feature = "codespan-reporting"))]
+	assert_send_sync::<json_syntax::diagnostic::Diagnostic>();
+}
+
+#[test]
+fn shared() {
+	let mut a = json_syntax::json!({ "a": [0, 1] }).into_shared();
+	let b = a.clone();
+
+	Value::make_mut(&mut a)
+		.as_object_mut()
+		.unwrap()
+		.push("b".into(), Value::Null);
+	assert_eq!(*b, json_syntax::json!({ "a": [0, 1] }));
+	assert_eq!(*a, json_syntax::json!({ "a": [0, 1], "b": null }));
+
+	let mut c = Arc::new(b.as_object().unwrap().clone());
+	let d = c.clone();
+	Object::make_mut(&mut c).push("c".into(), Value::Null);
+	assert_eq!(d.len(), 1);
+	assert_eq!(c.len(), 2);
+}